@@ -20,6 +20,7 @@
 @group(2) @binding(10) var<uniform> slot_2_color: vec4<f32>;
 
 @group(2) @binding(11) var<uniform> slot_indexes: u32;
+@group(2) @binding(12) var<uniform> daylight: f32;
 
 struct Vertex {
     @builtin(instance_index) instance_index: u32,
@@ -164,7 +165,7 @@ fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
     var out = color;
 
 #ifdef IS_LIT
-    let light = f32(max(sunlight, torchlight)) / 15.0 + 0.05;
+    let light = max(f32(sunlight) * daylight, f32(torchlight)) / 15.0 + 0.05;
     out = out * light;
 #endif
 
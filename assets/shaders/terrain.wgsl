@@ -5,6 +5,7 @@
 @group(2) @binding(2) var<uniform> color: vec4<f32>;
 @group(2) @binding(3) var<uniform> texture_count: u32;
 @group(2) @binding(4) var<uniform> terrain_slice_y: u32;
+@group(2) @binding(5) var<uniform> daylight: f32;
 
 struct Vertex {
     @builtin(instance_index) instance_index: u32,
@@ -61,7 +62,7 @@ fn vertex(vertex: Vertex) -> VertexOutput {
 
     let torch = vertex.light & 0xf;
     let sun = (vertex.light >> 4) & 0xf;
-    out.light = f32(max(sun, torch)) / 15.0 + 0.1;
+    out.light = max(f32(sun) * daylight, f32(torch)) / 15.0 + 0.1;
 
     return out;
 }
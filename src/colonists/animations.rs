@@ -32,6 +32,8 @@ pub enum AnimClip {
     SwingPick,
     SwingHammer,
     SwingAxe,
+    /// the colonist is laid down by rotating them, this only holds them still
+    LieDown,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -75,6 +77,7 @@ pub fn colonist_animations(
                 AnimClip::SwingPick => animations.swing_pick.clone_weak(),
                 AnimClip::SwingHammer => animations.swing_hammer.clone_weak(),
                 AnimClip::SwingAxe => animations.swing_axe.clone_weak(),
+                AnimClip::LieDown => animations.base.clone_weak(),
             };
 
             let one_shot = match animator.clip {
@@ -85,6 +88,7 @@ pub fn colonist_animations(
                 AnimClip::PickUp => true,
                 AnimClip::SwingHammer => false,
                 AnimClip::SwingAxe => false,
+                AnimClip::LieDown => false,
             };
 
             animator.prev_clip = animator.clip;
@@ -4,14 +4,12 @@ use bevy::{
     ecs::{
         component::Component,
         entity::Entity,
-        system::{Commands, EntityCommands, Query, Res},
+        system::{Commands, EntityCommands, Query},
     },
     reflect::Reflect,
 };
 use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
 
-use crate::ui::GameSpeed;
-
 pub trait TaskBuilder: Send + Sync {
     fn insert(&self, cmd: &mut EntityCommands);
     fn remove(&self, cmd: &mut EntityCommands);
@@ -43,6 +41,8 @@ pub struct ActorRef(pub Entity);
 pub struct Behavior {
     pub label: String,
     pub tree: BehaviorNodeState,
+    /// urgent behaviors are not dropped when an enemy comes close
+    pub is_urgent: bool,
 }
 
 impl Behavior {
@@ -50,8 +50,14 @@ impl Behavior {
         Self {
             label: String::from(label),
             tree: BehaviorNodeState::new(tree),
+            is_urgent: false,
         }
     }
+
+    pub fn urgent(mut self) -> Self {
+        self.is_urgent = true;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -382,14 +388,9 @@ impl BehaviorNodeState {
 
 pub fn behavior_system(
     mut cmd: Commands,
-    game_speed: Res<GameSpeed>,
     mut q_behaviors: Query<(Entity, &ActorRef, &mut Behavior, &mut TaskState)>,
     q_has_behavior: Query<&HasBehavior>,
 ) {
-    if game_speed.is_paused {
-        return;
-    }
-
     for (entity, ActorRef(actor), mut behavior, mut state) in q_behaviors.iter_mut() {
         let Ok(has_behavior) = q_has_behavior.get(*actor) else {
             println!("Detached behavior detected? Despawning it.");
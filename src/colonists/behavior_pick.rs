@@ -6,38 +6,56 @@ use bevy::{
         component::Component,
         entity::Entity,
         query::{With, Without},
-        system::{Commands, Query},
+        system::{Commands, Query, Res},
     },
     reflect::Reflect,
 };
 use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
 use bevy_trait_query::One;
 
+use crate::{items::Ingredient, TimeOfDay};
+
 use super::{
-    Actor, ActorRef, AnimClip, BehaviorNode, HasBehavior, ItemTag, Path, Score, ScorerBuilder,
-    Scorers, TaskAnimate, TaskCheckHasItem, TaskFindNearestItem, TaskItemPickUp, TaskMoveTo,
-    TaskState,
+    Actor, ActorRef, AnimClip, Behavior, BehaviorNode, DailySchedule, Downed, Drafted, HasBehavior,
+    ItemTag, Path, Score, ScorerBuilder, Scorers, TaskAnimate, TaskCheckHasIngredient,
+    TaskCheckHasItem, TaskFindIngredient, TaskFindNearestItem, TaskItemPickUp, TaskMeleeAttack,
+    TaskMoveTo, TaskPickTarget, TaskState,
 };
 
 #[derive(Reflect, Component, Default, InspectorOptions)]
 #[reflect(InspectorOptions)]
 pub struct Blackboard {
     pub job: Option<Entity>,
-    pub bed: u8,
+    pub bed: Option<Entity>,
     pub move_goals: Vec<[u32; 3]>,
     pub primary_goal: Option<[u32; 3]>,
     pub item: Option<Entity>,
     pub path: Option<Path>,
     pub target_block: Option<[u32; 3]>,
+    /// the actor being fought
+    pub target: Option<Entity>,
     pub job_invalid: bool,
 }
 
 pub fn behavior_pick_system(
     mut cmd: Commands,
+    time_of_day: Res<TimeOfDay>,
     q_scores: Query<(&Score, One<&dyn ScorerBuilder>)>,
-    q_actors: Query<(Entity, &Scorers), (With<Actor>, Without<HasBehavior>)>,
+    q_actors: Query<
+        (Entity, &Scorers, Option<&DailySchedule>),
+        (
+            With<Actor>,
+            Without<HasBehavior>,
+            Without<Downed>,
+            Without<Drafted>,
+        ),
+    >,
 ) {
-    for (actor, scorers) in q_actors.iter() {
+    let hour = time_of_day.hour();
+
+    for (actor, scorers, schedule) in q_actors.iter() {
+        let activity = schedule.map(|s| s.at(hour));
+
         let mut high_score = 0.;
         let mut high_score_builder = None;
 
@@ -47,6 +65,14 @@ pub fn behavior_pick_system(
                 continue;
             };
 
+            // behaviors kept to a part of the day wait for it
+            if builder
+                .activity()
+                .is_some_and(|a| activity.is_some_and(|b| a != b))
+            {
+                continue;
+            }
+
             if score.0 > high_score {
                 high_score = score.0;
                 high_score_builder = Some(builder);
@@ -59,26 +85,33 @@ pub fn behavior_pick_system(
         }
 
         let builder = high_score_builder.unwrap();
-        let behavior = builder.build();
-
-        let b_entity = cmd
-            .spawn((
-                Name::new(behavior.label.clone()),
-                Blackboard::default(),
-                TaskState::Success,
-                ActorRef(actor),
-                behavior,
-            ))
-            .id();
-
-        let mut cmd_actor = cmd.entity(actor);
-
-        cmd_actor.insert(HasBehavior {
-            behavior_entity: b_entity,
-        });
+
+        assign_behavior(&mut cmd, actor, builder.build(), Blackboard::default());
     }
 }
 
+/// Starts the behavior on the actor, as if it had scored the highest.
+pub fn assign_behavior(
+    cmd: &mut Commands,
+    actor: Entity,
+    behavior: Behavior,
+    blackboard: Blackboard,
+) {
+    let b_entity = cmd
+        .spawn((
+            Name::new(behavior.label.clone()),
+            blackboard,
+            TaskState::Success,
+            ActorRef(actor),
+            behavior,
+        ))
+        .id();
+
+    cmd.entity(actor).insert(HasBehavior {
+        behavior_entity: b_entity,
+    });
+}
+
 pub fn tree_aquire_item(tags: Vec<ItemTag>) -> BehaviorNode {
     BehaviorNode::Try(
         Box::new(BehaviorNode::Task(Arc::new(TaskCheckHasItem(tags.clone())))),
@@ -90,3 +123,26 @@ pub fn tree_aquire_item(tags: Vec<ItemTag>) -> BehaviorNode {
         ])),
     )
 }
+
+pub fn tree_aquire_ingredient(ingredient: Ingredient) -> BehaviorNode {
+    BehaviorNode::Try(
+        Box::new(BehaviorNode::Task(Arc::new(TaskCheckHasIngredient(
+            ingredient.clone(),
+        )))),
+        Box::new(BehaviorNode::Sequence(vec![
+            BehaviorNode::Task(Arc::new(TaskFindIngredient(ingredient))),
+            BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+            BehaviorNode::Task(Arc::new(TaskItemPickUp)),
+            BehaviorNode::Task(Arc::new(TaskAnimate(AnimClip::PickUp))),
+        ])),
+    )
+}
+
+/// Closes in on the nearest enemy within `max_distance` and fights it.
+pub fn tree_attack(max_distance: f32) -> BehaviorNode {
+    BehaviorNode::Sequence(vec![
+        BehaviorNode::Task(Arc::new(TaskPickTarget { max_distance })),
+        BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+        BehaviorNode::Task(Arc::new(TaskMeleeAttack::default())),
+    ])
+}
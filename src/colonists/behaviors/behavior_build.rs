@@ -9,10 +9,11 @@ use bevy::ecs::{
 
 use crate::{
     colonists::{
-        is_reachable, job_access_points_many, Actor, ActorRef, Behavior, BehaviorNode, HasBehavior,
-        IsJobAccessible, IsJobCancelled, Job, JobBuild, JobLocation, NavigationFlags,
+        is_reachable, job_access_points_many, Activity, Actor, ActorRef, Behavior, BehaviorNode,
+        HasBehavior, IsJobAccessible, IsJobCancelled, Job, JobBuild, JobLocation, NavigationFlags,
         NavigationGraph, PartitionPathRequest, Score, ScorerBuilder, TaskBuild, TaskGetJobLocation,
-        TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMoveTo,
+        TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMoveTo, WorkPreferences,
+        WorkType, JOB_PRIORITY_MAX, JOB_PRIORITY_MIN,
     },
     common::Distance,
     structures::PartSlots,
@@ -34,6 +35,10 @@ impl ScorerBuilder for ScorerBuild {
         "Build".to_string()
     }
 
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
     fn build(&self) -> Behavior {
         Behavior::new(
             "Build",
@@ -71,18 +76,27 @@ pub fn score_build(
         ),
     >,
     q_slots: Query<&PartSlots>,
-    q_actors: Query<(&Position, &NavigationFlags), (With<Actor>, Without<HasBehavior>)>,
+    q_actors: Query<
+        (&Position, &NavigationFlags, &WorkPreferences),
+        (With<Actor>, Without<HasBehavior>),
+    >,
     mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerBuild)>,
 ) {
     for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
-        let Ok((position, flags)) = q_actors.get(*actor) else {
+        let Ok((position, flags, work)) = q_actors.get(*actor) else {
             *score = Score(0.);
             continue;
         };
 
+        if !work.is_enabled(WorkType::Build) {
+            *score = Score(0.);
+            continue;
+        }
+
         let pos = [position.x, position.y, position.z];
 
         let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
         let mut best_structure = None;
         let mut best_dist = 100000.;
 
@@ -117,11 +131,14 @@ pub fn score_build(
                 [pos[0] as i32, pos[1] as i32, pos[2] as i32],
             );
 
-            if job_distance < best_dist {
+            if job.priority > best_priority
+                || (job.priority == best_priority && job_distance < best_dist)
+            {
+                best_priority = job.priority;
                 best = Some(e);
                 best_dist = job_distance;
                 best_structure = Some(job_build.structure);
-                if job_distance < 2. {
+                if job_distance < 2. && job.priority == JOB_PRIORITY_MAX {
                     break;
                 }
             }
@@ -135,6 +152,6 @@ pub fn score_build(
         scorer.job = best;
         scorer.structure = best_structure;
 
-        *score = Score(7.);
+        *score = Score(work.score(WorkType::Build, best_priority, 1.));
     }
 }
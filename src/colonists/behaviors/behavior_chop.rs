@@ -10,11 +10,13 @@ use bevy::ecs::{
 
 use crate::{
     colonists::{
-        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Actor, ActorRef,
-        Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory, IsJobAccessible,
-        IsJobCancelled, Item, ItemTag, Job, JobChop, JobLocation, NavigationFlags, NavigationGraph,
-        PartitionPathRequest, Score, ScorerBuilder, TaskChopTree, TaskGetJobLocation, TaskIdle,
-        TaskItemEquip, TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMoveTo,
+        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Activity, Actor,
+        ActorRef, Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory,
+        IsJobAccessible, IsJobCancelled, Item, ItemTag, Job, JobChop, JobLocation, NavigationFlags,
+        NavigationGraph, PartitionPathRequest, Score, ScorerBuilder, TaskChopTree,
+        TaskGetJobLocation, TaskIdle, TaskItemEquip, TaskJobAssign, TaskJobComplete,
+        TaskJobUnassign, TaskLookAt, TaskMoveTo, WorkPreferences, WorkType, JOB_PRIORITY_MAX,
+        JOB_PRIORITY_MIN, WORK_READINESS_FETCH,
     },
     common::Distance,
     Position, Terrain,
@@ -35,6 +37,10 @@ impl ScorerBuilder for ScorerChop {
         "Chop".to_string()
     }
 
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
     fn build(&self) -> Behavior {
         Behavior::new(
             "Chop",
@@ -77,18 +83,27 @@ pub fn score_chop(
     >,
     q_items: Query<&Item>,
     q_free_items: Query<(&Item, &Position), (Without<InInventory>, Without<InSlot>)>,
-    q_actors: Query<(&Inventory, &Position, &NavigationFlags), (With<Actor>, Without<HasBehavior>)>,
+    q_actors: Query<
+        (&Inventory, &Position, &NavigationFlags, &WorkPreferences),
+        (With<Actor>, Without<HasBehavior>),
+    >,
     mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerChop)>,
 ) {
     for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
-        let Ok((inventory, position, flags)) = q_actors.get(*actor) else {
+        let Ok((inventory, position, flags, work)) = q_actors.get(*actor) else {
             *score = Score(0.);
             continue;
         };
 
+        if !work.is_enabled(WorkType::Chop) {
+            *score = Score(0.);
+            continue;
+        }
+
         let pos = [position.x, position.y, position.z];
 
         let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
         let mut best_tree = None;
         let mut best_dist = 100000.;
 
@@ -117,11 +132,14 @@ pub fn score_chop(
                 [pos[0] as i32, pos[1] as i32, pos[2] as i32],
             );
 
-            if job_distance < best_dist {
+            if job.priority > best_priority
+                || (job.priority == best_priority && job_distance < best_dist)
+            {
+                best_priority = job.priority;
                 best = Some(e);
                 best_dist = job_distance;
                 best_tree = Some(job_chop.tree);
-                if job_distance < 2. {
+                if job_distance < 2. && job.priority == JOB_PRIORITY_MAX {
                     break;
                 }
             }
@@ -147,7 +165,7 @@ pub fn score_chop(
 
         // if we have a pickaxe, score is higher
         if has_axe {
-            *score = Score(0.6);
+            *score = Score(work.score(WorkType::Chop, best_priority, 1.));
             continue;
         }
 
@@ -165,7 +183,7 @@ pub fn score_chop(
                     &graph,
                 )
         }) {
-            *score = Score(0.2);
+            *score = Score(work.score(WorkType::Chop, best_priority, WORK_READINESS_FETCH));
             continue;
         } else {
             *score = Score(0.0);
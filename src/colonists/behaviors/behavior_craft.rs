@@ -0,0 +1,231 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    system::{EntityCommands, Query, Res},
+};
+
+use crate::{
+    colonists::{
+        is_reachable, job_access_points_many, Activity, Actor, ActorRef, AnimClip, Behavior,
+        BehaviorNode, HasBehavior, InInventory, InSlot, Inventory, IsJobAccessible, IsJobCancelled,
+        Item, Job, JobCraft, JobLocation, NavigationFlags, NavigationGraph, PartitionPathRequest,
+        Score, ScorerBuilder, TaskAnimate, TaskCraft, TaskFindIngredient, TaskGetJobLocation,
+        TaskItemPickUp, TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMoveTo,
+        WorkPreferences, WorkType, JOB_PRIORITY_MAX, JOB_PRIORITY_MIN, WORK_READINESS_FETCH,
+    },
+    common::Distance,
+    items::{Commodity, Ingredient, RecipeType, Recipes},
+    Position, Terrain,
+};
+
+#[derive(Component, Clone, Default)]
+pub struct ScorerCraft {
+    job: Option<Entity>,
+    station: Option<Entity>,
+    recipe: Option<RecipeType>,
+    /// ingredients still to be fetched
+    inputs: Vec<Ingredient>,
+}
+
+impl ScorerBuilder for ScorerCraft {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Craft".to_string()
+    }
+
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
+    fn build(&self) -> Behavior {
+        let mut steps = vec![BehaviorNode::Task(Arc::new(TaskJobAssign(
+            self.job.unwrap(),
+        )))];
+
+        for input in self.inputs.iter() {
+            steps.push(BehaviorNode::Sequence(vec![
+                BehaviorNode::Task(Arc::new(TaskFindIngredient(input.clone()))),
+                BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+                BehaviorNode::Task(Arc::new(TaskItemPickUp)),
+                BehaviorNode::Task(Arc::new(TaskAnimate(AnimClip::PickUp))),
+            ]));
+        }
+
+        steps.push(BehaviorNode::Sequence(vec![
+            BehaviorNode::Task(Arc::new(TaskGetJobLocation)),
+            BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+            BehaviorNode::Task(Arc::new(TaskLookAt)),
+            BehaviorNode::Task(Arc::new(TaskCraft {
+                station: self.station.unwrap(),
+                recipe: self.recipe.unwrap(),
+                progress: 0.,
+            })),
+            BehaviorNode::Task(Arc::new(TaskJobComplete)),
+        ]));
+
+        Behavior::new(
+            "Craft",
+            BehaviorNode::Try(
+                Box::new(BehaviorNode::Sequence(steps)),
+                Box::new(BehaviorNode::Task(Arc::new(TaskJobUnassign))),
+            ),
+        )
+    }
+}
+
+pub fn score_craft(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    recipes: Res<Recipes>,
+    q_jobs: Query<
+        (Entity, &Job, &JobCraft, &JobLocation),
+        (
+            With<IsJobAccessible>,
+            Without<IsJobCancelled>,
+            Without<TaskJobComplete>,
+        ),
+    >,
+    q_items: Query<(&Item, Option<&Commodity>)>,
+    q_free_items: Query<
+        (Entity, &Item, &Position, Option<&Commodity>),
+        (Without<InInventory>, Without<InSlot>),
+    >,
+    q_actors: Query<
+        (&Inventory, &Position, &NavigationFlags, &WorkPreferences),
+        (With<Actor>, Without<HasBehavior>),
+    >,
+    mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerCraft)>,
+) {
+    for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
+        let Ok((inventory, position, flags, work)) = q_actors.get(*actor) else {
+            *score = Score(0.);
+            continue;
+        };
+
+        if !work.is_enabled(WorkType::Craft) {
+            *score = Score(0.);
+            continue;
+        }
+
+        let pos = [position.x, position.y, position.z];
+
+        let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
+        let mut best_dist = 100000.;
+
+        for (e, job, job_craft, job_location) in q_jobs.iter() {
+            if job.assignee.is_some() {
+                continue;
+            }
+
+            let goals = job_access_points_many(&job_location.targets, job.job_type);
+            let request = PartitionPathRequest {
+                start: pos,
+                goals,
+                flags: *flags,
+            };
+
+            if !is_reachable(&request, &terrain, &graph) {
+                continue;
+            }
+
+            let job_distance = Distance::manhattan(
+                [
+                    job_location.primary_target[0] as i32,
+                    job_location.primary_target[1] as i32,
+                    job_location.primary_target[2] as i32,
+                ],
+                [pos[0] as i32, pos[1] as i32, pos[2] as i32],
+            );
+
+            if job.priority > best_priority
+                || (job.priority == best_priority && job_distance < best_dist)
+            {
+                best_priority = job.priority;
+                best = Some((e, *job_craft));
+                best_dist = job_distance;
+                if job_distance < 2. && job.priority == JOB_PRIORITY_MAX {
+                    break;
+                }
+            }
+        }
+
+        let Some((job, job_craft)) = best else {
+            *score = Score(0.);
+            continue;
+        };
+
+        let Some(recipe) = recipes.0.get(&job_craft.recipe) else {
+            *score = Score(0.);
+            continue;
+        };
+
+        // ingredients already held are used first, the rest each need their
+        // own unreserved and reachable item
+        let mut held = vec![];
+        let mut missing = vec![];
+
+        for input in recipe.inputs.iter() {
+            let item = inventory.items.iter().copied().find(|e| {
+                !held.contains(e)
+                    && q_items
+                        .get(*e)
+                        .is_ok_and(|(item, commodity)| input.matches(item, commodity))
+            });
+
+            match item {
+                Some(item) => held.push(item),
+                None => missing.push(input.clone()),
+            }
+        }
+
+        let mut found = vec![];
+
+        for input in missing.iter() {
+            let item = q_free_items.iter().find(|(e, i, p, c)| {
+                i.reserved.is_none()
+                    && !found.contains(e)
+                    && input.matches(i, *c)
+                    && is_reachable(
+                        &PartitionPathRequest {
+                            start: pos,
+                            goals: vec![[p.x, p.y, p.z]],
+                            flags: *flags,
+                        },
+                        &terrain,
+                        &graph,
+                    )
+            });
+
+            let Some((e, _, _, _)) = item else {
+                break;
+            };
+
+            found.push(e);
+        }
+
+        if found.len() < missing.len() {
+            *score = Score(0.);
+            continue;
+        }
+
+        let readiness = if missing.is_empty() {
+            1.
+        } else {
+            WORK_READINESS_FETCH
+        };
+
+        scorer.job = Some(job);
+        scorer.station = Some(job_craft.station);
+        scorer.recipe = Some(job_craft.recipe);
+        scorer.inputs = missing;
+
+        *score = Score(work.score(WorkType::Craft, best_priority, readiness));
+    }
+}
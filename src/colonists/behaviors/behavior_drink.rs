@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query, Res},
+};
+
+use crate::{
+    colonists::{
+        find_water, Actor, ActorRef, Behavior, BehaviorNode, HasBehavior, NavigationFlags,
+        NavigationGraph, Needs, Score, ScorerBuilder, TaskDrink, TaskFindWater, TaskLookAt,
+        TaskMoveTo,
+    },
+    Position, Terrain,
+};
+
+#[derive(Component, Clone)]
+pub struct ScorerDrink;
+
+impl ScorerBuilder for ScorerDrink {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Drink".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new(
+            "Drink",
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::Task(Arc::new(TaskFindWater)),
+                BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+                BehaviorNode::Task(Arc::new(TaskLookAt)),
+                BehaviorNode::Task(Arc::new(TaskDrink)),
+            ]),
+        )
+    }
+}
+
+pub fn score_drink(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<(&Needs, &Position, &NavigationFlags), (With<Actor>, Without<HasBehavior>)>,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerDrink>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        let Ok((needs, position, flags)) = q_actors.get(*actor) else {
+            *score = Score(0.);
+            continue;
+        };
+
+        let urgency = needs.thirst.urgency();
+
+        // searching for water is costly, only look once thirsty
+        if urgency <= 0. || find_water(position.as_array(), *flags, &terrain, &graph).is_none() {
+            *score = Score(0.);
+            continue;
+        }
+
+        *score = Score(0.5 + urgency * 0.5);
+    }
+}
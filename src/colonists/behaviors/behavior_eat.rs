@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query, Res},
+};
+
+use crate::{
+    colonists::{
+        is_reachable, test_item_tags, tree_aquire_item, Actor, ActorRef, Behavior, BehaviorNode,
+        HasBehavior, InInventory, InSlot, Inventory, Item, ItemTag, NavigationFlags,
+        NavigationGraph, Needs, PartitionPathRequest, Score, ScorerBuilder, TaskEat,
+    },
+    Position, Terrain,
+};
+
+#[derive(Component, Clone)]
+pub struct ScorerEat;
+
+impl ScorerBuilder for ScorerEat {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Eat".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new(
+            "Eat",
+            BehaviorNode::Sequence(vec![
+                tree_aquire_item(vec![ItemTag::Food]),
+                BehaviorNode::Task(Arc::new(TaskEat::default())),
+            ]),
+        )
+    }
+}
+
+pub fn score_eat(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_items: Query<&Item>,
+    q_free_items: Query<(&Item, &Position), (Without<InInventory>, Without<InSlot>)>,
+    q_actors: Query<
+        (&Needs, &Inventory, &Position, &NavigationFlags),
+        (With<Actor>, Without<HasBehavior>),
+    >,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerEat>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        let Ok((needs, inventory, position, flags)) = q_actors.get(*actor) else {
+            *score = Score(0.);
+            continue;
+        };
+
+        let urgency = needs.hunger.urgency();
+
+        if urgency <= 0. {
+            *score = Score(0.);
+            continue;
+        }
+
+        let item_tags = &[ItemTag::Food];
+
+        let has_food = inventory.items.iter().any(|e| {
+            let Ok(item) = q_items.get(*e) else {
+                return false;
+            };
+
+            test_item_tags(&item.tags, item_tags)
+        });
+
+        let can_get_food = has_food
+            || q_free_items.iter().any(|(i, p)| {
+                test_item_tags(&i.tags, item_tags)
+                    && i.reserved.is_none()
+                    && is_reachable(
+                        &PartitionPathRequest {
+                            start: position.as_array(),
+                            goals: vec![p.as_array()],
+                            flags: *flags,
+                        },
+                        &terrain,
+                        &graph,
+                    )
+            });
+
+        if can_get_food {
+            *score = Score(0.5 + urgency * 0.5);
+        } else {
+            *score = Score(0.);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query, Res},
+};
+
+use crate::{
+    colonists::{
+        nearest_enemy, tree_attack, Actor, ActorRef, Behavior, Colonist, CombatantQuery,
+        HasBehavior, Health, NavigationGraph, Score, ScorerBuilder, FIGHT_DISTANCE, FLEE_HEALTH,
+    },
+    Terrain,
+};
+
+#[derive(Component, Clone)]
+pub struct ScorerFight;
+
+impl ScorerBuilder for ScorerFight {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Fight".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new("Fight", tree_attack(FIGHT_DISTANCE)).urgent()
+    }
+}
+
+/// Colonists healthy enough to fight take on hostiles that come close.
+pub fn score_fight(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<&Health, (With<Actor>, With<Colonist>, Without<HasBehavior>)>,
+    q_combatants: CombatantQuery,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerFight>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        *score = Score(0.);
+
+        let Ok(health) = q_actors.get(*actor) else {
+            continue;
+        };
+
+        if health.value < FLEE_HEALTH * health.max {
+            continue;
+        }
+
+        if nearest_enemy(&terrain, &graph, &q_combatants, *actor, FIGHT_DISTANCE).is_some() {
+            *score = Score(1.);
+        }
+    }
+}
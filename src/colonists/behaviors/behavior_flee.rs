@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query},
+};
+
+use crate::colonists::{
+    alert_distance, nearest_threat, Actor, ActorRef, Behavior, BehaviorNode, CombatantQuery,
+    Creature, HasBehavior, Health, Score, ScorerBuilder, TaskMoveTo, TaskPickFleeSpot, FLEE_HEALTH,
+};
+
+#[derive(Component, Clone)]
+pub struct ScorerFlee;
+
+impl ScorerBuilder for ScorerFlee {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Flee".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new(
+            "Flee",
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::Task(Arc::new(TaskPickFleeSpot)),
+                BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+            ]),
+        )
+        .urgent()
+    }
+}
+
+/// Animals run from hostiles, wildlife from colonists too, and colonists run
+/// from hostiles once they are too hurt to fight.
+pub fn score_flee(
+    q_actors: Query<(Option<&Creature>, &Health), (With<Actor>, Without<HasBehavior>)>,
+    q_combatants: CombatantQuery,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerFlee>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        *score = Score(0.);
+
+        let Ok((creature, health)) = q_actors.get(*actor) else {
+            continue;
+        };
+
+        if nearest_threat(&q_combatants, *actor, alert_distance(creature)).is_none() {
+            continue;
+        }
+
+        match creature {
+            Some(_) => *score = Score(0.9),
+            None if health.value < FLEE_HEALTH * health.max => *score = Score(1.),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query},
+};
+
+use crate::{
+    colonists::{
+        Actor, ActorRef, Behavior, BehaviorNode, Colonist, Creature, HasBehavior, Score,
+        ScorerBuilder, TaskIdle, TaskMoveTo, TaskPickFollowSpot, FOLLOW_DISTANCE,
+    },
+    common::Distance,
+    Position,
+};
+
+#[derive(Component, Clone)]
+pub struct ScorerFollowOwner;
+
+impl ScorerBuilder for ScorerFollowOwner {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Follow owner".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new(
+            "Follow owner",
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::Task(Arc::new(TaskPickFollowSpot)),
+                BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+                BehaviorNode::Task(Arc::new(TaskIdle {
+                    duration_s: 1.,
+                    progress: 0.,
+                })),
+            ]),
+        )
+    }
+}
+
+pub fn score_follow_owner(
+    q_actors: Query<(&Creature, &Position), (With<Actor>, Without<HasBehavior>)>,
+    q_colonists: Query<(&Colonist, &Position)>,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerFollowOwner>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        *score = Score(0.);
+
+        let Ok((creature, position)) = q_actors.get(*actor) else {
+            continue;
+        };
+
+        let Some(owner) = creature.owner else {
+            continue;
+        };
+
+        let Some((_, owner_position)) = q_colonists.iter().find(|(c, _)| c.id == owner) else {
+            continue;
+        };
+
+        let distance = Distance::diagonal(position.as_i32(), owner_position.as_i32());
+
+        if distance > FOLLOW_DISTANCE {
+            *score = Score(0.5);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    self,
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    system::{Query, Res},
+};
+
+use crate::{
+    colonists::{
+        is_reachable, job_access_points_many, Activity, Actor, ActorRef, Behavior, BehaviorNode,
+        HasBehavior, IsJobAccessible, IsJobCancelled, Item, Job, JobHaul, JobLocation,
+        NavigationFlags, NavigationGraph, PartitionPathRequest, Score, ScorerBuilder,
+        TaskGetHaulItem, TaskGetJobLocation, TaskItemPickUp, TaskJobAssign, TaskJobComplete,
+        TaskJobUnassign, TaskMoveTo, TaskStoreItem, WorkPreferences, WorkType, JOB_PRIORITY_MAX,
+        JOB_PRIORITY_MIN,
+    },
+    common::Distance,
+    Position, Terrain,
+};
+
+#[derive(Component, Clone, Default)]
+pub struct ScorerHaul {
+    job: Option<Entity>,
+}
+
+impl ScorerBuilder for ScorerHaul {
+    fn insert(&self, cmd: &mut ecs::system::EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Haul".to_string()
+    }
+
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new(
+            "Haul",
+            BehaviorNode::Try(
+                Box::new(BehaviorNode::Sequence(vec![
+                    BehaviorNode::Task(Arc::new(TaskJobAssign(self.job.unwrap()))),
+                    BehaviorNode::Task(Arc::new(TaskGetHaulItem)),
+                    BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+                    BehaviorNode::Task(Arc::new(TaskItemPickUp)),
+                    BehaviorNode::Task(Arc::new(TaskGetJobLocation)),
+                    BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+                    BehaviorNode::Task(Arc::new(TaskStoreItem)),
+                    BehaviorNode::Task(Arc::new(TaskJobComplete)),
+                ])),
+                Box::new(BehaviorNode::Task(Arc::new(TaskJobUnassign))),
+            ),
+        )
+    }
+}
+
+pub fn score_haul(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_jobs: Query<
+        (Entity, &Job, &JobHaul, &JobLocation),
+        (
+            With<IsJobAccessible>,
+            Without<IsJobCancelled>,
+            Without<TaskJobComplete>,
+        ),
+    >,
+    q_items: Query<(&Item, &Position)>,
+    q_actors: Query<
+        (&Position, &NavigationFlags, &WorkPreferences),
+        (With<Actor>, Without<HasBehavior>),
+    >,
+    mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerHaul)>,
+) {
+    for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
+        let Ok((position, flags, work)) = q_actors.get(*actor) else {
+            *score = Score(0.);
+            continue;
+        };
+
+        if !work.is_enabled(WorkType::Haul) {
+            *score = Score(0.);
+            continue;
+        }
+
+        let pos = position.as_array();
+
+        let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
+        let mut best_dist = 100000.;
+
+        for (e, job, job_haul, job_location) in q_jobs.iter() {
+            if job.assignee.is_some() {
+                continue;
+            }
+
+            let Ok((item, item_position)) = q_items.get(job_haul.item) else {
+                continue;
+            };
+
+            if item.reserved.is_some() {
+                continue;
+            }
+
+            let item_pos = item_position.as_array();
+
+            let is_item_reachable = is_reachable(
+                &PartitionPathRequest {
+                    start: pos,
+                    goals: vec![item_pos],
+                    flags: *flags,
+                },
+                &terrain,
+                &graph,
+            );
+
+            let is_stockpile_reachable = is_reachable(
+                &PartitionPathRequest {
+                    start: item_pos,
+                    goals: job_access_points_many(&job_location.targets, job.job_type),
+                    flags: *flags,
+                },
+                &terrain,
+                &graph,
+            );
+
+            if !is_item_reachable || !is_stockpile_reachable {
+                continue;
+            }
+
+            let item_distance = Distance::manhattan(
+                [item_pos[0] as i32, item_pos[1] as i32, item_pos[2] as i32],
+                [pos[0] as i32, pos[1] as i32, pos[2] as i32],
+            );
+
+            if job.priority > best_priority
+                || (job.priority == best_priority && item_distance < best_dist)
+            {
+                best_priority = job.priority;
+                best = Some(e);
+                best_dist = item_distance;
+                if item_distance < 2. && job.priority == JOB_PRIORITY_MAX {
+                    break;
+                }
+            }
+        }
+
+        if best.is_none() {
+            *score = Score(0.);
+            continue;
+        }
+
+        scorer.job = best;
+
+        // below other work, hauling is done when there is nothing else to do
+        *score = Score(work.score(WorkType::Haul, best_priority, 0.5));
+    }
+}
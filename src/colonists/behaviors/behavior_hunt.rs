@@ -0,0 +1,55 @@
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query, Res},
+};
+
+use crate::{
+    colonists::{
+        nearest_enemy, tree_attack, Actor, ActorRef, Behavior, CombatantQuery, Creature,
+        HasBehavior, NavigationGraph, Score, ScorerBuilder, HUNT_DISTANCE,
+    },
+    Terrain,
+};
+
+#[derive(Component, Clone)]
+pub struct ScorerHunt;
+
+impl ScorerBuilder for ScorerHunt {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Hunt".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        Behavior::new("Hunt", tree_attack(HUNT_DISTANCE)).urgent()
+    }
+}
+
+/// Hostiles go after any colonist they can reach.
+pub fn score_hunt(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<&Creature, (With<Actor>, Without<HasBehavior>)>,
+    q_combatants: CombatantQuery,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerHunt>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        *score = Score(0.);
+
+        let Ok(creature) = q_actors.get(*actor) else {
+            continue;
+        };
+
+        if !creature.creature_type.is_hostile() {
+            continue;
+        }
+
+        if nearest_enemy(&terrain, &graph, &q_combatants, *actor, HUNT_DISTANCE).is_some() {
+            *score = Score(0.8);
+        }
+    }
+}
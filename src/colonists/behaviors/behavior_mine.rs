@@ -10,11 +10,13 @@ use bevy::ecs::{
 
 use crate::{
     colonists::{
-        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Actor, ActorRef,
-        Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory, IsJobAccessible,
-        IsJobCancelled, Item, ItemTag, Job, JobLocation, JobMine, NavigationFlags, NavigationGraph,
-        PartitionPathRequest, Score, ScorerBuilder, TaskGetJobLocation, TaskIdle, TaskItemEquip,
-        TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMineBlock, TaskMoveTo,
+        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Activity, Actor,
+        ActorRef, Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory,
+        IsJobAccessible, IsJobCancelled, Item, ItemTag, Job, JobLocation, JobMine, NavigationFlags,
+        NavigationGraph, PartitionPathRequest, Score, ScorerBuilder, TaskGetJobLocation, TaskIdle,
+        TaskItemEquip, TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMineBlock,
+        TaskMoveTo, WorkPreferences, WorkType, JOB_PRIORITY_MAX, JOB_PRIORITY_MIN,
+        WORK_READINESS_FETCH,
     },
     common::Distance,
     Position, Terrain,
@@ -34,6 +36,10 @@ impl ScorerBuilder for ScorerMine {
         "Mine".to_string()
     }
 
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
     fn build(&self) -> Behavior {
         Behavior::new(
             "Mine",
@@ -74,18 +80,27 @@ pub fn score_mine(
     >,
     q_items: Query<&Item>,
     q_free_items: Query<(&Item, &Position), (Without<InInventory>, Without<InSlot>)>,
-    q_actors: Query<(&Inventory, &Position, &NavigationFlags), (With<Actor>, Without<HasBehavior>)>,
+    q_actors: Query<
+        (&Inventory, &Position, &NavigationFlags, &WorkPreferences),
+        (With<Actor>, Without<HasBehavior>),
+    >,
     mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerMine)>,
 ) {
     for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
-        let Ok((inventory, position, flags)) = q_actors.get(*actor) else {
+        let Ok((inventory, position, flags, work)) = q_actors.get(*actor) else {
             *score = Score(0.);
             continue;
         };
 
+        if !work.is_enabled(WorkType::Mine) {
+            *score = Score(0.);
+            continue;
+        }
+
         let pos = [position.x, position.y, position.z];
 
         let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
         let mut best_dist = 100000.;
 
         for (e, job, job_location) in q_jobs.iter() {
@@ -113,10 +128,13 @@ pub fn score_mine(
                 [pos[0] as i32, pos[1] as i32, pos[2] as i32],
             );
 
-            if job_distance < best_dist {
+            if job.priority > best_priority
+                || (job.priority == best_priority && job_distance < best_dist)
+            {
+                best_priority = job.priority;
                 best = Some(e);
                 best_dist = job_distance;
-                if job_distance < 2. {
+                if job_distance < 2. && job.priority == JOB_PRIORITY_MAX {
                     break;
                 }
             }
@@ -141,7 +159,7 @@ pub fn score_mine(
 
         // if we have a pickaxe, score is higher
         if has_pickaxe {
-            *score = Score(0.6);
+            *score = Score(work.score(WorkType::Mine, best_priority, 1.));
             continue;
         }
 
@@ -159,7 +177,7 @@ pub fn score_mine(
                     &graph,
                 )
         }) {
-            *score = Score(0.2);
+            *score = Score(work.score(WorkType::Mine, best_priority, WORK_READINESS_FETCH));
             continue;
         } else {
             *score = Score(0.0);
@@ -12,21 +12,24 @@ use bevy::{
 
 use crate::{
     colonists::{
-        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Actor, ActorRef,
+        is_reachable, job_access_points_many, tree_aquire_ingredient, Activity, Actor, ActorRef,
         Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory, IsJobAccessible,
-        IsJobCancelled, IsJobCompleted, Item, ItemTag, Job, JobLocation, JobPlaceBlock, JobType,
+        IsJobCancelled, IsJobCompleted, Item, Job, JobLocation, JobPlaceBlock, JobType,
         NavigationFlags, NavigationGraph, PartitionPathRequest, Score, ScorerBuilder,
         TaskGetJobLocation, TaskIsTargetEmpty, TaskJobAssign, TaskJobCancel, TaskJobComplete,
-        TaskJobUnassign, TaskLookAt, TaskMoveTo, TaskPlaceBlock,
+        TaskJobUnassign, TaskLookAt, TaskMoveTo, TaskPlaceBlock, WorkPreferences, WorkType,
+        JOB_PRIORITY_MAX, JOB_PRIORITY_MIN, WORK_READINESS_FETCH,
     },
     common::Distance,
-    BlockType, Terrain,
+    items::{Commodity, Ingredient},
+    BlockRegistry, BlockType, Terrain,
 };
 
 #[derive(Component, Clone, Default)]
 pub struct ScorerPlaceBlock {
     job: Option<Entity>,
     block_type: Option<BlockType>,
+    material: Option<Commodity>,
 }
 
 impl ScorerBuilder for ScorerPlaceBlock {
@@ -38,7 +41,28 @@ impl ScorerBuilder for ScorerPlaceBlock {
         "Build".to_string()
     }
 
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
     fn build(&self) -> Behavior {
+        let mut steps = vec![];
+
+        if let Some(material) = self.material {
+            steps.push(tree_aquire_ingredient(Ingredient::Commodity(material)));
+        }
+
+        steps.push(BehaviorNode::Sequence(vec![
+            BehaviorNode::Task(Arc::new(TaskGetJobLocation)),
+            BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+            BehaviorNode::Task(Arc::new(TaskLookAt)),
+            BehaviorNode::Task(Arc::new(TaskPlaceBlock {
+                progress: 0.,
+                block_type: self.block_type.unwrap(),
+            })),
+            BehaviorNode::Task(Arc::new(TaskJobComplete)),
+        ]));
+
         Behavior::new(
             "Build",
             BehaviorNode::Try(
@@ -46,19 +70,7 @@ impl ScorerBuilder for ScorerPlaceBlock {
                     BehaviorNode::Task(Arc::new(TaskJobAssign(self.job.unwrap()))),
                     BehaviorNode::IfElse(
                         Box::new(BehaviorNode::Task(Arc::new(TaskIsTargetEmpty))),
-                        Box::new(BehaviorNode::Sequence(vec![
-                            tree_aquire_item(vec![ItemTag::Stone]),
-                            BehaviorNode::Sequence(vec![
-                                BehaviorNode::Task(Arc::new(TaskGetJobLocation)),
-                                BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
-                                BehaviorNode::Task(Arc::new(TaskLookAt)),
-                                BehaviorNode::Task(Arc::new(TaskPlaceBlock {
-                                    progress: 0.,
-                                    block_type: self.block_type.unwrap(),
-                                })),
-                                BehaviorNode::Task(Arc::new(TaskJobComplete)),
-                            ]),
-                        ])),
+                        Box::new(BehaviorNode::Sequence(steps)),
                         Box::new(BehaviorNode::Task(Arc::new(TaskJobCancel))),
                     ),
                 ])),
@@ -80,20 +92,28 @@ pub fn score_place_block(
             Without<IsJobCompleted>,
         ),
     >,
-    q_items: Query<&Item>,
-    q_free_items: Query<(&Item, &Transform), (Without<InInventory>, Without<InSlot>)>,
+    q_items: Query<(&Item, Option<&Commodity>)>,
+    q_free_items: Query<
+        (&Item, &Transform, Option<&Commodity>),
+        (Without<InInventory>, Without<InSlot>),
+    >,
     q_actors: Query<
-        (&Inventory, &Transform, &NavigationFlags),
+        (&Inventory, &Transform, &NavigationFlags, &WorkPreferences),
         (With<Actor>, Without<HasBehavior>),
     >,
     mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerPlaceBlock)>,
 ) {
     for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
-        let Ok((inventory, transform, flags)) = q_actors.get(*actor) else {
+        let Ok((inventory, transform, flags, work)) = q_actors.get(*actor) else {
             *score = Score(0.);
             continue;
         };
 
+        if !work.is_enabled(WorkType::PlaceBlock) {
+            *score = Score(0.);
+            continue;
+        }
+
         let pos = [
             transform.translation.x as u32,
             transform.translation.y as u32,
@@ -101,6 +121,7 @@ pub fn score_place_block(
         ];
 
         let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
         let mut best_block_type = None;
         let mut best_dist = 100000.;
 
@@ -129,7 +150,10 @@ pub fn score_place_block(
                 [pos[0] as i32, pos[1] as i32, pos[2] as i32],
             );
 
-            if job_distance < best_dist {
+            if job.priority > best_priority
+                || (job.priority == best_priority && job_distance < best_dist)
+            {
+                best_priority = job.priority;
                 best = Some(e);
                 best_dist = job_distance;
 
@@ -139,7 +163,7 @@ pub fn score_place_block(
 
                 best_block_type = Some(block_type);
 
-                if job_distance < 2. {
+                if job_distance < 2. && job.priority == JOB_PRIORITY_MAX {
                     break;
                 }
             }
@@ -150,28 +174,39 @@ pub fn score_place_block(
             continue;
         };
 
+        let Some(block_type) = best_block_type else {
+            *score = Score(0.);
+            continue;
+        };
+
+        let material = BlockRegistry::get().definition(block_type).material;
+
         scorer.job = best;
         scorer.block_type = best_block_type;
+        scorer.material = material;
 
-        let item_tags = &[ItemTag::Stone];
+        let Some(material) = material else {
+            *score = Score(work.score(WorkType::PlaceBlock, best_priority, 1.));
+            continue;
+        };
 
-        let has_stone = inventory.items.iter().any(|e| {
-            let Ok(item) = q_items.get(*e) else {
-                return false;
-            };
+        let ingredient = Ingredient::Commodity(material);
 
-            test_item_tags(&item.tags, item_tags)
+        let has_material = inventory.items.iter().any(|e| {
+            q_items
+                .get(*e)
+                .is_ok_and(|(item, commodity)| ingredient.matches(item, commodity))
         });
 
-        // if we have stone, score is higher
-        if has_stone {
-            *score = Score(0.6);
+        // if we have the material, score is higher
+        if has_material {
+            *score = Score(work.score(WorkType::PlaceBlock, best_priority, 1.));
             continue;
         }
 
         // check if any of the items are unreserved and accessible
-        if q_free_items.iter().any(|(i, t)| {
-            test_item_tags(&i.tags, item_tags)
+        if q_free_items.iter().any(|(i, t, c)| {
+            ingredient.matches(i, c)
                 && i.reserved.is_none()
                 && is_reachable(
                     &PartitionPathRequest {
@@ -187,7 +222,7 @@ pub fn score_place_block(
                     &graph,
                 )
         }) {
-            *score = Score(0.2);
+            *score = Score(work.score(WorkType::PlaceBlock, best_priority, WORK_READINESS_FETCH));
             continue;
         } else {
             *score = Score(0.0);
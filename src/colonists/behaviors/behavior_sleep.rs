@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::{EntityCommands, Query, Res},
+};
+
+use crate::{
+    colonists::{
+        urgency, Activity, Actor, ActorRef, Behavior, BehaviorNode, DailySchedule, Fatigue,
+        HasBehavior, Needs, Score, ScorerBuilder, TaskFindBed, TaskMoveTo, TaskReleaseBed,
+        TaskSleep, FATIGUE_THRESHOLD,
+    },
+    TimeOfDay,
+};
+
+/// Least urgency to sleep during the hours set aside for it.
+const SLEEP_HOURS_URGENCY: f32 = 0.5;
+
+#[derive(Component, Clone)]
+pub struct ScorerSleep;
+
+impl ScorerBuilder for ScorerSleep {
+    fn insert(&self, cmd: &mut EntityCommands) {
+        cmd.insert(self.clone());
+    }
+
+    fn label(&self) -> String {
+        "Sleep".to_string()
+    }
+
+    fn build(&self) -> Behavior {
+        // without a bed to go to, colonists lie down where they stand
+        Behavior::new(
+            "Sleep",
+            BehaviorNode::IfElse(
+                Box::new(BehaviorNode::Task(Arc::new(TaskFindBed))),
+                Box::new(BehaviorNode::Try(
+                    Box::new(BehaviorNode::Sequence(vec![
+                        BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+                        BehaviorNode::Task(Arc::new(TaskSleep)),
+                        BehaviorNode::Task(Arc::new(TaskReleaseBed)),
+                    ])),
+                    Box::new(BehaviorNode::Task(Arc::new(TaskReleaseBed))),
+                )),
+                Box::new(BehaviorNode::Task(Arc::new(TaskSleep))),
+            ),
+        )
+    }
+}
+
+pub fn score_sleep(
+    time_of_day: Res<TimeOfDay>,
+    q_actors: Query<
+        (&Fatigue, &Needs, Option<&DailySchedule>),
+        (With<Actor>, Without<HasBehavior>),
+    >,
+    mut q_behaviors: Query<(&ActorRef, &mut Score), With<ScorerSleep>>,
+) {
+    for (ActorRef(actor), mut score) in q_behaviors.iter_mut() {
+        let Ok((fatigue, needs, schedule)) = q_actors.get(*actor) else {
+            *score = Score(0.);
+            continue;
+        };
+
+        let mut urgency = urgency(fatigue.value, FATIGUE_THRESHOLD).max(needs.comfort.urgency());
+
+        if schedule.is_some_and(|s| s.at(time_of_day.hour()) == Activity::Sleep) {
+            urgency = urgency.max(SLEEP_HOURS_URGENCY);
+        }
+
+        if urgency <= 0. {
+            *score = Score(0.);
+            continue;
+        }
+
+        *score = Score(0.5 + urgency * 0.5);
+    }
+}
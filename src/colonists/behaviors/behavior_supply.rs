@@ -10,11 +10,13 @@ use bevy::ecs::{
 
 use crate::{
     colonists::{
-        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Actor, ActorRef,
-        Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory, IsJobAccessible,
-        IsJobCancelled, Item, ItemTag, Job, JobLocation, JobSupply, NavigationFlags,
-        NavigationGraph, PartitionPathRequest, Score, ScorerBuilder, TaskGetJobLocation,
-        TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt, TaskMoveTo, TaskSupply,
+        is_reachable, job_access_points_many, test_item_tags, tree_aquire_item, Activity, Actor,
+        ActorRef, Behavior, BehaviorNode, HasBehavior, InInventory, InSlot, Inventory,
+        IsJobAccessible, IsJobCancelled, Item, ItemTag, Job, JobLocation, JobSupply,
+        NavigationFlags, NavigationGraph, PartitionPathRequest, Score, ScorerBuilder,
+        TaskGetJobLocation, TaskJobAssign, TaskJobComplete, TaskJobUnassign, TaskLookAt,
+        TaskMoveTo, TaskSupply, WorkPreferences, WorkType, JOB_PRIORITY_MAX, JOB_PRIORITY_MIN,
+        WORK_READINESS_FETCH,
     },
     common::Distance,
     rendering::SlotIndex,
@@ -38,6 +40,10 @@ impl ScorerBuilder for ScorerSupply {
         "Supply".to_string()
     }
 
+    fn activity(&self) -> Option<Activity> {
+        Some(Activity::Work)
+    }
+
     fn build(&self) -> Behavior {
         Behavior::new(
             "Supply",
@@ -75,18 +81,27 @@ pub fn score_supply(
     >,
     q_items: Query<&Item>,
     q_free_items: Query<(&Item, &Position), (Without<InInventory>, Without<InSlot>)>,
-    q_actors: Query<(&Inventory, &Position, &NavigationFlags), (With<Actor>, Without<HasBehavior>)>,
+    q_actors: Query<
+        (&Inventory, &Position, &NavigationFlags, &WorkPreferences),
+        (With<Actor>, Without<HasBehavior>),
+    >,
     mut q_behaviors: Query<(&ActorRef, &mut Score, &mut ScorerSupply)>,
 ) {
     for (ActorRef(actor), mut score, mut scorer) in q_behaviors.iter_mut() {
-        let Ok((inventory, position, flags)) = q_actors.get(*actor) else {
+        let Ok((inventory, position, flags, work)) = q_actors.get(*actor) else {
             *score = Score(0.);
             continue;
         };
 
+        if !work.is_enabled(WorkType::Build) {
+            *score = Score(0.);
+            continue;
+        }
+
         let pos = [position.x, position.y, position.z];
 
         let mut best = None;
+        let mut best_priority = JOB_PRIORITY_MIN;
         let mut best_tags = None;
         let mut best_idx = None;
         let mut best_target = None;
@@ -117,13 +132,16 @@ pub fn score_supply(
                 [pos[0] as i32, pos[1] as i32, pos[2] as i32],
             );
 
-            if job_distance < best_dist {
+            if job.priority > best_priority
+                || (job.priority == best_priority && job_distance < best_dist)
+            {
+                best_priority = job.priority;
                 best = Some(e);
                 best_dist = job_distance;
                 best_target = Some(job_supply.target);
                 best_idx = Some(job_supply.slot_target_idx);
                 best_tags = Some(job_supply.flags.clone());
-                if job_distance < 2. {
+                if job_distance < 2. && job.priority == JOB_PRIORITY_MAX {
                     break;
                 }
             }
@@ -151,7 +169,7 @@ pub fn score_supply(
 
         // if we have the item, score is higher
         if has_item {
-            *score = Score(0.6);
+            *score = Score(work.score(WorkType::Build, best_priority, 1.));
             continue;
         }
 
@@ -169,7 +187,7 @@ pub fn score_supply(
                     &graph,
                 )
         }) {
-            *score = Score(0.2);
+            *score = Score(work.score(WorkType::Build, best_priority, WORK_READINESS_FETCH));
             continue;
         } else {
             *score = Score(0.0);
@@ -1,13 +1,31 @@
 mod behavior_build;
 mod behavior_chop;
+mod behavior_craft;
+mod behavior_drink;
+mod behavior_eat;
+mod behavior_fight;
+mod behavior_flee;
+mod behavior_follow;
+mod behavior_haul;
+mod behavior_hunt;
 mod behavior_mine;
 mod behavior_place_block;
+mod behavior_sleep;
 mod behavior_supply;
 mod behavior_wander;
 
 pub use behavior_build::*;
 pub use behavior_chop::*;
+pub use behavior_craft::*;
+pub use behavior_drink::*;
+pub use behavior_eat::*;
+pub use behavior_fight::*;
+pub use behavior_flee::*;
+pub use behavior_follow::*;
+pub use behavior_haul::*;
+pub use behavior_hunt::*;
 pub use behavior_mine::*;
 pub use behavior_place_block::*;
+pub use behavior_sleep::*;
 pub use behavior_supply::*;
 pub use behavior_wander::*;
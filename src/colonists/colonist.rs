@@ -5,8 +5,9 @@ use bevy::{
     core::Name,
     ecs::{
         component::Component,
+        entity::Entity,
         event::{Event, EventReader},
-        system::{Commands, Res, ResMut},
+        system::{Commands, Query, Res, ResMut},
     },
     math::primitives::Cuboid,
     prelude::default,
@@ -14,7 +15,6 @@ use bevy::{
     scene::SceneBundle,
     transform::components::Transform,
 };
-use bevy_mod_picking::backends::raycast::bevy_mod_raycast::markers::SimplifiedMesh;
 
 use crate::{
     rendering::{BasicMaterial, GltfBinding},
@@ -23,18 +23,25 @@ use crate::{
 };
 
 use super::{
-    Actor, Faller, Fatigue, Inventory, NavigationFlags, ScorerBuild, ScorerChop, ScorerMine,
-    ScorerPlaceBlock, ScorerSupply, ScorerWander, Thinker,
+    Actor, DailySchedule, Faller, Fatigue, Health, Inventory, Mood, NavigationFlags, Needs,
+    ScorerBuild, ScorerChop, ScorerCraft, ScorerDrink, ScorerEat, ScorerFight, ScorerFlee,
+    ScorerHaul, ScorerMine, ScorerPlaceBlock, ScorerSleep, ScorerSupply, ScorerWander, Skills,
+    Thinker, WorkPreferences, COLONIST_MELEE,
 };
 
+/// `id` names the colonist in `SimInput`s, since entities are not stable
+/// across runs.
 #[derive(Component, Default)]
-pub struct Colonist {}
+pub struct Colonist {
+    pub id: u32,
+}
 
 #[derive(Component)]
 pub struct ChildMaterials(pub Handle<BasicMaterial>);
 
 #[derive(Event)]
 pub struct SpawnColonistEvent {
+    pub entity: Option<Entity>,
     pub pos: [u32; 3],
 }
 
@@ -43,11 +50,15 @@ pub fn on_spawn_colonist(
     mut ev_spawn_colonist: EventReader<SpawnColonistEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
     asset_server: Res<AssetServer>,
+    q_colonists: Query<&Colonist>,
 ) {
-    for ev in ev_spawn_colonist.read() {
+    let next_id = q_colonists.iter().map(|c| c.id + 1).max().unwrap_or(0);
+
+    for (id, ev) in (next_id..).zip(ev_spawn_colonist.read()) {
         let gltf = asset_server.load("human.gltf#Scene0");
+        let entity = ev.entity.unwrap_or_else(|| cmd.spawn_empty().id());
 
-        cmd.spawn((
+        cmd.entity(entity).insert((
             Name::new("Colonist"),
             SceneBundle {
                 scene: gltf,
@@ -69,9 +80,18 @@ pub fn on_spawn_colonist(
                 value: 30.,
                 per_second: 5.,
             },
+            Needs::default(),
+            Mood::default(),
             Actor,
             Inventory::default(),
-            Colonist::default(),
+            (
+                Colonist { id },
+                WorkPreferences::default(),
+                Skills::default(),
+                Health::default(),
+                DailySchedule::default(),
+                COLONIST_MELEE,
+            ),
             Inspectable {
                 display_name: "Colonist".into(),
             },
@@ -83,6 +103,13 @@ pub fn on_spawn_colonist(
                     Arc::new(ScorerPlaceBlock::default()),
                     Arc::new(ScorerBuild::default()),
                     Arc::new(ScorerSupply::default()),
+                    Arc::new(ScorerHaul::default()),
+                    Arc::new(ScorerCraft::default()),
+                    Arc::new(ScorerEat),
+                    Arc::new(ScorerDrink),
+                    Arc::new(ScorerSleep),
+                    Arc::new(ScorerFight),
+                    Arc::new(ScorerFlee),
                 ],
             },
             Faller,
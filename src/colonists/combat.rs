@@ -0,0 +1,320 @@
+use std::sync::Arc;
+
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventReader},
+    query::{Has, With, Without},
+    system::{Commands, Query, Res},
+};
+use ordered_float::OrderedFloat;
+
+use crate::{common::Distance, structures::Bed, Position, Terrain};
+
+use super::{
+    assign_behavior, is_reachable, release_actor, Actor, Behavior, BehaviorNode, Blackboard,
+    Colonist, Creature, Downed, HasBehavior, Health, Item, Job, NavigationFlags, NavigationGraph,
+    PartitionPathRequest, TaskMeleeAttack, TaskMoveTo, TaskPickTarget, FLEE_DISTANCE,
+};
+
+/// Hostiles go after colonists this close.
+pub const HUNT_DISTANCE: f32 = 32.;
+/// Colonists take on hostiles this close.
+pub const FIGHT_DISTANCE: f32 = 8.;
+/// Drafted colonists strike enemies this close without being ordered to.
+pub const DEFEND_DISTANCE: f32 = 2.;
+/// Below this share of their max health, colonists run instead of fighting.
+pub const FLEE_HEALTH: f32 = 0.4;
+
+pub const COLONIST_MELEE: Melee = Melee {
+    damage: 10.,
+    cooldown_s: 1.2,
+};
+
+/// Hits anything within reach for `damage`, once every `cooldown_s`.
+#[derive(Component, Clone, Copy)]
+pub struct Melee {
+    pub damage: f32,
+    pub cooldown_s: f32,
+}
+
+/// Under the player's direct control, the colonist only acts on orders and
+/// strikes enemies within reach.
+#[derive(Component)]
+pub struct Drafted;
+
+pub type CombatantQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Position,
+        &'static NavigationFlags,
+        Option<&'static Creature>,
+        Has<Downed>,
+    ),
+    With<Actor>,
+>;
+
+/// Whether `actor` attacks `other`, `None` being a colonist. Hostiles hunt
+/// colonists, colonists fight back, animals never fight.
+pub fn is_enemy(actor: Option<&Creature>, other: Option<&Creature>) -> bool {
+    let is_other_hostile = other.is_some_and(|c| c.creature_type.is_hostile());
+
+    match actor {
+        None => is_other_hostile,
+        Some(c) if c.creature_type.is_hostile() => other.is_none(),
+        Some(_) => false,
+    }
+}
+
+/// Whether `actor` has reason to run from `other`. Everyone but hostiles
+/// fears hostiles, and wildlife fears colonists too.
+pub fn is_threat(actor: Option<&Creature>, other: Option<&Creature>) -> bool {
+    let is_other_hostile = other.is_some_and(|c| c.creature_type.is_hostile());
+
+    match actor {
+        None => is_other_hostile,
+        Some(c) if c.creature_type.is_hostile() => false,
+        Some(c) => is_other_hostile || (other.is_none() && c.owner.is_none()),
+    }
+}
+
+/// How far off the actor notices enemies and threats.
+pub fn alert_distance(creature: Option<&Creature>) -> f32 {
+    match creature {
+        None => FIGHT_DISTANCE,
+        Some(c) if c.creature_type.is_hostile() => HUNT_DISTANCE,
+        Some(_) => FLEE_DISTANCE,
+    }
+}
+
+/// Both blocks are next to each other, diagonals and steps included.
+pub fn is_in_reach(a: [i32; 3], b: [i32; 3]) -> bool {
+    Distance::chebyshev(a, b) <= 1.
+}
+
+/// The nearest enemy within `max_distance` the actor can get to. Downed
+/// colonists are left alone.
+pub fn nearest_enemy(
+    terrain: &Terrain,
+    graph: &NavigationGraph,
+    q_combatants: &CombatantQuery,
+    actor: Entity,
+    max_distance: f32,
+) -> Option<(Entity, [u32; 3])> {
+    let (_, position, flags, creature, _) = q_combatants.get(actor).ok()?;
+    let pos = position.as_i32();
+
+    q_combatants
+        .iter()
+        .filter(|(e, _, _, other, is_downed)| {
+            *e != actor && is_enemy(creature, *other) && !(*is_downed && other.is_none())
+        })
+        .map(|(e, p, _, _, _)| (e, p, Distance::diagonal(pos, p.as_i32())))
+        .filter(|(_, _, d)| *d <= max_distance)
+        .filter(|(_, p, _)| {
+            is_in_reach(pos, p.as_i32())
+                || is_reachable(
+                    &PartitionPathRequest {
+                        start: position.as_array(),
+                        goals: vec![p.as_array()],
+                        flags: *flags,
+                    },
+                    terrain,
+                    graph,
+                )
+        })
+        .min_by_key(|(_, _, d)| OrderedFloat(*d))
+        .map(|(e, p, _)| (e, p.as_array()))
+}
+
+/// The nearest threat within `max_distance`, reachable or not.
+pub fn nearest_threat(
+    q_combatants: &CombatantQuery,
+    actor: Entity,
+    max_distance: f32,
+) -> Option<[i32; 3]> {
+    let (_, position, _, creature, _) = q_combatants.get(actor).ok()?;
+    let pos = position.as_i32();
+
+    q_combatants
+        .iter()
+        .filter(|(e, _, _, other, is_downed)| {
+            *e != actor && !*is_downed && is_threat(creature, *other)
+        })
+        .map(|(_, p, _, _, _)| p.as_i32())
+        .map(|p| (p, Distance::diagonal(pos, p)))
+        .filter(|(_, d)| *d <= max_distance)
+        .min_by_key(|(_, d)| OrderedFloat(*d))
+        .map(|(p, _)| p)
+}
+
+/// Drops what actors are doing when an enemy or threat comes close, so they
+/// can choose between fighting and running. Only actors that will fight or
+/// run are alerted, healthy colonists ignore enemies they cannot get to.
+/// Drafted colonists wait for orders instead.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn combat_alert_system(
+    mut cmd: Commands,
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<
+        (Entity, Option<&Creature>, &Health, &HasBehavior),
+        (With<Actor>, Without<Drafted>, Without<Downed>),
+    >,
+    q_combatants: CombatantQuery,
+    q_behaviors: Query<&Behavior>,
+    mut q_jobs: Query<&mut Job>,
+    mut q_items: Query<&mut Item>,
+    mut q_beds: Query<&mut Bed>,
+) {
+    for (actor, creature, health, has_behavior) in q_actors.iter() {
+        let Ok(behavior) = q_behaviors.get(has_behavior.behavior_entity) else {
+            continue;
+        };
+
+        if behavior.is_urgent {
+            continue;
+        }
+
+        // creatures always hunt or run, colonists fight unless hurt
+        let is_hurt = creature.is_none() && health.value < FLEE_HEALTH * health.max;
+        let distance = alert_distance(creature);
+        let is_alerted = if is_hurt {
+            nearest_threat(&q_combatants, actor, distance).is_some()
+        } else {
+            (creature.is_some() && nearest_threat(&q_combatants, actor, distance).is_some())
+                || nearest_enemy(&terrain, &graph, &q_combatants, actor, distance).is_some()
+        };
+
+        if is_alerted {
+            release_actor(
+                &mut cmd,
+                actor,
+                Some(has_behavior),
+                &mut q_jobs,
+                &mut q_items,
+                &mut q_beds,
+            );
+        }
+    }
+}
+
+/// Drafted colonists hold their ground, striking enemies within reach.
+pub fn drafted_system(
+    mut cmd: Commands,
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_drafted: Query<Entity, (With<Drafted>, Without<HasBehavior>, Without<Downed>)>,
+    q_combatants: CombatantQuery,
+) {
+    for actor in q_drafted.iter() {
+        if nearest_enemy(&terrain, &graph, &q_combatants, actor, DEFEND_DISTANCE).is_none() {
+            continue;
+        }
+
+        let behavior = Behavior::new(
+            "Defend",
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::Task(Arc::new(TaskPickTarget {
+                    max_distance: DEFEND_DISTANCE,
+                })),
+                BehaviorNode::Task(Arc::new(TaskMeleeAttack::default())),
+            ]),
+        )
+        .urgent();
+
+        assign_behavior(&mut cmd, actor, behavior, Blackboard::default());
+    }
+}
+
+#[derive(Event)]
+pub struct SetDraftedEvent {
+    /// the colonist's `Colonist::id`
+    pub colonist: u32,
+    pub is_drafted: bool,
+}
+
+#[derive(Event)]
+pub struct OrderMoveEvent {
+    /// the colonist's `Colonist::id`
+    pub colonist: u32,
+    pub pos: [u32; 3],
+}
+
+pub fn on_set_drafted(
+    mut cmd: Commands,
+    mut ev_set_drafted: EventReader<SetDraftedEvent>,
+    q_colonists: Query<(Entity, &Colonist, Option<&HasBehavior>)>,
+    mut q_jobs: Query<&mut Job>,
+    mut q_items: Query<&mut Item>,
+    mut q_beds: Query<&mut Bed>,
+) {
+    for ev in ev_set_drafted.read() {
+        let Some((entity, _, has_behavior)) =
+            q_colonists.iter().find(|(_, c, _)| c.id == ev.colonist)
+        else {
+            println!("No colonist with id {}", ev.colonist);
+            continue;
+        };
+
+        if !ev.is_drafted {
+            cmd.entity(entity).remove::<Drafted>();
+            continue;
+        }
+
+        release_actor(
+            &mut cmd,
+            entity,
+            has_behavior,
+            &mut q_jobs,
+            &mut q_items,
+            &mut q_beds,
+        );
+
+        cmd.entity(entity).insert(Drafted);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn on_order_move(
+    mut cmd: Commands,
+    mut ev_order_move: EventReader<OrderMoveEvent>,
+    q_colonists: Query<(Entity, &Colonist, Option<&HasBehavior>), (With<Drafted>, Without<Downed>)>,
+    mut q_jobs: Query<&mut Job>,
+    mut q_items: Query<&mut Item>,
+    mut q_beds: Query<&mut Bed>,
+) {
+    for ev in ev_order_move.read() {
+        let Some((entity, _, has_behavior)) =
+            q_colonists.iter().find(|(_, c, _)| c.id == ev.colonist)
+        else {
+            println!("No drafted colonist with id {}", ev.colonist);
+            continue;
+        };
+
+        release_actor(
+            &mut cmd,
+            entity,
+            has_behavior,
+            &mut q_jobs,
+            &mut q_items,
+            &mut q_beds,
+        );
+
+        let behavior = Behavior::new(
+            "Ordered move",
+            BehaviorNode::Task(Arc::new(TaskMoveTo::default())),
+        )
+        .urgent();
+
+        let blackboard = Blackboard {
+            move_goals: vec![ev.pos],
+            primary_goal: Some(ev.pos),
+            ..Default::default()
+        };
+
+        assign_behavior(&mut cmd, entity, behavior, blackboard);
+    }
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use bevy::{
+    asset::AssetServer,
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        system::{Commands, Res},
+    },
+    math::Vec3,
+    prelude::default,
+    render::view::Visibility,
+    scene::SceneBundle,
+    transform::components::Transform,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{rendering::GltfBinding, ui::Inspectable, Position};
+
+use super::{
+    Actor, Faller, Health, Melee, NavigationFlags, ScorerFlee, ScorerFollowOwner, ScorerHunt,
+    ScorerWander, Thinker,
+};
+
+/// Pets stop following once they are this close to their owner.
+pub const FOLLOW_DISTANCE: f32 = 4.;
+/// Wild creatures run from colonists that come this close.
+pub const FLEE_DISTANCE: f32 = 5.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CreatureType {
+    Cat,
+    Rabbit,
+    /// hostile, comes up from the deep caverns to hunt colonists
+    Crawler,
+}
+
+impl CreatureType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CreatureType::Cat => "Cat",
+            CreatureType::Rabbit => "Rabbit",
+            CreatureType::Crawler => "Cave crawler",
+        }
+    }
+
+    pub fn max_health(&self) -> f32 {
+        match self {
+            CreatureType::Cat => 40.,
+            CreatureType::Rabbit => 20.,
+            CreatureType::Crawler => 60.,
+        }
+    }
+
+    pub fn is_hostile(&self) -> bool {
+        matches!(self, CreatureType::Crawler)
+    }
+
+    /// Where the creature can go. Animals are small enough to fit through
+    /// one block high gaps, crawlers go wherever colonists do.
+    pub fn flags(&self) -> NavigationFlags {
+        match self {
+            CreatureType::Cat | CreatureType::Rabbit => NavigationFlags::CAT,
+            CreatureType::Crawler => NavigationFlags::COLONIST,
+        }
+    }
+
+    pub fn melee(&self) -> Option<Melee> {
+        match self {
+            CreatureType::Cat | CreatureType::Rabbit => None,
+            CreatureType::Crawler => Some(Melee {
+                damage: 8.,
+                cooldown_s: 1.5,
+            }),
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            CreatureType::Cat | CreatureType::Rabbit => 1.,
+            CreatureType::Crawler => 1.5,
+        }
+    }
+}
+
+/// An animal, either a pet, wildlife or a hostile. `owner` is the id of the
+/// colonist a pet belongs to, the others have none.
+#[derive(Component, Clone, Copy)]
+pub struct Creature {
+    pub creature_type: CreatureType,
+    pub owner: Option<u32>,
+}
+
+#[derive(Event)]
+pub struct SpawnCreatureEvent {
+    pub entity: Option<Entity>,
+    pub pos: [u32; 3],
+    pub creature_type: CreatureType,
+    pub owner: Option<u32>,
+    /// defaults to full health
+    pub health: Option<f32>,
+}
+
+pub fn on_spawn_creature(
+    mut cmd: Commands,
+    mut ev_spawn_creature: EventReader<SpawnCreatureEvent>,
+    asset_server: Res<AssetServer>,
+) {
+    for ev in ev_spawn_creature.read() {
+        let entity = ev.entity.unwrap_or_else(|| cmd.spawn_empty().id());
+        let max_health = ev.creature_type.max_health();
+        let label = ev.creature_type.label();
+        let scale = ev.creature_type.scale();
+
+        cmd.entity(entity).insert((
+            Name::new(label),
+            SceneBundle {
+                scene: asset_server.load("creature.gltf#Scene0"),
+                transform: Transform::from_xyz(
+                    ev.pos[0] as f32 + 0.5,
+                    ev.pos[1] as f32,
+                    ev.pos[2] as f32 + 0.5,
+                )
+                .with_scale(Vec3::splat(scale)),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            GltfBinding {
+                armature_name: None,
+                mesh_name: "CreatureMesh".into(),
+                texture_path: None,
+            },
+            Creature {
+                creature_type: ev.creature_type,
+                owner: ev.owner,
+            },
+            Health {
+                value: ev.health.unwrap_or(max_health).min(max_health),
+                max: max_health,
+            },
+            Actor,
+            Inspectable {
+                display_name: label.into(),
+            },
+            Thinker {
+                score_builders: vec![
+                    Arc::new(ScorerWander),
+                    Arc::new(ScorerFollowOwner),
+                    Arc::new(ScorerFlee),
+                    Arc::new(ScorerHunt),
+                ],
+            },
+            Faller,
+            Position::default(),
+            ev.creature_type.flags(),
+        ));
+
+        if let Some(melee) = ev.creature_type.melee() {
+            cmd.entity(entity).insert(melee);
+        }
+    }
+}
@@ -2,7 +2,8 @@ use bevy::{
     ecs::{
         component::Component,
         entity::Entity,
-        query::{With, Without},
+        event::EventWriter,
+        query::{Has, With, Without},
         system::{Commands, Query, Res},
     },
     transform::components::Transform,
@@ -10,17 +11,20 @@ use bevy::{
 
 use crate::{colonists::BlockMove, Terrain};
 
-use super::{InInventory, InSlot, NavigationFlags, NavigationGraph};
+use super::{
+    fall_damage, DamageEvent, Health, InInventory, InSlot, NavigationFlags, NavigationGraph,
+};
 
 #[derive(Component)]
 pub struct Faller;
 
+#[allow(clippy::type_complexity)]
 pub fn apply_falling(
     mut cmd: Commands,
     terrain: Res<Terrain>,
     graph: Res<NavigationGraph>,
     q_fallers: Query<
-        (Entity, &Transform, Option<&NavigationFlags>),
+        (Entity, &Transform, Option<&NavigationFlags>, Has<Health>),
         (
             With<Faller>,
             Without<BlockMove>,
@@ -28,14 +32,20 @@ pub fn apply_falling(
             Without<InInventory>,
         ),
     >,
+    mut ev_damage: EventWriter<DamageEvent>,
 ) {
-    for (entity, transform, opt_flags) in q_fallers.iter() {
+    for (entity, transform, opt_flags, has_health) in q_fallers.iter() {
         let x = transform.translation.x as u32;
         let y = transform.translation.y as u32;
         let z = transform.translation.z as u32;
 
         let [chunk_idx, block_idx] = terrain.get_block_indexes(x, y, z);
 
+        // nothing falls through a chunk that is not loaded
+        if !terrain.is_chunk_resident(chunk_idx) {
+            continue;
+        }
+
         if terrain.get_partition_id(chunk_idx, block_idx).is_some() {
             continue;
         }
@@ -69,6 +79,15 @@ pub fn apply_falling(
                                 target: [x as i32, sub_y as i32, z as i32],
                                 look_at: false,
                             });
+
+                            let damage = fall_damage(delta_y);
+
+                            if has_health && damage > 0. {
+                                ev_damage.send(DamageEvent {
+                                    entity,
+                                    amount: damage,
+                                });
+                            }
                             break;
                         }
                     }
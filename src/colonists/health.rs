@@ -0,0 +1,298 @@
+use bevy::{
+    asset::Handle,
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        query::{With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::{BuildChildren, DespawnRecursiveExt},
+    math::{EulerRot, Quat},
+    prelude::default,
+    render::view::Visibility,
+    scene::{Scene, SceneBundle},
+    time::Time,
+    transform::components::Transform,
+};
+
+use crate::{rendering::GltfBinding, structures::Bed, ui::Inspectable, Position, Terrain};
+
+use super::{
+    AnimClip, Animator, HasBehavior, InInventory, Inventory, Item, Job, JobAssignment,
+    NavigationGraph, Path,
+};
+
+pub const HEALTH_MAX: f32 = 100.;
+/// Below this share of their max health, actors collapse and drop whatever
+/// they were doing.
+pub const HEALTH_DOWNED: f32 = 0.25;
+/// Downed actors get back up once they have healed to this share.
+pub const HEALTH_RECOVERED: f32 = 0.5;
+/// Health regained per second.
+pub const HEALTH_REGEN: f32 = 0.5;
+/// Damage per second while touching a destructive fluid.
+pub const MAGMA_DAMAGE: f32 = 40.;
+/// Blocks a colonist can drop without getting hurt.
+pub const FALL_SAFE_HEIGHT: u32 = 3;
+pub const FALL_DAMAGE_PER_BLOCK: f32 = 15.;
+
+#[derive(Component, Clone, Copy)]
+pub struct Health {
+    pub value: f32,
+    pub max: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self {
+            value: HEALTH_MAX,
+            max: HEALTH_MAX,
+        }
+    }
+}
+
+/// Too hurt to act, the colonist lies still until healed.
+#[derive(Component)]
+pub struct Downed;
+
+/// What is left of a colonist after they die. Corpses are not saved, a
+/// loaded colony has buried its dead.
+#[derive(Component)]
+pub struct Corpse;
+
+#[derive(Event)]
+pub struct DamageEvent {
+    pub entity: Entity,
+    pub amount: f32,
+}
+
+pub fn on_damage(mut ev_damage: EventReader<DamageEvent>, mut q_health: Query<&mut Health>) {
+    for ev in ev_damage.read() {
+        let Ok(mut health) = q_health.get_mut(ev.entity) else {
+            continue;
+        };
+
+        health.value = (health.value - ev.amount).max(0.);
+    }
+}
+
+/// Fall damage for a drop of `distance` blocks.
+pub fn fall_damage(distance: u32) -> f32 {
+    distance.saturating_sub(FALL_SAFE_HEIGHT) as f32 * FALL_DAMAGE_PER_BLOCK
+}
+
+/// Heals colonists over time, burns them in magma, and downs or kills the
+/// ones that are hurt badly enough.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn health_system(
+    mut cmd: Commands,
+    time: Res<Time>,
+    mut terrain: ResMut<Terrain>,
+    mut graph: ResMut<NavigationGraph>,
+    mut q_health: Query<(
+        Entity,
+        &mut Health,
+        &Position,
+        &mut Transform,
+        Option<&Downed>,
+        Option<&HasBehavior>,
+        Option<&Inventory>,
+        Option<&mut Animator>,
+        Option<(&Handle<Scene>, &GltfBinding)>,
+    )>,
+    mut q_jobs: Query<&mut Job>,
+    mut q_items: Query<&mut Item>,
+    mut q_item_positions: Query<&mut Position, (With<Item>, Without<Health>)>,
+    mut q_beds: Query<&mut Bed>,
+) {
+    let dt = time.delta_seconds();
+
+    for (
+        entity,
+        mut health,
+        position,
+        mut transform,
+        downed,
+        has_behavior,
+        inventory,
+        animator,
+        model,
+    ) in q_health.iter_mut()
+    {
+        let [x, y, z] = position.as_array();
+
+        let is_burning = [
+            terrain.get_block(x, y, z),
+            terrain.get_block_i32(x as i32, y as i32 - 1, z as i32),
+        ]
+        .iter()
+        .any(|b| b.block.fluid().is_some_and(|f| f.is_destructive));
+
+        if is_burning {
+            health.value = (health.value - MAGMA_DAMAGE * dt).max(0.);
+        } else if health.value > 0. {
+            health.value = (health.value + HEALTH_REGEN * dt).min(health.max);
+        }
+
+        if health.value <= 0. {
+            release_actor(
+                &mut cmd,
+                entity,
+                has_behavior,
+                &mut q_jobs,
+                &mut q_items,
+                &mut q_beds,
+            );
+
+            // what they carried is left where they fell. it is placed here
+            // rather than in update_positions, which skips items that have
+            // not changed partition since they were picked up
+            let partition_id = terrain.get_partition_id(position.chunk_idx, position.block_idx);
+
+            for item in inventory.iter().flat_map(|i| i.items.iter()) {
+                if let Ok(mut i) = q_items.get_mut(*item) {
+                    i.reserved = None;
+                }
+
+                if let Ok(mut item_position) = q_item_positions.get_mut(*item) {
+                    terrain.remove_item(item_position.chunk_idx, item_position.block_idx, item);
+                    terrain.add_item(position.chunk_idx, position.block_idx, *item);
+
+                    if let Some(item_partition_id) = item_position.partition_id {
+                        graph.remove_item_from_partition(&item_partition_id, item);
+                    }
+
+                    if let Some(partition_id) = partition_id {
+                        graph.add_item_to_partition(&partition_id, *item);
+                    }
+
+                    item_position.x = x;
+                    item_position.y = y;
+                    item_position.z = z;
+                    item_position.chunk_idx = position.chunk_idx;
+                    item_position.block_idx = position.block_idx;
+                    item_position.partition_id = partition_id;
+                }
+
+                cmd.entity(*item)
+                    .remove::<InInventory>()
+                    .remove_parent()
+                    .insert((
+                        Transform::from_translation(transform.translation),
+                        Visibility::Inherited,
+                    ));
+            }
+
+            for mut bed in q_beds.iter_mut() {
+                if bed.owner == Some(entity) {
+                    bed.owner = None;
+                }
+            }
+
+            terrain.remove_item(position.chunk_idx, position.block_idx, &entity);
+
+            if let Some(partition_id) = position.partition_id {
+                graph.remove_item_from_partition(&partition_id, &entity);
+            }
+
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
+            // the body keeps the model it had, but no longer moves
+            if let Some((scene, binding)) = model {
+                cmd.spawn((
+                    Name::new("Corpse"),
+                    Corpse,
+                    SceneBundle {
+                        scene: scene.clone(),
+                        transform: Transform::from_translation(transform.translation)
+                            .with_rotation(
+                                Quat::from_rotation_y(yaw)
+                                    * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+                            ),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    GltfBinding {
+                        armature_name: None,
+                        mesh_name: binding.mesh_name.clone(),
+                        texture_path: binding.texture_path.clone(),
+                    },
+                    Inspectable {
+                        display_name: "Corpse".into(),
+                    },
+                ));
+            }
+
+            cmd.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        if downed.is_none() && health.value < HEALTH_DOWNED * health.max {
+            release_actor(
+                &mut cmd,
+                entity,
+                has_behavior,
+                &mut q_jobs,
+                &mut q_items,
+                &mut q_beds,
+            );
+
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            transform.rotation =
+                Quat::from_rotation_y(yaw) * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
+
+            if let Some(mut animator) = animator {
+                animator.clip = AnimClip::LieDown;
+            }
+
+            cmd.entity(entity).insert(Downed);
+        } else if downed.is_some() && health.value >= HEALTH_RECOVERED * health.max {
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            transform.rotation = Quat::from_rotation_y(yaw);
+
+            if let Some(mut animator) = animator {
+                animator.clip = AnimClip::Idle;
+            }
+
+            cmd.entity(entity).remove::<Downed>();
+        }
+    }
+}
+
+/// Cancels the actor's behavior and frees the job, items and bed it had
+/// claimed, so that others can take them over.
+pub fn release_actor(
+    cmd: &mut Commands,
+    actor: Entity,
+    has_behavior: Option<&HasBehavior>,
+    q_jobs: &mut Query<&mut Job>,
+    q_items: &mut Query<&mut Item>,
+    q_beds: &mut Query<&mut Bed>,
+) {
+    if let Some(has_behavior) = has_behavior {
+        cmd.entity(has_behavior.behavior_entity).despawn();
+    }
+
+    cmd.entity(actor)
+        .remove::<(HasBehavior, JobAssignment, Path)>();
+
+    for mut job in q_jobs.iter_mut() {
+        if job.assignee == Some(actor) {
+            job.assignee = None;
+        }
+    }
+
+    for mut item in q_items.iter_mut() {
+        if item.reserved == Some(actor) {
+            item.reserved = None;
+        }
+    }
+
+    for mut bed in q_beds.iter_mut() {
+        if bed.reserved == Some(actor) {
+            bed.reserved = None;
+        }
+    }
+}
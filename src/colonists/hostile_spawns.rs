@@ -0,0 +1,114 @@
+use bevy::ecs::{
+    event::EventWriter,
+    query::With,
+    system::{Query, Res, ResMut},
+};
+
+use crate::{
+    common::{Distance, Rand},
+    simulation::{SimClock, SIM_TICK_SECONDS},
+    Position, Terrain, TerrainGenerator,
+};
+
+use super::{
+    is_reachable, Colonist, Creature, CreatureType, NavigationFlags, NavigationGraph,
+    PartitionPathRequest, SpawnCreatureEvent,
+};
+
+/// Seconds between crawlers coming up from the deep.
+pub const HOSTILE_SPAWN_INTERVAL_S: f64 = 120.;
+/// No more crawlers come while this many are about.
+pub const HOSTILE_MAX: usize = 4;
+/// Crawlers come out of sight, at least this far from any colonist.
+pub const HOSTILE_SPAWN_DISTANCE: f32 = 16.;
+
+/// Every so often a crawler comes out of a dark cavern below the cavern
+/// level that can reach the colony. Worlds not made by the generator have no
+/// caverns to come from.
+#[allow(clippy::too_many_arguments)]
+pub fn hostile_spawn_system(
+    clock: Res<SimClock>,
+    generator: Option<Res<TerrainGenerator>>,
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    mut rand: ResMut<Rand>,
+    q_colonists: Query<&Position, With<Colonist>>,
+    q_creatures: Query<&Creature>,
+    mut ev_spawn_creature: EventWriter<SpawnCreatureEvent>,
+) {
+    let Some(generator) = generator else {
+        return;
+    };
+
+    let interval = (HOSTILE_SPAWN_INTERVAL_S / SIM_TICK_SECONDS) as u64;
+
+    if clock.tick == 0 || !clock.tick.is_multiple_of(interval) {
+        return;
+    }
+
+    let hostiles = q_creatures
+        .iter()
+        .filter(|c| c.creature_type.is_hostile())
+        .count();
+
+    if hostiles >= HOSTILE_MAX {
+        return;
+    }
+
+    let colonists: Vec<[u32; 3]> = q_colonists.iter().map(|p| p.as_array()).collect();
+
+    if colonists.is_empty() {
+        return;
+    }
+
+    let creature_type = CreatureType::Crawler;
+    let cavern_level = generator.cavern_level(&terrain);
+
+    let spots: Vec<[u32; 3]> = graph
+        .partitions()
+        .filter(|p| p.flags.contains(NavigationFlags::TALL) && p.extents.max_y < cavern_level)
+        .flat_map(|p| {
+            p.blocks
+                .iter()
+                .map(|block_idx| terrain.get_block_world_pos(p.chunk_idx, *block_idx))
+        })
+        .filter(|[x, y, z]| {
+            terrain.get_block(*x, *y, *z).is_empty() && terrain.get_sunlight_xyz(*x, *y, *z) == 0
+        })
+        .filter(|spot| {
+            colonists.iter().all(|c| {
+                Distance::diagonal(spot.map(|v| v as i32), c.map(|v| v as i32))
+                    >= HOSTILE_SPAWN_DISTANCE
+            })
+        })
+        .collect();
+
+    // try a few, most caverns lead nowhere
+    for _ in 0..8 {
+        if spots.is_empty() {
+            return;
+        }
+
+        let spot = rand.pick(&spots);
+
+        let request = PartitionPathRequest {
+            start: spot,
+            goals: colonists.clone(),
+            flags: creature_type.flags(),
+        };
+
+        if !is_reachable(&request, &terrain, &graph) {
+            continue;
+        }
+
+        ev_spawn_creature.send(SpawnCreatureEvent {
+            entity: None,
+            pos: spot,
+            creature_type,
+            owner: None,
+            health: None,
+        });
+
+        return;
+    }
+}
@@ -9,18 +9,21 @@ use bevy::{
     },
     hierarchy::DespawnRecursiveExt,
     reflect::Reflect,
-    utils::HashSet,
 };
 use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
+use serde::{Deserialize, Serialize};
 
-use crate::{rendering::SlotIndex, Position, Terrain};
+use crate::{
+    common::StableHashSet, rendering::SlotIndex, structures::PartSlots, Position, Terrain,
+};
 
 use super::NavigationGraph;
 
 #[derive(Component, Default, Reflect, InspectorOptions)]
 #[reflect(InspectorOptions)]
 pub struct Inventory {
-    pub items: HashSet<Entity>,
+    #[reflect(ignore)]
+    pub items: StableHashSet<Entity>,
 }
 
 #[derive(Component, Reflect, InspectorOptions)]
@@ -42,7 +45,7 @@ pub struct InSlot {
     pub slot_idx: SlotIndex,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug, Reflect, InspectorOptions)]
+#[derive(Clone, Copy, PartialEq, Debug, Reflect, InspectorOptions, Serialize, Deserialize)]
 #[reflect(InspectorOptions)]
 pub enum ItemTag {
     Axe,
@@ -50,6 +53,12 @@ pub enum ItemTag {
     Stone,
     Log,
     BasicBuildMaterial,
+    Food,
+    Plank,
+    /// placed as a terrain block
+    Block,
+    Soil,
+    Ore,
 }
 
 impl Display for ItemTag {
@@ -72,25 +81,40 @@ pub fn destroy_items(
     mut graph: ResMut<NavigationGraph>,
     mut cmd: Commands,
     q_items: Query<&Position>,
+    q_slots: Query<&PartSlots>,
     mut ev_destroy_item: EventReader<DestroyItemEvent>,
 ) {
-    for ev in ev_destroy_item.read() {
-        cmd.entity(ev.entity).despawn_recursive();
+    let mut entities = ev_destroy_item
+        .read()
+        .map(|ev| ev.entity)
+        .collect::<Vec<_>>();
+
+    // the parts of a tool are destroyed with it
+    let parts = entities
+        .iter()
+        .filter_map(|e| q_slots.get(*e).ok())
+        .flat_map(|slots| slots.as_vec().into_iter().filter_map(|s| s.content))
+        .collect::<Vec<_>>();
+
+    entities.extend(parts);
+
+    for entity in entities {
+        cmd.entity(entity).despawn_recursive();
 
-        let Ok(position) = q_items.get(ev.entity) else {
+        let Ok(position) = q_items.get(entity) else {
             continue;
         };
 
-        terrain.remove_item(position.chunk_idx, position.block_idx, &ev.entity);
+        terrain.remove_item(position.chunk_idx, position.block_idx, &entity);
 
         let Some(partition_id) = position.partition_id else {
             continue;
         };
 
-        if !graph.remove_item_from_partition(&partition_id, &ev.entity) {
+        if !graph.remove_item_from_partition(&partition_id, &entity) {
             println!(
                 "Item not in expected partition! item={} partition_id={}",
-                ev.entity.index(),
+                entity.index(),
                 partition_id
             );
         }
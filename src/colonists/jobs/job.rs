@@ -9,22 +9,41 @@ use bevy::{
     hierarchy::DespawnRecursiveExt,
     time::Time,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{BlockType, Terrain};
 
-#[derive(Clone, Debug, Copy, PartialEq)]
+#[derive(Clone, Debug, Copy, PartialEq, Serialize, Deserialize)]
 pub enum JobType {
     Mine,
     Chop,
     PlaceBlock(BlockType),
     Build,
     Supply,
+    Haul,
+    Craft,
 }
 
+pub const JOB_PRIORITY_MIN: u8 = 1;
+pub const JOB_PRIORITY_MAX: u8 = 5;
+pub const JOB_PRIORITY_DEFAULT: u8 = 3;
+
 #[derive(Component, Clone, Copy)]
 pub struct Job {
     pub job_type: JobType,
     pub assignee: Option<Entity>,
+    /// from `JOB_PRIORITY_MIN` to `JOB_PRIORITY_MAX`, higher is done first
+    pub priority: u8,
+}
+
+impl Job {
+    pub fn new(job_type: JobType) -> Self {
+        Self {
+            job_type,
+            assignee: None,
+            priority: JOB_PRIORITY_DEFAULT,
+        }
+    }
 }
 
 #[derive(Component)]
@@ -45,6 +64,7 @@ pub struct IsJobCancelled;
 pub struct IsJobCompleted;
 
 #[derive(Component)]
+#[allow(dead_code)]
 pub struct JobAssignment {
     pub job: Entity,
 }
@@ -125,6 +145,34 @@ pub fn on_cancel_job(
     }
 }
 
+/// Sets the priority of every job with a target inside the area.
+#[derive(Event)]
+pub struct SetJobPriorityEvent {
+    pub min: [u32; 3],
+    pub max: [u32; 3],
+    pub priority: u8,
+}
+
+pub fn on_set_job_priority(
+    mut ev_set_job_priority: EventReader<SetJobPriorityEvent>,
+    mut q_jobs: Query<(&mut Job, &JobLocation), Without<IsJobCancelled>>,
+) {
+    for ev in ev_set_job_priority.read() {
+        let priority = ev.priority.clamp(JOB_PRIORITY_MIN, JOB_PRIORITY_MAX);
+
+        for (mut job, job_location) in q_jobs.iter_mut() {
+            let is_inside = job_location
+                .targets
+                .iter()
+                .any(|t| (0..3).all(|i| t[i] >= ev.min[i] && t[i] <= ev.max[i]));
+
+            if is_inside {
+                job.priority = priority;
+            }
+        }
+    }
+}
+
 pub fn job_access_points_many(targets: &[[u32; 3]], job: JobType) -> Vec<[u32; 3]> {
     let points = targets.iter().flat_map(|t| job_access_points(*t, job));
 
@@ -148,6 +196,9 @@ pub fn job_access_points(pos: [u32; 3], job: JobType) -> Vec<[u32; 3]> {
     let [x, y, z] = pos;
 
     match job {
+        // items are set down where the colonist stands, and stations are
+        // worked from their hotspots
+        JobType::Haul | JobType::Craft => vec![pos],
         JobType::Chop => {
             let mut goals = vec![[x + 1, y, z], [x, y, z + 1], [x + 1, y, z + 1]];
 
@@ -2,7 +2,7 @@ use bevy::ecs::{
     component::Component,
     entity::Entity,
     event::{Event, EventReader, EventWriter},
-    query::Without,
+    query::{With, Without},
     system::{Commands, Query},
 };
 
@@ -23,6 +23,29 @@ pub struct JobBuild {
     pub structure: Entity,
 }
 
+/// A placed structure whose build job is spawned once its tiles are known.
+#[derive(Component)]
+pub struct IsAwaitingBuildJob;
+
+pub fn spawn_awaiting_build_jobs(
+    mut cmd: Commands,
+    mut ev_spawn_job_build: EventWriter<SpawnJobBuildEvent>,
+    q_structures: Query<(Entity, &Structure), With<IsAwaitingBuildJob>>,
+) {
+    for (entity, structure) in q_structures.iter() {
+        if structure.is_dirty {
+            continue;
+        }
+
+        cmd.entity(entity).remove::<IsAwaitingBuildJob>();
+
+        // invalid structures are removed by check_structures
+        if structure.is_valid {
+            ev_spawn_job_build.send(SpawnJobBuildEvent { structure: entity });
+        }
+    }
+}
+
 pub fn on_spawn_job_build(
     mut cmd: Commands,
     mut ev_spawn_job_build: EventReader<SpawnJobBuildEvent>,
@@ -82,10 +105,7 @@ pub fn on_spawn_job_build(
         }
 
         cmd.spawn((
-            Job {
-                job_type: super::JobType::Build,
-                assignee: None,
-            },
+            Job::new(super::JobType::Build),
             JobBuild {
                 structure: ev.structure,
             },
@@ -48,10 +48,7 @@ pub fn on_spawn_job_chop(
 
             if chop {
                 cmd.spawn((
-                    Job {
-                        job_type: JobType::Chop,
-                        assignee: None,
-                    },
+                    Job::new(JobType::Chop),
                     JobChop { tree: tree_entity },
                     JobLocation {
                         targets: tree.trunk.clone(),
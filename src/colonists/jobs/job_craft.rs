@@ -0,0 +1,89 @@
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventWriter,
+    query::Without,
+    system::{Commands, Query},
+};
+
+use crate::{
+    items::{CraftBills, RecipeType},
+    structures::Structure,
+};
+
+use super::{IsJobCancelled, Job, JobCancelEvent, JobLocation, JobType};
+
+#[derive(Component, Clone, Copy)]
+pub struct JobCraft {
+    pub station: Entity,
+    pub recipe: RecipeType,
+}
+
+/// Spawns a craft job for the first bill of every built station that does
+/// not have one yet.
+pub fn spawn_craft_jobs(
+    mut cmd: Commands,
+    q_stations: Query<(Entity, &Structure, &CraftBills)>,
+    q_jobs: Query<&JobCraft, Without<IsJobCancelled>>,
+) {
+    for (entity, structure, bills) in q_stations.iter() {
+        if !structure.is_built() || q_jobs.iter().any(|j| j.station == entity) {
+            continue;
+        }
+
+        let Some(bill) = bills.current() else {
+            continue;
+        };
+
+        // colonists work the station from its hotspots
+        let targets = structure
+            .tiles
+            .iter()
+            .filter(|t| t.hotspot.is_some())
+            .map(|t| {
+                [
+                    t.position[0] as u32,
+                    t.position[1] as u32,
+                    t.position[2] as u32,
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        if targets.is_empty() {
+            println!("Station has no hotspots, cannot craft!");
+            continue;
+        }
+
+        cmd.spawn((
+            Job::new(JobType::Craft),
+            JobCraft {
+                station: entity,
+                recipe: bill.recipe,
+            },
+            JobLocation {
+                targets,
+                primary_target: structure.position,
+                last_accessibility_check: 0.,
+                source: None,
+            },
+        ));
+    }
+}
+
+/// Craft jobs are cancelled when the station is removed or its bill for the
+/// recipe is done or cleared.
+pub fn check_job_craft_valid(
+    q_jobs: Query<(Entity, &JobCraft), Without<IsJobCancelled>>,
+    q_stations: Query<&CraftBills>,
+    mut ev_job_cancel: EventWriter<JobCancelEvent>,
+) {
+    for (entity, job_craft) in q_jobs.iter() {
+        let is_billed = q_stations
+            .get(job_craft.station)
+            .is_ok_and(|b| b.current().is_some_and(|c| c.recipe == job_craft.recipe));
+
+        if !is_billed {
+            ev_job_cancel.send(JobCancelEvent(entity));
+        }
+    }
+}
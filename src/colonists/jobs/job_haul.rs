@@ -0,0 +1,193 @@
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventWriter,
+    query::{With, Without},
+    system::{Commands, Query},
+};
+
+use crate::{
+    colonists::{InInventory, InSlot, Item},
+    common::{Distance, StableHashMap, StableHashSet},
+    items::{InStockpile, Stockpile},
+    Position,
+};
+
+use super::{IsJobCancelled, Job, JobCancelEvent, JobLocation, JobType};
+
+#[derive(Component, Clone, Copy)]
+pub struct JobHaul {
+    pub item: Entity,
+    pub stockpile: Entity,
+}
+
+/// Spawns a haul job for every loose item with room for it in a stockpile.
+/// Items already lying in a stockpile that accepts them are marked stored,
+/// and items in a stockpile that is gone are loose again. Full stockpiles are
+/// not searched.
+pub fn spawn_haul_jobs(
+    mut cmd: Commands,
+    q_stockpiles: Query<(Entity, &Stockpile)>,
+    q_loose_items: Query<
+        (Entity, &Item, &Position),
+        (Without<InInventory>, Without<InSlot>, Without<InStockpile>),
+    >,
+    q_stored_items: Query<(Entity, &InStockpile, &Position)>,
+    q_jobs: Query<(&JobHaul, &JobLocation), Without<IsJobCancelled>>,
+) {
+    let mut tile_counts: StableHashMap<[u32; 3], u32> = StableHashMap::default();
+    let mut hauled = StableHashSet::default();
+
+    for (item_entity, in_stockpile, position) in q_stored_items.iter() {
+        let pos = position.as_array();
+
+        if q_stockpiles
+            .get(in_stockpile.0)
+            .is_ok_and(|(_, s)| s.contains(pos))
+        {
+            *tile_counts.entry(pos).or_default() += 1;
+        } else {
+            cmd.entity(item_entity).remove::<InStockpile>();
+        }
+    }
+
+    if q_stockpiles.is_empty() || q_loose_items.is_empty() {
+        return;
+    }
+
+    for (job_haul, job_location) in q_jobs.iter() {
+        *tile_counts.entry(job_location.primary_target).or_default() += 1;
+        hauled.insert(job_haul.item);
+    }
+
+    let mut stockpile_tiles: StableHashSet<[u32; 3]> = StableHashSet::default();
+    let mut free_slots: StableHashMap<Entity, u32> = StableHashMap::default();
+
+    for (stockpile_entity, stockpile) in q_stockpiles.iter() {
+        let free = stockpile
+            .tiles
+            .iter()
+            .map(|t| {
+                stockpile
+                    .capacity
+                    .saturating_sub(tile_counts.get(t).copied().unwrap_or(0))
+            })
+            .sum();
+
+        stockpile_tiles.extend(stockpile.tiles.iter().copied());
+        free_slots.insert(stockpile_entity, free);
+    }
+
+    for (item_entity, item, position) in q_loose_items.iter() {
+        if item.reserved.is_some() || hauled.contains(&item_entity) {
+            continue;
+        }
+
+        let pos = position.as_array();
+
+        let stored_in = if stockpile_tiles.contains(&pos) {
+            q_stockpiles
+                .iter()
+                .find(|(_, s)| s.contains(pos) && s.accepts(&item.tags))
+        } else {
+            None
+        };
+
+        if let Some((stockpile_entity, _)) = stored_in {
+            cmd.entity(item_entity)
+                .insert(InStockpile(stockpile_entity));
+            add_to_tile(&mut tile_counts, &mut free_slots, stockpile_entity, pos);
+            continue;
+        }
+
+        if free_slots.values().all(|f| *f == 0) {
+            continue;
+        }
+
+        let mut best = None;
+        let mut best_dist = f32::MAX;
+
+        for (stockpile_entity, stockpile) in q_stockpiles.iter() {
+            if free_slots.get(&stockpile_entity) == Some(&0) || !stockpile.accepts(&item.tags) {
+                continue;
+            }
+
+            for tile in stockpile.tiles.iter() {
+                if tile_counts.get(tile).copied().unwrap_or(0) >= stockpile.capacity {
+                    continue;
+                }
+
+                let dist = Distance::manhattan(
+                    [tile[0] as i32, tile[1] as i32, tile[2] as i32],
+                    [pos[0] as i32, pos[1] as i32, pos[2] as i32],
+                );
+
+                if dist < best_dist {
+                    best = Some((stockpile_entity, *tile));
+                    best_dist = dist;
+                }
+            }
+        }
+
+        let Some((stockpile_entity, tile)) = best else {
+            continue;
+        };
+
+        add_to_tile(&mut tile_counts, &mut free_slots, stockpile_entity, tile);
+
+        cmd.spawn((
+            Job::new(JobType::Haul),
+            JobHaul {
+                item: item_entity,
+                stockpile: stockpile_entity,
+            },
+            JobLocation {
+                targets: vec![tile],
+                primary_target: tile,
+                last_accessibility_check: 0.,
+                source: Some(pos),
+            },
+        ));
+    }
+}
+
+/// Counts an item against a stockpile tile and the stockpile's free slots.
+fn add_to_tile(
+    tile_counts: &mut StableHashMap<[u32; 3], u32>,
+    free_slots: &mut StableHashMap<Entity, u32>,
+    stockpile: Entity,
+    tile: [u32; 3],
+) {
+    *tile_counts.entry(tile).or_default() += 1;
+
+    if let Some(free) = free_slots.get_mut(&stockpile) {
+        *free = free.saturating_sub(1);
+    }
+}
+
+/// Haul jobs are cancelled when the item is used or taken by someone else,
+/// or the stockpile is removed.
+pub fn check_job_haul_valid(
+    q_jobs: Query<(Entity, &Job, &JobHaul, &JobLocation), Without<IsJobCancelled>>,
+    q_items: Query<Option<&InInventory>, (With<Item>, Without<InSlot>)>,
+    q_stockpiles: Query<&Stockpile>,
+    mut ev_job_cancel: EventWriter<JobCancelEvent>,
+) {
+    for (entity, job, job_haul, job_location) in q_jobs.iter() {
+        let Ok(in_inventory) = q_items.get(job_haul.item) else {
+            ev_job_cancel.send(JobCancelEvent(entity));
+            continue;
+        };
+
+        let is_held_by_other =
+            in_inventory.is_some_and(|i| job.assignee.is_none_or(|a| a != i.holder));
+
+        let is_stockpile_valid = q_stockpiles
+            .get(job_haul.stockpile)
+            .is_ok_and(|s| s.contains(job_location.primary_target));
+
+        if is_held_by_other || !is_stockpile_valid {
+            ev_job_cancel.send(JobCancelEvent(entity));
+        }
+    }
+}
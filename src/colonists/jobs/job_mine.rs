@@ -35,10 +35,7 @@ pub fn on_spawn_job_mine(
         }
 
         cmd.spawn((
-            Job {
-                job_type: JobType::Mine,
-                assignee: None,
-            },
+            Job::new(JobType::Mine),
             JobMine,
             JobLocation {
                 targets: vec![ev.pos],
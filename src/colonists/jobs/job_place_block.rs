@@ -23,10 +23,7 @@ pub fn on_spawn_job_place_block(
 ) {
     for ev in ev_spawn_place_block_job.read() {
         cmd.spawn((
-            Job {
-                job_type: JobType::PlaceBlock(ev.block_type),
-                assignee: None,
-            },
+            Job::new(JobType::PlaceBlock(ev.block_type)),
             JobPlaceBlock,
             JobLocation {
                 targets: vec![ev.pos],
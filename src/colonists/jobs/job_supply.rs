@@ -32,10 +32,7 @@ pub fn on_spawn_job_supply(
 ) {
     for ev in ev_spawn_job_supply.read() {
         cmd.spawn((
-            Job {
-                job_type: super::JobType::Supply,
-                assignee: None,
-            },
+            Job::new(super::JobType::Supply),
             JobSupply {
                 flags: ev.flags.clone(),
                 slot_target_idx: ev.slot_taget_idx,
@@ -1,6 +1,8 @@
 mod job;
 mod job_build;
 mod job_chop;
+mod job_craft;
+mod job_haul;
 mod job_mine;
 mod job_place_block;
 mod job_supply;
@@ -8,6 +10,8 @@ mod job_supply;
 pub use job::*;
 pub use job_build::*;
 pub use job_chop::*;
+pub use job_craft::*;
+pub use job_haul::*;
 pub use job_mine::*;
 pub use job_place_block::*;
 pub use job_supply::*;
@@ -3,29 +3,49 @@ mod behavior;
 mod behavior_pick;
 mod behaviors;
 mod colonist;
+mod combat;
+mod creature;
 mod falling;
 mod fatigue;
+mod health;
+mod hostile_spawns;
 mod inventory;
 mod jobs;
 mod movement;
+mod needs;
 mod partition;
 mod partitioning;
+mod path_cache;
+mod path_queue;
 mod pathfinding;
+mod schedule;
 mod scorer;
+mod skills;
 mod tasks;
+mod work;
 
 pub use animations::*;
 pub use behavior::*;
 pub use behavior_pick::*;
 pub use behaviors::*;
 pub use colonist::*;
+pub use combat::*;
+pub use creature::*;
 pub use falling::*;
 pub use fatigue::*;
+pub use health::*;
+pub use hostile_spawns::*;
 pub use inventory::*;
 pub use jobs::*;
 pub use movement::*;
+pub use needs::*;
 pub use partition::*;
 pub use partitioning::*;
+pub use path_cache::*;
+pub use path_queue::*;
 pub use pathfinding::*;
+pub use schedule::*;
 pub use scorer::*;
+pub use skills::*;
 pub use tasks::*;
+pub use work::*;
@@ -9,8 +9,6 @@ use bevy::{
     transform::components::Transform,
 };
 
-use crate::ui::GameSpeed;
-
 #[derive(Component)]
 pub struct BlockMove {
     pub speed: f32,
@@ -21,7 +19,6 @@ pub struct BlockMove {
 pub fn block_move_system(
     mut cmd: Commands,
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut q_movers: Query<(Entity, &BlockMove, &mut Transform)>,
 ) {
     for (entity, block_move, mut transform) in q_movers.iter_mut() {
@@ -33,7 +30,7 @@ pub fn block_move_system(
 
         let direction = (target - transform.translation).normalize();
         let distance = transform.translation.distance(target);
-        let move_dist = time.delta_seconds() * block_move.speed * game_speed.speed();
+        let move_dist = time.delta_seconds() * block_move.speed;
 
         if distance < move_dist {
             transform.translation = target;
@@ -50,7 +47,7 @@ pub fn block_move_system(
 
                 transform.rotation = transform
                     .rotation
-                    .slerp(target_rot, time.delta_seconds() * game_speed.speed() * 20.);
+                    .slerp(target_rot, time.delta_seconds() * 20.);
             }
         }
     }
@@ -0,0 +1,152 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        system::{Query, Res},
+    },
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
+use serde::{Deserialize, Serialize};
+
+use super::{DamageEvent, Fatigue};
+
+pub const NEED_MAX: f32 = 100.;
+
+/// Fatigue has no threshold of its own, colonists rest once it is past this.
+pub const FATIGUE_THRESHOLD: f32 = 70.;
+
+/// How far mood moves toward what the needs call for, per second.
+pub const MOOD_PER_SECOND: f32 = 2.;
+
+/// Damage per second for each of hunger and thirst at its maximum, enough to
+/// outpace healing.
+pub const STARVATION_DAMAGE: f32 = 3.;
+
+/// A need from 0, satisfied, to `NEED_MAX`. It grows by `per_second`, and
+/// colonists look after it once it is past `threshold`.
+#[derive(Clone, Copy, Default, Reflect, InspectorOptions, Serialize, Deserialize)]
+#[reflect(InspectorOptions)]
+pub struct Need {
+    pub value: f32,
+    pub per_second: f32,
+    pub threshold: f32,
+}
+
+impl Need {
+    pub fn new(value: f32, per_second: f32, threshold: f32) -> Self {
+        Self {
+            value,
+            per_second,
+            threshold,
+        }
+    }
+
+    pub fn grow(&mut self, seconds: f32) {
+        self.value = (self.value + self.per_second * seconds).min(NEED_MAX);
+    }
+
+    pub fn satisfy(&mut self, amount: f32) {
+        self.value = (self.value - amount).max(0.);
+    }
+
+    /// 0 up to the threshold, rising to 1 when the need is at its maximum.
+    pub fn urgency(&self) -> f32 {
+        urgency(self.value, self.threshold)
+    }
+
+    pub fn is_maxed(&self) -> bool {
+        self.value >= NEED_MAX
+    }
+}
+
+pub fn urgency(value: f32, threshold: f32) -> f32 {
+    if value < threshold {
+        return 0.;
+    }
+
+    ((value - threshold) / (NEED_MAX - threshold)).min(1.)
+}
+
+#[derive(Component, Clone, Copy, Reflect, InspectorOptions, Serialize, Deserialize)]
+#[reflect(InspectorOptions)]
+pub struct Needs {
+    pub hunger: Need,
+    pub thirst: Need,
+    /// satisfied by resting
+    pub comfort: Need,
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Self {
+            hunger: Need::new(20., 0.4, 60.),
+            thirst: Need::new(20., 0.6, 60.),
+            comfort: Need::new(0., 0.3, 70.),
+        }
+    }
+}
+
+/// From 0, miserable, to 100, content. Drifts toward how well the colonist's
+/// needs are met.
+#[derive(Component, Clone, Copy, Reflect, InspectorOptions)]
+#[reflect(InspectorOptions)]
+pub struct Mood {
+    pub value: f32,
+}
+
+impl Default for Mood {
+    fn default() -> Self {
+        Self { value: 75. }
+    }
+}
+
+pub fn needs_system(time: Res<Time>, mut q_needs: Query<&mut Needs>) {
+    for mut needs in q_needs.iter_mut() {
+        needs.hunger.grow(time.delta_seconds());
+        needs.thirst.grow(time.delta_seconds());
+        needs.comfort.grow(time.delta_seconds());
+    }
+}
+
+/// Colonists left starving or parched waste away until they are downed, and
+/// die if nobody sees to them.
+pub fn starvation_system(
+    time: Res<Time>,
+    q_needs: Query<(Entity, &Needs)>,
+    mut ev_damage: EventWriter<DamageEvent>,
+) {
+    for (entity, needs) in q_needs.iter() {
+        let maxed = [needs.hunger, needs.thirst]
+            .iter()
+            .filter(|n| n.is_maxed())
+            .count();
+
+        if maxed == 0 {
+            continue;
+        }
+
+        ev_damage.send(DamageEvent {
+            entity,
+            amount: maxed as f32 * STARVATION_DAMAGE * time.delta_seconds(),
+        });
+    }
+}
+
+pub fn mood_system(time: Res<Time>, mut q_moods: Query<(&mut Mood, &Needs, &Fatigue)>) {
+    for (mut mood, needs, fatigue) in q_moods.iter_mut() {
+        let urgencies = [
+            needs.hunger.urgency(),
+            needs.thirst.urgency(),
+            needs.comfort.urgency(),
+            urgency(fatigue.value, FATIGUE_THRESHOLD),
+        ];
+        let unmet = urgencies.iter().sum::<f32>() / urgencies.len() as f32;
+        let target = 100. - unmet * 100.;
+        let step = MOOD_PER_SECOND * time.delta_seconds();
+
+        mood.value += (target - mood.value).clamp(-step, step);
+    }
+}
@@ -2,12 +2,25 @@ use crate::Terrain;
 
 use super::NavigationFlags;
 
+/// Cost of walking through a block of plain ground, other costs are relative
+/// to it.
+pub const NAV_COST_DEFAULT: f32 = 1.;
+/// Cheapest block to walk through, keeps path heuristics from overestimating.
+pub const NAV_COST_MIN: f32 = 0.5;
+pub const NAV_COST_DOOR: f32 = 2.;
+/// Ladders and climbing.
+pub const NAV_COST_CLIMB: f32 = 3.;
+/// Wading through shallow fluid.
+pub const NAV_COST_WADE: f32 = 3.;
+/// Added for blocks next to a destructive fluid such as magma.
+pub const NAV_COST_DANGER: f32 = 10.;
+
 pub fn get_block_flags(terrain: &Terrain, x: i32, y: i32, z: i32) -> NavigationFlags {
     let block = terrain.get_block_i32(x, y, z);
 
     let mut flags = NavigationFlags::NONE;
 
-    if !block.is_empty() {
+    if !block.is_empty() && !block.is_wadeable() {
         return NavigationFlags::NONE;
     }
 
@@ -67,3 +80,43 @@ pub fn get_block_flags(terrain: &Terrain, x: i32, y: i32, z: i32) -> NavigationF
 
     flags
 }
+
+/// Cost of walking through the block, from the floor it has and what is
+/// around it.
+pub fn get_block_cost(terrain: &Terrain, x: i32, y: i32, z: i32) -> f32 {
+    let flags = get_block_flags(terrain, x, y, z);
+    let block = terrain.get_block_i32(x, y, z);
+
+    let mut cost = if flags.intersects(NavigationFlags::DOOR) {
+        NAV_COST_DOOR
+    } else if flags.intersects(NavigationFlags::LADDER | NavigationFlags::CLIMB) {
+        NAV_COST_CLIMB
+    } else if block.is_fluid() {
+        NAV_COST_WADE
+    } else {
+        terrain.get_block_i32(x, y - 1, z).walk_cost()
+    };
+
+    let is_dangerous = [
+        [x + 1, y, z],
+        [x - 1, y, z],
+        [x, y + 1, z],
+        [x, y - 1, z],
+        [x, y, z + 1],
+        [x, y, z - 1],
+    ]
+    .iter()
+    .any(|[nx, ny, nz]| {
+        terrain
+            .get_block_i32(*nx, *ny, *nz)
+            .block
+            .fluid()
+            .is_some_and(|f| f.is_destructive)
+    });
+
+    if is_dangerous {
+        cost += NAV_COST_DANGER;
+    }
+
+    cost.max(NAV_COST_MIN)
+}
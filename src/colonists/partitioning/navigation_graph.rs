@@ -1,35 +1,42 @@
-use bevy::{
-    ecs::{entity::Entity, system::Resource},
-    utils::hashbrown::{HashMap, HashSet},
-};
+use bevy::ecs::{entity::Entity, system::Resource};
 
-use crate::{common::flood_fill, Terrain};
+use crate::{
+    common::{flood_fill, StableHashMap, StableHashSet},
+    Terrain,
+};
 
 use super::{NavigationFlags, NavigationGroup, Partition, Region};
 
 #[derive(Resource)]
 pub struct NavigationGraph {
-    partitions: HashMap<u32, Partition>,
-    regions: HashMap<u32, Region>,
-    groups: HashMap<u32, NavigationGroup>,
+    partitions: StableHashMap<u32, Partition>,
+    regions: StableHashMap<u32, Region>,
+    groups: StableHashMap<u32, NavigationGroup>,
 
-    group_types: HashSet<NavigationFlags>,
+    group_types: StableHashSet<NavigationFlags>,
 
     cur_partition_id: u32,
     cur_region_id: u32,
     cur_group_id: u32,
+
+    /// bumped whenever partitions are added or removed
+    generation: u32,
 }
 
 impl Default for NavigationGraph {
     fn default() -> Self {
         Self {
-            partitions: HashMap::new(),
-            regions: HashMap::new(),
-            groups: HashMap::new(),
-            group_types: HashSet::from([NavigationFlags::COLONIST, NavigationFlags::CAT]),
+            partitions: StableHashMap::default(),
+            regions: StableHashMap::default(),
+            groups: StableHashMap::default(),
+            group_types: StableHashSet::from_iter([
+                NavigationFlags::COLONIST,
+                NavigationFlags::CAT,
+            ]),
             cur_partition_id: 0,
             cur_region_id: 0,
             cur_group_id: 0,
+            generation: 0,
         }
     }
 }
@@ -42,6 +49,7 @@ impl NavigationGraph {
         flags: NavigationFlags,
     ) -> u32 {
         self.cur_partition_id += 1;
+        self.generation += 1;
         let partition_id = self.cur_partition_id;
         let partition = Partition::new(partition_id, region_id, chunk_idx, flags);
         self.partitions.insert(partition_id, partition);
@@ -66,8 +74,8 @@ impl NavigationGraph {
         &mut self,
         flags: NavigationFlags,
         region_id: &u32,
-    ) -> HashSet<u32> {
-        let mut group_ids = HashSet::new();
+    ) -> StableHashSet<u32> {
+        let mut group_ids = StableHashSet::default();
 
         for group_type in self.group_types.iter() {
             if !flags.intersects(*group_type) {
@@ -87,10 +95,41 @@ impl NavigationGraph {
         group_ids
     }
 
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// A copy of the partitions and how they connect, without their blocks
+    /// and items, for path searches running off the main thread.
+    pub fn snapshot(&self) -> Self {
+        let partitions = self
+            .partitions
+            .iter()
+            .map(|(id, p)| {
+                let mut partition = Partition::new(p.id, p.region_id, p.chunk_idx, p.flags);
+                partition.is_computed = p.is_computed;
+                partition.neighbor_ids = p.neighbor_ids.clone();
+                partition.extents = p.extents;
+                partition.cost = p.cost;
+                (*id, partition)
+            })
+            .collect();
+
+        Self {
+            partitions,
+            generation: self.generation,
+            ..Default::default()
+        }
+    }
+
     pub fn get_partition(&self, id: &u32) -> Option<&Partition> {
         self.partitions.get(id)
     }
 
+    pub fn partitions(&self) -> impl Iterator<Item = &Partition> {
+        self.partitions.values()
+    }
+
     pub fn get_partition_mut(&mut self, id: &u32) -> Option<&mut Partition> {
         self.partitions.get_mut(id)
     }
@@ -111,23 +150,23 @@ impl NavigationGraph {
         self.groups.get_mut(id)
     }
 
-    pub fn get_group_ids_for_partition(&self, partition_id: &u32) -> HashSet<u32> {
+    pub fn get_group_ids_for_partition(&self, partition_id: &u32) -> StableHashSet<u32> {
         let Some(partition) = self.get_partition(partition_id) else {
-            return HashSet::new();
+            return StableHashSet::default();
         };
 
         let Some(region) = self.get_region(&partition.region_id) else {
-            return HashSet::new();
+            return StableHashSet::default();
         };
 
         region.group_ids.clone()
     }
 
-    pub fn get_groups_for_partition(&self, partition_id: &u32) -> HashSet<&NavigationGroup> {
+    pub fn get_groups_for_partition(&self, partition_id: &u32) -> StableHashSet<&NavigationGroup> {
         self.get_group_ids_for_partition(partition_id)
             .iter()
             .filter_map(|group_id| self.get_group(group_id))
-            .collect::<HashSet<_>>()
+            .collect::<StableHashSet<_>>()
     }
 
     pub fn add_item_to_partition(&mut self, partition_id: &u32, item: Entity) -> bool {
@@ -311,8 +350,8 @@ impl NavigationGraph {
         let mut islands = vec![];
 
         while let Some(seed) = open_list.pop() {
-            let mut island = HashSet::new();
-            let mut neighbors = HashSet::new();
+            let mut island = StableHashSet::default();
+            let mut neighbors = StableHashSet::default();
 
             flood_fill(
                 seed,
@@ -351,7 +390,7 @@ impl NavigationGraph {
         &mut self,
         region_id: &u32,
         flags: NavigationFlags,
-        islands: Vec<(HashSet<u32>, HashSet<u32>)>,
+        islands: Vec<(StableHashSet<u32>, StableHashSet<u32>)>,
     ) {
         // TODO: split up the NAV GROUPs after
 
@@ -408,6 +447,8 @@ impl NavigationGraph {
     }
 
     pub fn delete_partitions_for_chunk(&mut self, chunk_idx: u32) -> Vec<Partition> {
+        self.generation += 1;
+
         let partition_ids = self.get_partition_ids_for_chunk(chunk_idx);
 
         partition_ids
@@ -1,13 +1,13 @@
 use std::hash::Hash;
 
-use bevy::utils::hashbrown::HashSet;
+use crate::common::StableHashSet;
 
 use super::NavigationFlags;
 
 pub struct NavigationGroup {
     pub id: u32,
     pub flags: NavigationFlags,
-    pub region_ids: HashSet<u32>,
+    pub region_ids: StableHashSet<u32>,
 }
 
 impl NavigationGroup {
@@ -15,7 +15,7 @@ impl NavigationGroup {
         Self {
             id,
             flags,
-            region_ids: HashSet::new(),
+            region_ids: StableHashSet::default(),
         }
     }
 }
@@ -1,4 +1,8 @@
-use bevy::{ecs::entity::Entity, utils::hashbrown::HashSet};
+use bevy::ecs::entity::Entity;
+
+use crate::common::StableHashSet;
+
+use crate::colonists::NAV_COST_DEFAULT;
 
 use super::{NavigationFlags, PartitionExtents};
 
@@ -8,10 +12,12 @@ pub struct Partition {
     pub chunk_idx: u32,
     pub flags: NavigationFlags,
     pub is_computed: bool,
-    pub neighbor_ids: HashSet<u32>,
-    pub blocks: HashSet<u32>,
+    pub neighbor_ids: StableHashSet<u32>,
+    pub blocks: StableHashSet<u32>,
     pub extents: PartitionExtents,
-    pub items: HashSet<Entity>,
+    pub items: StableHashSet<Entity>,
+    /// average cost of walking through the partition's blocks
+    pub cost: f32,
 }
 
 impl Partition {
@@ -22,10 +28,11 @@ impl Partition {
             chunk_idx,
             flags,
             is_computed: false,
-            neighbor_ids: HashSet::new(),
-            blocks: HashSet::new(),
+            neighbor_ids: StableHashSet::default(),
+            blocks: StableHashSet::default(),
             extents: PartitionExtents::default(),
-            items: HashSet::new(),
+            items: StableHashSet::default(),
+            cost: NAV_COST_DEFAULT,
         }
     }
 
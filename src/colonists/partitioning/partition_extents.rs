@@ -1,6 +1,6 @@
 use crate::common::{max_3, Distance};
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct PartitionExtents {
     is_init: bool,
     pub min_x: u32,
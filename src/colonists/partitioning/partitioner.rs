@@ -1,14 +1,14 @@
-use bevy::{
-    ecs::{
-        entity::Entity,
-        system::{Query, ResMut},
-    },
-    utils::hashbrown::HashSet,
+use bevy::ecs::{
+    entity::Entity,
+    system::{Query, ResMut},
 };
 use ndshape::AbstractShape;
 
 use crate::{
-    colonists::get_block_flags, common::flood_fill_i32, structures::Structure, Position, Terrain,
+    colonists::{get_block_cost, get_block_flags, PathCache},
+    common::{flood_fill_i32, StableHashMap, StableHashSet},
+    structures::Structure,
+    Position, Terrain,
 };
 
 use super::NavigationGraph;
@@ -16,6 +16,7 @@ use super::NavigationGraph;
 pub fn partition(
     mut graph: ResMut<NavigationGraph>,
     mut terrain: ResMut<Terrain>,
+    mut path_cache: ResMut<PathCache>,
     mut q_items: Query<&mut Position>,
     mut q_structures: Query<&mut Structure>,
 ) {
@@ -26,11 +27,13 @@ pub fn partition(
             continue;
         }
 
-        let mut items: HashSet<Entity> = HashSet::new();
-        let mut structures: HashSet<Entity> = HashSet::new();
+        let mut items: StableHashSet<Entity> = StableHashSet::default();
+        let mut structures: StableHashSet<Entity> = StableHashSet::default();
 
         let cleanups = graph.delete_partitions_for_chunk(chunk_idx);
 
+        path_cache.invalidate(&cleanups.iter().map(|c| c.id).collect());
+
         for cleanup in cleanups {
             for block_cleanup_idx in cleanup.blocks.iter() {
                 terrain.unset_partition_id(chunk_idx, *block_cleanup_idx);
@@ -173,6 +176,27 @@ pub fn partition(
             partition.extents.update_traversal_distance();
         }
 
+        let mut costs: StableHashMap<u32, (f32, u32)> = StableHashMap::default();
+
+        for block_idx in 0..terrain.chunk_shape.size() {
+            let Some(partition_id) = terrain.get_partition_id(chunk_idx, block_idx) else {
+                continue;
+            };
+
+            let [x, y, z] = terrain.get_block_world_pos(chunk_idx, block_idx);
+            let cost = get_block_cost(&terrain, x as i32, y as i32, z as i32);
+            let (sum, count) = costs.entry(partition_id).or_insert((0., 0));
+
+            *sum += cost;
+            *count += 1;
+        }
+
+        for (partition_id, (sum, count)) in costs {
+            if let Some(partition) = graph.get_partition_mut(&partition_id) {
+                partition.cost = sum / count as f32;
+            }
+        }
+
         for item in items {
             let Ok(mut position) = q_items.get_mut(item) else {
                 println!("Item does not exist anymore. {}", item.index());
@@ -1,13 +1,13 @@
-use bevy::utils::hashbrown::HashSet;
+use crate::common::StableHashSet;
 
 use super::NavigationFlags;
 
 pub struct Region {
     pub id: u32,
     pub flags: NavigationFlags,
-    pub partition_ids: HashSet<u32>,
-    pub neighbor_ids: HashSet<u32>,
-    pub group_ids: HashSet<u32>,
+    pub partition_ids: StableHashSet<u32>,
+    pub neighbor_ids: StableHashSet<u32>,
+    pub group_ids: StableHashSet<u32>,
 }
 
 impl Region {
@@ -15,9 +15,9 @@ impl Region {
         Self {
             id,
             flags,
-            partition_ids: HashSet::new(),
-            neighbor_ids: HashSet::new(),
-            group_ids: HashSet::new(),
+            partition_ids: StableHashSet::default(),
+            neighbor_ids: StableHashSet::default(),
+            group_ids: StableHashSet::default(),
         }
     }
 }
@@ -0,0 +1,49 @@
+use bevy::ecs::system::Resource;
+
+use crate::common::{StableHashMap, StableHashSet};
+
+use super::NavigationFlags;
+
+/// Cached paths are dropped all at once past this many.
+pub const PATH_CACHE_MAX: usize = 4096;
+
+/// Partition paths found before, keyed by their start partition, goal
+/// partition and the flags they were found for. Paths are stored goal first,
+/// like `Path::partition_path`.
+#[derive(Resource, Default)]
+pub struct PathCache {
+    paths: StableHashMap<(u32, u32, NavigationFlags), Vec<u32>>,
+}
+
+impl PathCache {
+    pub fn get(&self, start: u32, goal: u32, flags: NavigationFlags) -> Option<&Vec<u32>> {
+        self.paths.get(&(start, goal, flags))
+    }
+
+    pub fn insert(&mut self, path: &[u32], flags: NavigationFlags) {
+        // a path within a single partition is not worth keeping
+        let (Some(goal), Some(start)) = (path.first(), path.last()) else {
+            return;
+        };
+
+        if goal == start {
+            return;
+        }
+
+        if self.paths.len() >= PATH_CACHE_MAX {
+            self.paths.clear();
+        }
+
+        self.paths.insert((*start, *goal, flags), path.to_vec());
+    }
+
+    /// Forgets every path through any of the partitions.
+    pub fn invalidate(&mut self, partition_ids: &StableHashSet<u32>) {
+        if partition_ids.is_empty() {
+            return;
+        }
+
+        self.paths
+            .retain(|_, path| !path.iter().any(|id| partition_ids.contains(id)));
+    }
+}
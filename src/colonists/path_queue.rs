@@ -0,0 +1,289 @@
+use std::{collections::VecDeque, sync::Arc, task::Poll};
+
+use bevy::{
+    ecs::{
+        entity::{Entities, Entity},
+        system::{Res, ResMut, Resource},
+    },
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+};
+
+use crate::{
+    common::{StableHashMap, StableHashSet},
+    Terrain,
+};
+
+use super::{
+    find_partition_path, get_granular_path, start_partition_path, GranularPath,
+    GranularPathRequest, NavigationGraph, PartitionPath, PartitionPathRequest, PartitionPathStart,
+    PathCache,
+};
+
+/// Most path searches started per tick, the rest wait their turn.
+pub const PATH_SEARCH_BUDGET: usize = 16;
+
+#[derive(Clone, PartialEq)]
+pub enum PathSearch {
+    Partition(PartitionPathRequest),
+    Granular(GranularPathRequest),
+}
+
+#[derive(Clone)]
+pub enum PathSearchResult {
+    Partition(Option<PartitionPath>),
+    Granular(Option<GranularPath>),
+}
+
+/// A search running on the task pool, and the actors waiting on it.
+struct RunningPathSearch {
+    search: PathSearch,
+    actors: Vec<Entity>,
+    task: Task<PathSearchResult>,
+}
+
+/// Path searches waiting to run, the ones running, and the results of the
+/// last batch. Actors poll with the same search every tick until the result
+/// is in.
+#[derive(Resource, Default)]
+pub struct PathQueue {
+    pending: VecDeque<(Entity, PathSearch)>,
+    running: Vec<RunningPathSearch>,
+    results: StableHashMap<Entity, (PathSearch, PathSearchResult)>,
+    /// the partition graph running searches read, kept until it changes
+    graph: Option<Arc<NavigationGraph>>,
+}
+
+impl PathQueue {
+    pub fn poll_partition(
+        &mut self,
+        actor: Entity,
+        request: PartitionPathRequest,
+    ) -> Poll<Option<PartitionPath>> {
+        match self.poll(actor, PathSearch::Partition(request)) {
+            Poll::Ready(PathSearchResult::Partition(path)) => Poll::Ready(path),
+            _ => Poll::Pending,
+        }
+    }
+
+    pub fn poll_granular(
+        &mut self,
+        actor: Entity,
+        request: GranularPathRequest,
+    ) -> Poll<Option<GranularPath>> {
+        match self.poll(actor, PathSearch::Granular(request)) {
+            Poll::Ready(PathSearchResult::Granular(path)) => Poll::Ready(path),
+            _ => Poll::Pending,
+        }
+    }
+
+    /// Takes the result of the search if it is done, otherwise queues it up.
+    /// An actor only has one search at a time, asking for a different one
+    /// replaces it.
+    fn poll(&mut self, actor: Entity, search: PathSearch) -> Poll<PathSearchResult> {
+        if let Some((done, result)) = self.results.remove(&actor) {
+            if done == search {
+                return Poll::Ready(result);
+            }
+        }
+
+        if let Some(running) = self.running.iter_mut().find(|r| r.actors.contains(&actor)) {
+            if running.search == search {
+                return Poll::Pending;
+            }
+
+            running.actors.retain(|a| *a != actor);
+        }
+
+        if let Some(idx) = self.pending.iter().position(|(a, _)| *a == actor) {
+            if self.pending[idx].1 == search {
+                return Poll::Pending;
+            }
+
+            self.pending.remove(idx);
+        }
+
+        if let Some(running) = self.running.iter_mut().find(|r| r.search == search) {
+            running.actors.push(actor);
+            return Poll::Pending;
+        }
+
+        self.pending.push_back((actor, search));
+
+        Poll::Pending
+    }
+}
+
+/// Starts the next batch of queued searches on the task pool, where they run
+/// until the next tick collects them. Actors asking for the same path share
+/// one search.
+pub fn path_search_system(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    path_cache: Res<PathCache>,
+    mut queue: ResMut<PathQueue>,
+) {
+    let mut batch: Vec<(PathSearch, Vec<Entity>)> = vec![];
+
+    while batch.len() < PATH_SEARCH_BUDGET {
+        let Some((actor, search)) = queue.pending.pop_front() else {
+            break;
+        };
+
+        match batch.iter_mut().find(|(s, _)| *s == search) {
+            Some((_, actors)) => actors.push(actor),
+            None => batch.push((search, vec![actor])),
+        }
+    }
+
+    if batch.is_empty() {
+        return;
+    }
+
+    // tasks outlive the tick, so they read copies rather than the resources
+    if queue
+        .graph
+        .as_ref()
+        .is_none_or(|g| g.generation() != graph.generation())
+    {
+        queue.graph = Some(Arc::new(graph.snapshot()));
+    }
+
+    let snapshot = queue.graph.clone().unwrap();
+    let pool = AsyncComputeTaskPool::get();
+
+    for (search, actors) in batch {
+        let task = match &search {
+            PathSearch::Partition(request) => {
+                match start_partition_path(request, &terrain, &path_cache) {
+                    PartitionPathStart::Done(path) => {
+                        pool.spawn(async move { PathSearchResult::Partition(path) })
+                    }
+                    PartitionPathStart::Search {
+                        start_partition_id,
+                        goal_partition_ids,
+                        goal_points,
+                    } => {
+                        let graph = snapshot.clone();
+                        let request = request.clone();
+
+                        pool.spawn(async move {
+                            let path = find_partition_path(
+                                start_partition_id,
+                                &goal_partition_ids,
+                                &goal_points,
+                                request.flags,
+                                &graph,
+                            )
+                            .map(|path| PartitionPath {
+                                path,
+                                goals: request.goals,
+                            });
+
+                            PathSearchResult::Partition(path)
+                        })
+                    }
+                }
+            }
+            PathSearch::Granular(request) => {
+                let graph = snapshot.clone();
+                let terrain = granular_terrain(&terrain, &graph, request);
+                let request = request.clone();
+
+                pool.spawn(async move {
+                    PathSearchResult::Granular(get_granular_path(&graph, &terrain, &request))
+                })
+            }
+        };
+
+        queue.running.push(RunningPathSearch {
+            search,
+            actors,
+            task,
+        });
+    }
+}
+
+/// Collects the searches started on the last tick, before actors poll for
+/// them. The tick only waits on searches that are still running, so results
+/// always arrive on the same tick and replays stay deterministic. Searches
+/// nobody waits on any more are dropped.
+pub fn path_results_system(
+    entities: &Entities,
+    mut path_cache: ResMut<PathCache>,
+    mut queue: ResMut<PathQueue>,
+) {
+    // results left from the last batch belong to actors that moved on
+    queue.results.clear();
+
+    for mut running in std::mem::take(&mut queue.running) {
+        running.actors.retain(|a| entities.contains(*a));
+
+        // dropping the task cancels it
+        if running.actors.is_empty() {
+            continue;
+        }
+
+        let result = block_on(running.task);
+
+        if let (PathSearch::Partition(request), PathSearchResult::Partition(Some(path))) =
+            (&running.search, &result)
+        {
+            path_cache.insert(&path.path, request.flags);
+        }
+
+        for actor in running.actors {
+            queue
+                .results
+                .insert(actor, (running.search.clone(), result.clone()));
+        }
+    }
+}
+
+/// The chunks a granular search reads, around the partition the actor is in
+/// and the one it heads to, one block further for their neighbors.
+fn granular_terrain(
+    terrain: &Terrain,
+    graph: &NavigationGraph,
+    request: &GranularPathRequest,
+) -> Terrain {
+    let [x, y, z] = request.start;
+    let partition_ids = [
+        terrain.get_partition_id_u32(x, y, z),
+        Some(request.goal_partition_id),
+    ];
+
+    let chunk_size = terrain.chunk_size;
+    let world_max = [
+        terrain.world_size_x() - 1,
+        terrain.world_size_y() - 1,
+        terrain.world_size_z() - 1,
+    ];
+
+    let mut chunk_idxs = StableHashSet::default();
+
+    for partition in partition_ids
+        .iter()
+        .flatten()
+        .filter_map(|id| graph.get_partition(id))
+    {
+        let e = &partition.extents;
+        let min = [e.min_x, e.min_y, e.min_z].map(|v| v.saturating_sub(1) / chunk_size);
+        let max = [e.max_x, e.max_y, e.max_z];
+        let max = [0, 1, 2].map(|i| (max[i] + 1).min(world_max[i]) / chunk_size);
+
+        for cx in min[0]..=max[0] {
+            for cy in min[1]..=max[1] {
+                for cz in min[2]..=max[2] {
+                    let [chunk_idx, _] = terrain.get_block_indexes(
+                        cx * chunk_size,
+                        cy * chunk_size,
+                        cz * chunk_size,
+                    );
+                    chunk_idxs.insert(chunk_idx);
+                }
+            }
+        }
+    }
+
+    terrain.snapshot(chunk_idxs)
+}
@@ -8,7 +8,9 @@ use crate::{
     Terrain,
 };
 
-use super::{get_block_flags, NavigationFlags, NavigationGraph};
+use super::{
+    get_block_cost, get_block_flags, NavigationFlags, NavigationGraph, PathCache, NAV_COST_MIN,
+};
 
 #[derive(Reflect, Component, Default, InspectorOptions)]
 #[reflect(InspectorOptions)]
@@ -22,7 +24,7 @@ pub struct Path {
     pub current_block_idx: usize,
 }
 
-#[derive(Clone, Component, Debug, Reflect, InspectorOptions)]
+#[derive(Clone, Component, Debug, PartialEq, Reflect, InspectorOptions)]
 #[reflect(InspectorOptions)]
 pub struct PartitionPathRequest {
     pub start: [u32; 3],
@@ -31,6 +33,7 @@ pub struct PartitionPathRequest {
     pub flags: NavigationFlags,
 }
 
+#[derive(Clone, PartialEq)]
 pub struct GranularPathRequest {
     pub start: [u32; 3],
     pub goals: Vec<[u32; 3]>,
@@ -39,6 +42,7 @@ pub struct GranularPathRequest {
     pub flags: NavigationFlags,
 }
 
+#[derive(Clone)]
 pub struct GranularPath {
     pub blocks: Vec<[i32; 3]>,
 }
@@ -87,9 +91,12 @@ pub fn get_granular_path(
                 partition_id == request.goal_partition_id
             }
         },
-        cost: |a, b| Distance::diagonal([a[0], a[1], a[2]], [b[0], b[1], b[2]]),
+        cost: |a, b| {
+            Distance::diagonal([a[0], a[1], a[2]], [b[0], b[1], b[2]])
+                * get_block_cost(terrain, b[0], b[1], b[2])
+        },
         heuristic: |v| {
-            if is_last_partition {
+            let distance = if is_last_partition {
                 goal_positions
                     .iter()
                     .map(|g| OrderedFloat(Distance::diagonal(v, *g)))
@@ -98,7 +105,9 @@ pub fn get_granular_path(
                     .0
             } else {
                 goal_partition.extents.distance_to_edge(v[0], v[1], v[2])
-            }
+            };
+
+            distance * NAV_COST_MIN
         },
         neighbors: |v| {
             // TODO: extract neighbors to block graph
@@ -185,6 +194,7 @@ impl Path {
     }
 }
 
+#[derive(Clone)]
 pub struct PartitionPath {
     pub path: Vec<u32>,
     pub goals: Vec<[u32; 3]>,
@@ -226,11 +236,22 @@ pub fn is_reachable(
         })
 }
 
-pub fn get_partition_path(
+/// How far a partition search gets without the partition graph: done if it
+/// has no goals, starts at one, or was found before.
+pub enum PartitionPathStart {
+    Done(Option<PartitionPath>),
+    Search {
+        start_partition_id: u32,
+        goal_partition_ids: Vec<u32>,
+        goal_points: Vec<[u32; 3]>,
+    },
+}
+
+pub fn start_partition_path(
     request: &PartitionPathRequest,
     terrain: &Terrain,
-    graph: &NavigationGraph,
-) -> Option<PartitionPath> {
+    cache: &PathCache,
+) -> PartitionPathStart {
     let [start_chunk_idx, start_block_idx] =
         terrain.get_block_indexes(request.start[0], request.start[1], request.start[2]);
 
@@ -251,21 +272,77 @@ pub fn get_partition_path(
     goal_partition_ids.sort();
     goal_partition_ids.dedup();
 
-    let starting_partition_id = terrain.get_partition_id(start_chunk_idx, start_block_idx)?;
+    let Some(starting_partition_id) = terrain.get_partition_id(start_chunk_idx, start_block_idx)
+    else {
+        return PartitionPathStart::Done(None);
+    };
 
     if goals.is_empty() {
-        return None;
+        return PartitionPathStart::Done(None);
     }
 
     if goal_partition_ids.contains(&starting_partition_id) {
-        return Some(PartitionPath {
+        return PartitionPathStart::Done(Some(PartitionPath {
             path: vec![starting_partition_id],
             goals: request.goals.clone(),
-        });
+        }));
+    }
+
+    let cached = goal_partition_ids
+        .iter()
+        .filter_map(|goal_id| cache.get(starting_partition_id, *goal_id, request.flags))
+        .min_by_key(|path| path.len());
+
+    if let Some(path) = cached {
+        return PartitionPathStart::Done(Some(PartitionPath {
+            path: path.clone(),
+            goals: request.goals.clone(),
+        }));
+    }
+
+    PartitionPathStart::Search {
+        start_partition_id: starting_partition_id,
+        goal_partition_ids,
+        goal_points: goals.iter().map(|(g, _)| *g).collect(),
+    }
+}
+
+pub fn get_partition_path(
+    request: &PartitionPathRequest,
+    terrain: &Terrain,
+    graph: &NavigationGraph,
+    cache: &PathCache,
+) -> Option<PartitionPath> {
+    match start_partition_path(request, terrain, cache) {
+        PartitionPathStart::Done(path) => path,
+        PartitionPathStart::Search {
+            start_partition_id,
+            goal_partition_ids,
+            goal_points,
+        } => find_partition_path(
+            start_partition_id,
+            &goal_partition_ids,
+            &goal_points,
+            request.flags,
+            graph,
+        )
+        .map(|path| PartitionPath {
+            path,
+            goals: request.goals.clone(),
+        }),
     }
+}
 
+/// A* over the partition graph, returning the partitions goal first.
+pub fn find_partition_path(
+    start_partition_id: u32,
+    goal_partition_ids: &[u32],
+    goal_points: &[[u32; 3]],
+    flags: NavigationFlags,
+    graph: &NavigationGraph,
+) -> Option<Vec<u32>> {
     let partition_path: crate::common::AStarResult<u32> = astar(AStarSettings {
-        start: starting_partition_id,
+        start: start_partition_id,
         is_goal: |p| goal_partition_ids.contains(&p),
         max_depth: 6000,
         neighbors: |v| {
@@ -279,7 +356,7 @@ pub fn get_partition_path(
                     let Some(n_p) = graph.get_partition(n) else {
                         return false;
                     };
-                    n_p.flags & request.flags != NavigationFlags::NONE
+                    n_p.flags & flags != NavigationFlags::NONE
                 })
                 .copied()
                 .collect()
@@ -287,9 +364,9 @@ pub fn get_partition_path(
         heuristic: |a| {
             let [ax, ay, az] = graph.get_partition(&a).unwrap().extents.center();
 
-            goals
+            goal_points
                 .iter()
-                .map(|(g, _pid)| {
+                .map(|g| {
                     OrderedFloat(Distance::diagonal(
                         [ax as i32, ay as i32, az as i32],
                         [g[0] as i32, g[1] as i32, g[2] as i32],
@@ -298,15 +375,20 @@ pub fn get_partition_path(
                 .min()
                 .unwrap()
                 .0
+                * NAV_COST_MIN
         },
         cost: |a, b| {
-            let [ax, ay, az] = graph.get_partition(&a).unwrap().extents.center();
-            let [bx, by, bz] = graph.get_partition(&b).unwrap().extents.center();
+            let a = graph.get_partition(&a).unwrap();
+            let b = graph.get_partition(&b).unwrap();
+            let [ax, ay, az] = a.extents.center();
+            let [bx, by, bz] = b.extents.center();
 
+            // half the way is through each partition
             Distance::diagonal(
                 [ax as i32, ay as i32, az as i32],
                 [bx as i32, by as i32, bz as i32],
-            )
+            ) * (a.cost + b.cost)
+                / 2.
         },
     });
 
@@ -314,8 +396,78 @@ pub fn get_partition_path(
         return None;
     }
 
-    Some(PartitionPath {
-        path: partition_path.path,
-        goals: request.goals.clone(),
-    })
+    Some(partition_path.path)
+}
+
+/// Re-plans the broken part of an in-flight path from `start`, keeping the
+/// stretch towards the goals that is still intact. Returns false if the goals
+/// can no longer be reached.
+pub fn repair_path(
+    path: &mut Path,
+    start: [u32; 3],
+    terrain: &Terrain,
+    graph: &NavigationGraph,
+    cache: &mut PathCache,
+) -> bool {
+    let Some(start_partition_id) = terrain.get_partition_id_u32(start[0], start[1], start[2])
+    else {
+        return false;
+    };
+
+    let remaining = (path.current_partition_idx + 1).min(path.partition_path.len());
+
+    // partitions are stored goal first, so the intact stretch is a prefix
+    let intact = path.partition_path[..remaining]
+        .iter()
+        .take_while(|id| graph.get_partition(id).is_some())
+        .count();
+
+    let repaired = if let Some(idx) = path.partition_path[..intact]
+        .iter()
+        .position(|id| *id == start_partition_id)
+    {
+        Some(path.partition_path[..=idx].to_vec())
+    } else if intact > 0 {
+        let rejoin_id = path.partition_path[intact - 1];
+        let rejoin = graph.get_partition(&rejoin_id).unwrap().extents.center();
+
+        find_partition_path(
+            start_partition_id,
+            &[rejoin_id],
+            &[rejoin],
+            path.flags,
+            graph,
+        )
+        .map(|detour| {
+            let mut partitions = path.partition_path[..intact - 1].to_vec();
+            partitions.extend(detour);
+            partitions
+        })
+    } else {
+        None
+    };
+
+    // the intact stretch may be cut off entirely, so try from scratch
+    let repaired = repaired.or_else(|| {
+        let request = PartitionPathRequest {
+            start,
+            goals: path.goals.clone(),
+            flags: path.flags,
+        };
+
+        let partitions = get_partition_path(&request, terrain, graph, cache)?.path;
+        cache.insert(&partitions, request.flags);
+        Some(partitions)
+    });
+
+    let Some(partition_path) = repaired else {
+        return false;
+    };
+
+    path.current_partition_idx = partition_path.len() - 1;
+    path.partition_path = partition_path;
+    path.blocks.clear();
+    path.current_block_idx = 0;
+
+    true
 }
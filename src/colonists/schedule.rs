@@ -0,0 +1,45 @@
+use bevy::ecs::component::Component;
+use serde::{Deserialize, Serialize};
+
+use crate::HOURS_PER_DAY;
+
+/// What a colonist spends an hour of the day on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Activity {
+    Work,
+    Sleep,
+    Recreation,
+}
+
+impl Activity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Activity::Work => "Work",
+            Activity::Sleep => "Sleep",
+            Activity::Recreation => "Recreation",
+        }
+    }
+}
+
+/// The activity for each hour of the day.
+#[derive(Component, Clone, Serialize, Deserialize)]
+pub struct DailySchedule {
+    pub hours: [Activity; HOURS_PER_DAY as usize],
+}
+
+impl Default for DailySchedule {
+    fn default() -> Self {
+        let mut hours = [Activity::Sleep; HOURS_PER_DAY as usize];
+
+        hours[6..18].fill(Activity::Work);
+        hours[18..22].fill(Activity::Recreation);
+
+        Self { hours }
+    }
+}
+
+impl DailySchedule {
+    pub fn at(&self, hour: u32) -> Activity {
+        self.hours[(hour % HOURS_PER_DAY) as usize]
+    }
+}
@@ -1,12 +1,13 @@
 use std::sync::Arc;
 
 use bevy::{
-    app::{Plugin, PreUpdate},
+    app::{FixedUpdate, Plugin},
     core::Name,
     ecs::{
         component::Component,
         entity::Entity,
         query::Without,
+        schedule::IntoSystemConfigs,
         system::{Commands, EntityCommands, Query},
     },
     prelude::App,
@@ -14,11 +15,16 @@ use bevy::{
 };
 use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
 
-use crate::colonists::{
-    ScorerBuild, ScorerChop, ScorerMine, ScorerPlaceBlock, ScorerSupply, ScorerWander,
+use crate::{
+    colonists::{
+        ScorerBuild, ScorerChop, ScorerCraft, ScorerDrink, ScorerEat, ScorerFight, ScorerFlee,
+        ScorerFollowOwner, ScorerHaul, ScorerHunt, ScorerMine, ScorerPlaceBlock, ScorerSleep,
+        ScorerSupply, ScorerWander,
+    },
+    simulation::SimSet,
 };
 
-use super::{ActorRef, Behavior};
+use super::{behavior_system, Activity, ActorRef, Behavior};
 
 #[derive(Clone, Component, Debug, Reflect, InspectorOptions)]
 #[reflect(InspectorOptions)]
@@ -40,6 +46,11 @@ pub trait ScorerBuilder: Send + Sync {
     fn insert(&self, cmd: &mut EntityCommands);
     fn label(&self) -> String;
     fn build(&self) -> Behavior;
+
+    /// The part of the day the behavior is kept to, if any.
+    fn activity(&self) -> Option<Activity> {
+        None
+    }
 }
 
 pub struct ScorerPlugin;
@@ -54,7 +65,21 @@ impl Plugin for ScorerPlugin {
             .register_component_as::<dyn ScorerBuilder, ScorerChop>()
             .register_component_as::<dyn ScorerBuilder, ScorerBuild>()
             .register_component_as::<dyn ScorerBuilder, ScorerSupply>()
-            .add_systems(PreUpdate, spawn_scorers);
+            .register_component_as::<dyn ScorerBuilder, ScorerHaul>()
+            .register_component_as::<dyn ScorerBuilder, ScorerCraft>()
+            .register_component_as::<dyn ScorerBuilder, ScorerEat>()
+            .register_component_as::<dyn ScorerBuilder, ScorerDrink>()
+            .register_component_as::<dyn ScorerBuilder, ScorerSleep>()
+            .register_component_as::<dyn ScorerBuilder, ScorerFollowOwner>()
+            .register_component_as::<dyn ScorerBuilder, ScorerFlee>()
+            .register_component_as::<dyn ScorerBuilder, ScorerFight>()
+            .register_component_as::<dyn ScorerBuilder, ScorerHunt>()
+            .add_systems(
+                FixedUpdate,
+                spawn_scorers
+                    .in_set(SimSet::Behavior)
+                    .before(behavior_system),
+            );
     }
 }
 
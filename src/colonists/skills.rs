@@ -0,0 +1,100 @@
+use bevy::{ecs::component::Component, reflect::Reflect};
+use bevy_inspector_egui::{inspector_options::ReflectInspectorOptions, InspectorOptions};
+use serde::{Deserialize, Serialize};
+
+pub const SKILL_LEVEL_MAX: u32 = 10;
+
+/// Experience for the first level, each level after needs more.
+pub const SKILL_XP_PER_LEVEL: f32 = 20.;
+
+/// Experience gained each time a task using the skill completes.
+pub const SKILL_XP_PER_TASK: f32 = 10.;
+
+/// Work speed gained per level, a level 10 colonist works twice as fast.
+const SKILL_SPEED_PER_LEVEL: f32 = 0.1;
+
+/// Drop chance gained per level, relative to the base chance.
+const SKILL_DROP_PER_LEVEL: f32 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum Skill {
+    Mining,
+    Woodcutting,
+    Construction,
+    Hauling,
+    Crafting,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 5] = [
+        Skill::Mining,
+        Skill::Woodcutting,
+        Skill::Construction,
+        Skill::Hauling,
+        Skill::Crafting,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Skill::Mining => "Mining",
+            Skill::Woodcutting => "Woodcutting",
+            Skill::Construction => "Construction",
+            Skill::Hauling => "Hauling",
+            Skill::Crafting => "Crafting",
+        }
+    }
+}
+
+/// Experience in each skill. Levels follow from experience, level `n` needs
+/// `SKILL_XP_PER_LEVEL * n * n`.
+#[derive(Component, Clone, Copy, Default, Reflect, InspectorOptions, Serialize, Deserialize)]
+#[reflect(InspectorOptions)]
+pub struct Skills {
+    pub mining: f32,
+    pub woodcutting: f32,
+    pub construction: f32,
+    pub hauling: f32,
+    pub crafting: f32,
+}
+
+impl Skills {
+    pub fn xp(&self, skill: Skill) -> f32 {
+        match skill {
+            Skill::Mining => self.mining,
+            Skill::Woodcutting => self.woodcutting,
+            Skill::Construction => self.construction,
+            Skill::Hauling => self.hauling,
+            Skill::Crafting => self.crafting,
+        }
+    }
+
+    pub fn gain(&mut self, skill: Skill, xp: f32) {
+        let value = match skill {
+            Skill::Mining => &mut self.mining,
+            Skill::Woodcutting => &mut self.woodcutting,
+            Skill::Construction => &mut self.construction,
+            Skill::Hauling => &mut self.hauling,
+            Skill::Crafting => &mut self.crafting,
+        };
+
+        *value += xp;
+    }
+
+    pub fn level(&self, skill: Skill) -> u32 {
+        let level = (self.xp(skill) / SKILL_XP_PER_LEVEL).sqrt() as u32;
+
+        level.min(SKILL_LEVEL_MAX)
+    }
+
+    /// Multiplier for task progress, 1 for an unskilled colonist.
+    pub fn work_speed(&self, skill: Skill) -> f32 {
+        1. + self.level(skill) as f32 * SKILL_SPEED_PER_LEVEL
+    }
+
+    /// `chance` of a drop, raised by the colonist's level.
+    pub fn drop_chance(&self, skill: Skill, chance: f32) -> f32 {
+        let bonus = 1. + self.level(skill) as f32 * SKILL_DROP_PER_LEVEL;
+
+        (chance * bonus).min(1.)
+    }
+}
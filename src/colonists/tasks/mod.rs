@@ -1,10 +1,17 @@
 mod task_animate;
 mod task_build;
+mod task_check_has_ingredient;
 mod task_check_has_item;
 mod task_chop_tree;
+mod task_craft;
 mod task_debug;
+mod task_drink;
+mod task_eat;
 mod task_find_bed;
+mod task_find_ingredient;
 mod task_find_nearest_item;
+mod task_find_water;
+mod task_get_haul_item;
 mod task_get_job_location;
 mod task_idle;
 mod task_is_target_empty;
@@ -15,20 +22,33 @@ mod task_job_cancel;
 mod task_job_complete;
 mod task_job_unassign;
 mod task_look_at;
+mod task_melee_attack;
 mod task_mine_block;
 mod task_move_to;
+mod task_pick_flee_spot;
+mod task_pick_follow_spot;
 mod task_pick_random_spot;
+mod task_pick_target;
 mod task_place_block;
+mod task_release_bed;
 mod task_sleep;
+mod task_store_item;
 mod task_supply;
 
 pub use task_animate::*;
 pub use task_build::*;
+pub use task_check_has_ingredient::*;
 pub use task_check_has_item::*;
 pub use task_chop_tree::*;
+pub use task_craft::*;
 pub use task_debug::*;
+pub use task_drink::*;
+pub use task_eat::*;
 pub use task_find_bed::*;
+pub use task_find_ingredient::*;
 pub use task_find_nearest_item::*;
+pub use task_find_water::*;
+pub use task_get_haul_item::*;
 pub use task_get_job_location::*;
 pub use task_idle::*;
 pub use task_is_target_empty::*;
@@ -39,9 +59,15 @@ pub use task_job_cancel::*;
 pub use task_job_complete::*;
 pub use task_job_unassign::*;
 pub use task_look_at::*;
+pub use task_melee_attack::*;
 pub use task_mine_block::*;
 pub use task_move_to::*;
+pub use task_pick_flee_spot::*;
+pub use task_pick_follow_spot::*;
 pub use task_pick_random_spot::*;
+pub use task_pick_target::*;
 pub use task_place_block::*;
+pub use task_release_bed::*;
 pub use task_sleep::*;
+pub use task_store_item::*;
 pub use task_supply::*;
@@ -12,8 +12,8 @@ pub fn task_animate(
 ) {
     for (ActorRef(actor), mut state, task) in q_behavior.iter_mut() {
         let Ok(mut animator) = q_animators.get_mut(*actor) else {
-            println!("Cannot play animation, actor does not have Animator");
-            *state = TaskState::Failed;
+            // actors without a rig (e.g. when running headless) skip animations
+            *state = TaskState::Success;
             continue;
         };
 
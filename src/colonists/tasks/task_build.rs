@@ -11,9 +11,11 @@ use bevy::{
 use task_derive::TaskBuilder;
 
 use crate::{
-    colonists::{Actor, ActorRef, AnimClip, Animator, TaskBuilder, TaskState},
+    colonists::{
+        Actor, ActorRef, AnimClip, Animator, Skill, Skills, TaskBuilder, TaskState,
+        SKILL_XP_PER_TASK,
+    },
     structures::BuildStructureEvent,
-    ui::GameSpeed,
 };
 
 #[derive(Component, Clone, TaskBuilder)]
@@ -24,8 +26,8 @@ pub struct TaskBuild {
 
 pub fn task_build(
     mut q_animators: Query<&mut Animator, With<Actor>>,
+    mut q_skills: Query<&mut Skills, With<Actor>>,
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut q_behavior: Query<(&ActorRef, &mut TaskState, &mut TaskBuild)>,
     mut ev_build_structure: EventWriter<BuildStructureEvent>,
 ) {
@@ -34,6 +36,11 @@ pub fn task_build(
             ev_build_structure.send(BuildStructureEvent {
                 entity: task.structure,
             });
+
+            if let Ok(mut skills) = q_skills.get_mut(*actor) {
+                skills.gain(Skill::Construction, SKILL_XP_PER_TASK);
+            }
+
             *state = TaskState::Success;
             continue;
         }
@@ -42,6 +49,8 @@ pub fn task_build(
             animator.clip = AnimClip::SwingHammer;
         };
 
-        task.progress += time.delta_seconds() * game_speed.speed();
+        let skills = q_skills.get(*actor).copied().unwrap_or_default();
+
+        task.progress += time.delta_seconds() * skills.work_speed(Skill::Construction);
     }
 }
@@ -0,0 +1,42 @@
+use bevy::ecs::{component::Component, system::Query};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{ActorRef, Blackboard, Inventory, Item, TaskBuilder, TaskState},
+    items::{Commodity, Ingredient},
+};
+
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskCheckHasIngredient(pub Ingredient);
+
+pub fn task_check_has_ingredient(
+    q_items: Query<(&Item, Option<&Commodity>)>,
+    q_inventories: Query<&Inventory>,
+    mut q_behavior: Query<(
+        &ActorRef,
+        &mut TaskState,
+        &mut Blackboard,
+        &TaskCheckHasIngredient,
+    )>,
+) {
+    for (ActorRef(actor), mut state, mut blackboard, task) in q_behavior.iter_mut() {
+        let Ok(inventory) = q_inventories.get(*actor) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let held = inventory.items.iter().copied().find(|e| {
+            q_items
+                .get(*e)
+                .is_ok_and(|(item, commodity)| task.0.matches(item, commodity))
+        });
+
+        *state = match held {
+            Some(item) => {
+                blackboard.item = Some(item);
+                TaskState::Success
+            }
+            None => TaskState::Failed,
+        }
+    }
+}
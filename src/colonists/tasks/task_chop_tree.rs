@@ -12,10 +12,14 @@ use bevy::{
 use task_derive::TaskBuilder;
 
 use crate::{
-    colonists::{Actor, ActorRef, AnimClip, Animator, TaskBuilder, TaskState},
+    colonists::{
+        spawn_block_drops, Actor, ActorRef, AnimClip, Animator, DestroyItemEvent, Inventory, Item,
+        ItemTag, Skill, Skills, TaskBuilder, TaskState, SKILL_XP_PER_TASK,
+    },
     common::Rand,
-    items::{Commodity::WoodBirchLog, SpawnCommodityEvent},
-    ui::GameSpeed,
+    items::{
+        tool_material, tool_work_speed, wear_tool, Durability, SpawnCommodityEvent, ToolMaterial,
+    },
     BlockType, Terrain, Tree,
 };
 
@@ -28,13 +32,16 @@ pub struct TaskChopTree {
 pub fn task_chop_tree(
     mut cmd: Commands,
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut terrain: ResMut<Terrain>,
     mut q_animators: Query<&mut Animator, With<Actor>>,
+    mut q_skills: Query<&mut Skills, With<Actor>>,
+    mut q_inventories: Query<&mut Inventory, With<Actor>>,
+    mut q_tools: Query<(&Item, &ToolMaterial, &mut Durability)>,
     mut q_behavior: Query<(&ActorRef, &mut TaskState, &mut TaskChopTree)>,
     q_trees: Query<&Tree>,
     mut rand: ResMut<Rand>,
     mut ev_spawn_commodity: EventWriter<SpawnCommodityEvent>,
+    mut ev_destroy_item: EventWriter<DestroyItemEvent>,
 ) {
     for (ActorRef(actor), mut state, mut task) in q_behavior.iter_mut() {
         let Ok(tree) = q_trees.get(task.tree) else {
@@ -42,7 +49,14 @@ pub fn task_chop_tree(
             continue;
         };
 
+        let skills = q_skills.get(*actor).copied().unwrap_or_default();
+
         if task.progress >= 6. {
+            let tool = q_inventories
+                .get(*actor)
+                .ok()
+                .and_then(|i| tool_material(i, &[ItemTag::Axe], &q_tools));
+
             for part in tree.canopy.iter() {
                 let [chunk_idx, block_idx] = terrain.get_block_indexes(part[0], part[1], part[2]);
                 terrain.remove_tree(chunk_idx, block_idx, &task.tree);
@@ -54,6 +68,16 @@ pub fn task_chop_tree(
                     let block = terrain.get_block_by_idx(chunk_idx, block_idx);
                     if block.block == BlockType::LEAVES {
                         terrain.set_block_type(part[0], part[1], part[2], BlockType::EMPTY);
+
+                        spawn_block_drops(
+                            &block.block.definition().drops,
+                            *part,
+                            tool,
+                            Skill::Woodcutting,
+                            &skills,
+                            &mut rand,
+                            &mut ev_spawn_commodity,
+                        );
                     }
                 }
             }
@@ -69,17 +93,31 @@ pub fn task_chop_tree(
                     let block = terrain.get_block_by_idx(chunk_idx, block_idx);
                     if block.block == BlockType::TREE_TRUNK {
                         terrain.set_block_type(part[0], part[1], part[2], BlockType::EMPTY);
+
+                        spawn_block_drops(
+                            &block.block.definition().drops,
+                            *part,
+                            tool,
+                            Skill::Woodcutting,
+                            &skills,
+                            &mut rand,
+                            &mut ev_spawn_commodity,
+                        );
                     }
                 }
+            }
 
-                if rand.bool(0.8) {
-                    ev_spawn_commodity.send(SpawnCommodityEvent {
-                        commodity: WoodBirchLog,
-                        position: *part,
-                        slot_entity: None,
-                        slot_idx: None,
-                    });
-                }
+            if let Ok(mut skills) = q_skills.get_mut(*actor) {
+                skills.gain(Skill::Woodcutting, SKILL_XP_PER_TASK);
+            }
+
+            if let Ok(mut inventory) = q_inventories.get_mut(*actor) {
+                wear_tool(
+                    &mut inventory,
+                    &[ItemTag::Axe],
+                    &mut q_tools,
+                    &mut ev_destroy_item,
+                );
             }
 
             cmd.entity(task.tree).despawn_recursive();
@@ -91,6 +129,10 @@ pub fn task_chop_tree(
             animator.clip = AnimClip::SwingAxe;
         };
 
-        task.progress += time.delta_seconds() * game_speed.speed();
+        let tool_speed = q_inventories
+            .get(*actor)
+            .map_or(1., |i| tool_work_speed(i, &[ItemTag::Axe], &q_tools));
+
+        task.progress += time.delta_seconds() * skills.work_speed(Skill::Woodcutting) * tool_speed;
     }
 }
@@ -0,0 +1,140 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        query::With,
+        system::{Query, Res},
+    },
+    time::Time,
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        Actor, ActorRef, AnimClip, Animator, DestroyItemEvent, Inventory, Item, Skill, Skills,
+        TaskBuilder, TaskState, SKILL_XP_PER_TASK,
+    },
+    items::{
+        Commodity, CraftBills, RecipeOutput, RecipeType, Recipes, SpawnAxeEvent,
+        SpawnCommodityEvent, SpawnPickaxeEvent,
+    },
+    Position,
+};
+
+/// Works the recipe at the station, then turns the ingredients held by the
+/// actor into the recipe's output at their feet.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskCraft {
+    pub station: Entity,
+    pub recipe: RecipeType,
+    pub progress: f32,
+}
+
+pub fn task_craft(
+    time: Res<Time>,
+    recipes: Res<Recipes>,
+    mut q_animators: Query<&mut Animator, With<Actor>>,
+    mut q_actors: Query<(&mut Inventory, &Position, &mut Skills), With<Actor>>,
+    q_items: Query<(&Item, Option<&Commodity>)>,
+    mut q_stations: Query<&mut CraftBills>,
+    mut q_behavior: Query<(&ActorRef, &mut TaskState, &mut TaskCraft)>,
+    mut ev_destroy_item: EventWriter<DestroyItemEvent>,
+    (mut ev_spawn_commodity, mut ev_spawn_axe, mut ev_spawn_pickaxe): (
+        EventWriter<SpawnCommodityEvent>,
+        EventWriter<SpawnAxeEvent>,
+        EventWriter<SpawnPickaxeEvent>,
+    ),
+) {
+    for (ActorRef(actor), mut state, mut task) in q_behavior.iter_mut() {
+        let Some(recipe) = recipes.0.get(&task.recipe) else {
+            println!("No recipe {:?}, cannot craft!", task.recipe);
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Ok((mut inventory, position, mut skills)) = q_actors.get_mut(*actor) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        if task.progress < recipe.work_time {
+            if let Ok(mut animator) = q_animators.get_mut(*actor) {
+                animator.clip = AnimClip::SwingHammer;
+            };
+
+            task.progress += time.delta_seconds() * skills.work_speed(Skill::Crafting);
+            continue;
+        }
+
+        let mut ingredients = vec![];
+
+        for input in recipe.inputs.iter() {
+            let found = inventory.items.iter().copied().find(|e| {
+                !ingredients.contains(e)
+                    && q_items
+                        .get(*e)
+                        .is_ok_and(|(item, commodity)| input.matches(item, commodity))
+            });
+
+            let Some(found) = found else {
+                break;
+            };
+
+            ingredients.push(found);
+        }
+
+        if ingredients.len() < recipe.inputs.len() {
+            println!(
+                "Missing ingredients for {}, cannot craft!",
+                task.recipe.label()
+            );
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        let Ok(mut bills) = q_stations.get_mut(task.station) else {
+            println!("Station does not exist, cannot craft!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        for ingredient in ingredients {
+            inventory.items.remove(&ingredient);
+            ev_destroy_item.send(DestroyItemEvent { entity: ingredient });
+        }
+
+        let pos = position.as_array();
+
+        match recipe.output {
+            RecipeOutput::Commodity(commodity) => {
+                ev_spawn_commodity.send(SpawnCommodityEvent {
+                    entity: None,
+                    commodity,
+                    position: pos,
+                    slot_entity: None,
+                    slot_idx: None,
+                });
+            }
+            RecipeOutput::Axe(material) => {
+                ev_spawn_axe.send(SpawnAxeEvent {
+                    pos,
+                    material,
+                    durability: None,
+                });
+            }
+            RecipeOutput::Pickaxe(material) => {
+                ev_spawn_pickaxe.send(SpawnPickaxeEvent {
+                    pos,
+                    material,
+                    durability: None,
+                });
+            }
+        }
+
+        bills.complete(task.recipe);
+        skills.gain(Skill::Crafting, SKILL_XP_PER_TASK);
+
+        *state = TaskState::Success;
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        query::With,
+        system::{Query, Res},
+    },
+    time::Time,
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{Actor, ActorRef, AnimClip, Animator, Blackboard, Needs, TaskBuilder, TaskState},
+    BlockType, Terrain,
+};
+
+/// Thirst satisfied per second of drinking.
+pub const DRINK_PER_SECOND: f32 = 40.;
+
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskDrink;
+
+pub fn task_drink(
+    time: Res<Time>,
+    terrain: Res<Terrain>,
+    mut q_actors: Query<(&mut Needs, Option<&mut Animator>), With<Actor>>,
+    mut q_behavior: Query<(&ActorRef, &Blackboard, &mut TaskState), With<TaskDrink>>,
+) {
+    for (ActorRef(actor), blackboard, mut state) in q_behavior.iter_mut() {
+        let Ok((mut needs, animator)) = q_actors.get_mut(*actor) else {
+            println!("Actor has no needs, cannot drink!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some([x, y, z]) = blackboard.target_block else {
+            println!("No water on blackboard, cannot drink!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        if terrain.get_block(x, y, z).block != BlockType::WATER {
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        if let Some(mut animator) = animator {
+            animator.clip = AnimClip::PickUp;
+        }
+
+        needs
+            .thirst
+            .satisfy(DRINK_PER_SECOND * time.delta_seconds());
+
+        if needs.thirst.value <= 0. {
+            *state = TaskState::Success;
+        }
+    }
+}
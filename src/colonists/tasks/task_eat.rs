@@ -0,0 +1,74 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        event::EventWriter,
+        query::With,
+        system::{Query, Res},
+    },
+    time::Time,
+};
+use task_derive::TaskBuilder;
+
+use crate::colonists::{
+    Actor, ActorRef, AnimClip, Animator, Blackboard, DestroyItemEvent, Inventory, Needs,
+    TaskBuilder, TaskState,
+};
+
+/// Hunger satisfied by a single item of food.
+pub const FOOD_NUTRITION: f32 = 60.;
+
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskEat {
+    pub progress: f32,
+    pub duration_s: f32,
+}
+
+impl Default for TaskEat {
+    fn default() -> Self {
+        Self {
+            progress: 0.,
+            duration_s: 2.,
+        }
+    }
+}
+
+pub fn task_eat(
+    time: Res<Time>,
+    mut q_actors: Query<(&mut Needs, &mut Inventory, Option<&mut Animator>), With<Actor>>,
+    mut q_behavior: Query<(&ActorRef, &Blackboard, &mut TaskState, &mut TaskEat)>,
+    mut ev_destroy_item: EventWriter<DestroyItemEvent>,
+) {
+    for (ActorRef(actor), blackboard, mut state, mut task) in q_behavior.iter_mut() {
+        let Ok((mut needs, mut inventory, animator)) = q_actors.get_mut(*actor) else {
+            println!("Actor has no needs, cannot eat!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some(item) = blackboard.item else {
+            println!("No food on blackboard, cannot eat!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        if !inventory.items.contains(&item) {
+            println!("Food is not in inventory, cannot eat!");
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        if task.progress < task.duration_s {
+            if let Some(mut animator) = animator {
+                animator.clip = AnimClip::Idle;
+            }
+
+            task.progress += time.delta_seconds();
+            continue;
+        }
+
+        inventory.items.remove(&item);
+        ev_destroy_item.send(DestroyItemEvent { entity: item });
+        needs.hunger.satisfy(FOOD_NUTRITION);
+        *state = TaskState::Success;
+    }
+}
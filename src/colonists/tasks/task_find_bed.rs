@@ -1,17 +1,108 @@
-use bevy::ecs::{component::Component, query::With, system::Query};
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    system::{Query, Res},
+};
 use task_derive::TaskBuilder;
 
-use crate::colonists::{ActorRef, Blackboard, TaskBuilder, TaskState};
+use crate::{
+    colonists::{
+        is_reachable, Actor, ActorRef, Blackboard, NavigationFlags, NavigationGraph,
+        PartitionPathRequest, TaskBuilder, TaskState,
+    },
+    common::Distance,
+    structures::{Bed, Structure},
+    Position, Terrain,
+};
 
 #[derive(Component, Clone, TaskBuilder)]
 pub struct TaskFindBed;
 
 pub fn task_find_bed(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<(&Position, &NavigationFlags), With<Actor>>,
+    mut q_beds: Query<(Entity, &mut Bed, &Structure)>,
     mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskFindBed>>,
 ) {
-    for (ActorRef(entity), mut blackboard, mut state) in q_behavior.iter_mut() {
-        println!("find a bed for {}", entity.index());
-        blackboard.bed = 3;
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        let Ok((position, flags)) = q_actors.get(*actor) else {
+            println!("Actor has no position, cannot find bed!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some((bed_entity, spot)) = find_bed(
+            *actor,
+            position.as_array(),
+            *flags,
+            q_beds.iter(),
+            &terrain,
+            &graph,
+        ) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Ok((_, mut bed, _)) = q_beds.get_mut(bed_entity) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        bed.owner = Some(*actor);
+        bed.reserved = Some(*actor);
+        blackboard.bed = Some(bed_entity);
+        blackboard.move_goals = vec![spot];
+        blackboard.primary_goal = Some(spot);
         *state = TaskState::Success;
     }
 }
+
+/// Where a colonist lies down in a bed.
+pub fn bed_spot(structure: &Structure) -> Option<[u32; 3]> {
+    let [x, y, z] = structure.tiles.first()?.position;
+
+    Some([x as u32, y as u32, z as u32])
+}
+
+/// The bed `actor` owns, or else the nearest free bed they can reach, and
+/// where to lie down in it.
+pub fn find_bed<'a>(
+    actor: Entity,
+    pos: [u32; 3],
+    flags: NavigationFlags,
+    beds: impl Iterator<Item = (Entity, &'a Bed, &'a Structure)>,
+    terrain: &Terrain,
+    graph: &NavigationGraph,
+) -> Option<(Entity, [u32; 3])> {
+    let mut candidates = beds
+        .filter(|(_, bed, structure)| structure.is_built() && bed.is_available(actor))
+        .filter_map(|(e, bed, structure)| {
+            let spot = bed_spot(structure)?;
+            let distance = Distance::manhattan(
+                [spot[0] as i32, spot[1] as i32, spot[2] as i32],
+                [pos[0] as i32, pos[1] as i32, pos[2] as i32],
+            );
+
+            Some((bed.owner.is_none(), distance, e, spot))
+        })
+        .collect::<Vec<_>>();
+
+    // owned beds first, then the nearest
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+
+    candidates.into_iter().find_map(|(_, _, e, spot)| {
+        let request = PartitionPathRequest {
+            start: pos,
+            goals: vec![spot],
+            flags,
+        };
+
+        if is_reachable(&request, terrain, graph) {
+            Some((e, spot))
+        } else {
+            None
+        }
+    })
+}
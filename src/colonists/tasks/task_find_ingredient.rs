@@ -0,0 +1,73 @@
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    system::{Query, Res},
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        find_nearest_item, Actor, ActorRef, Blackboard, InInventory, InSlot, Item, NavigationGraph,
+        TaskBuilder, TaskState,
+    },
+    items::{Commodity, Ingredient},
+    Position,
+};
+
+/// Reserves the nearest item for a recipe ingredient and moves to it.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskFindIngredient(pub Ingredient);
+
+pub fn task_find_ingredient(
+    graph: Res<NavigationGraph>,
+    mut q_items: Query<
+        (&Position, &mut Item, Option<&Commodity>),
+        (Without<InInventory>, Without<InSlot>),
+    >,
+    q_actors: Query<&Position, With<Actor>>,
+    mut q_behavior: Query<(
+        &ActorRef,
+        &mut TaskState,
+        &mut Blackboard,
+        &TaskFindIngredient,
+    )>,
+) {
+    for (ActorRef(actor), mut state, mut blackboard, task) in q_behavior.iter_mut() {
+        blackboard.item = None;
+
+        let Some(start_id) = q_actors.get(*actor).ok().and_then(|p| p.partition_id) else {
+            println!("Ingredient cannot be found because seeker is not in a partition!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let is_match = |e: Entity| {
+            q_items.get(e).is_ok_and(|(_, item, commodity)| {
+                item.reserved.is_none() && task.0.matches(item, commodity)
+            })
+        };
+
+        let Some(items) = find_nearest_item(start_id, &graph, is_match) else {
+            println!("No nearby ingredient");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let item_entity = items.first().unwrap();
+
+        let Ok((item_position, mut item, _)) = q_items.get_mut(*item_entity) else {
+            println!("Item without transform? Or stale item data");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        item.reserved = Some(*actor);
+        blackboard.item = Some(*item_entity);
+
+        let target_pos = item_position.as_array();
+        blackboard.move_goals = vec![target_pos];
+        blackboard.primary_goal = Some(target_pos);
+        *state = TaskState::Success;
+    }
+}
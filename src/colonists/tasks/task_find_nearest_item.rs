@@ -1,13 +1,10 @@
 use std::collections::VecDeque;
 
-use bevy::{
-    ecs::{
-        component::Component,
-        entity::Entity,
-        query::{With, Without},
-        system::{Query, Res},
-    },
-    utils::hashbrown::HashSet,
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    system::{Query, Res},
 };
 use task_derive::TaskBuilder;
 
@@ -16,6 +13,7 @@ use crate::{
         test_item_tags, Actor, ActorRef, Blackboard, InInventory, InSlot, Item, ItemTag,
         NavigationGraph, TaskBuilder, TaskState,
     },
+    common::StableHashSet,
     Position,
 };
 
@@ -47,7 +45,13 @@ pub fn task_find_nearest_item(
             continue;
         };
 
-        let Some(items) = find_nearest(start_id, task.0.clone(), &graph, &q_items) else {
+        let is_match = |e: Entity| {
+            q_items.get(e).is_ok_and(|(_, item)| {
+                item.reserved.is_none() && test_item_tags(&item.tags, &task.0)
+            })
+        };
+
+        let Some(items) = find_nearest_item(start_id, &graph, is_match) else {
             println!("No nearby item with matching tags");
             for tag in task.0.clone() {
                 println!("- tag {}", tag);
@@ -74,13 +78,14 @@ pub fn task_find_nearest_item(
     }
 }
 
-fn find_nearest(
+/// Items matching `is_match` in the nearest partition that has any, searching
+/// outwards from `start_id`.
+pub fn find_nearest_item(
     start_id: u32,
-    tags: Vec<ItemTag>,
     graph: &NavigationGraph,
-    q_items: &Query<(&Position, &mut Item), (Without<InInventory>, Without<InSlot>)>,
+    is_match: impl Fn(Entity) -> bool,
 ) -> Option<Vec<Entity>> {
-    let mut visited = HashSet::new();
+    let mut visited = StableHashSet::default();
     let mut queue = VecDeque::new();
 
     queue.push_back(start_id);
@@ -89,11 +94,7 @@ fn find_nearest(
 
     while let Some(partition_id) = queue.pop_front() {
         if visited.len() >= max_depth {
-            println!(
-                "max item search depth exceeded. Searching for {} in {}",
-                tags.first().unwrap(),
-                partition_id
-            );
+            println!("max item search depth exceeded in {}", partition_id);
             return None;
         }
 
@@ -106,17 +107,7 @@ fn find_nearest(
         let matching_items: Vec<Entity> = partition
             .items
             .iter()
-            .filter(|i| {
-                let Ok((_, item)) = q_items.get(**i) else {
-                    return false;
-                };
-
-                if item.reserved.is_some() {
-                    return false;
-                }
-
-                test_item_tags(&item.tags, &tags)
-            })
+            .filter(|i| is_match(**i))
             .cloned()
             .collect();
 
@@ -0,0 +1,87 @@
+use bevy::ecs::{
+    component::Component,
+    query::With,
+    system::{Query, Res},
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        is_reachable, job_access_points, Actor, ActorRef, Blackboard, JobType, NavigationFlags,
+        NavigationGraph, PartitionPathRequest, TaskBuilder, TaskState,
+    },
+    common::Distance,
+    BlockType, Position, Terrain,
+};
+
+/// How far from a colonist, in blocks along each axis, water is looked for.
+pub const WATER_SEARCH_RADIUS: i32 = 12;
+
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskFindWater;
+
+pub fn task_find_water(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<(&Position, &NavigationFlags), With<Actor>>,
+    mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskFindWater>>,
+) {
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        let Ok((position, flags)) = q_actors.get(*actor) else {
+            println!("Actor has no position, cannot find water!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some((water, goals)) = find_water(position.as_array(), *flags, &terrain, &graph) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        blackboard.move_goals = goals;
+        blackboard.primary_goal = Some(water);
+        blackboard.target_block = Some(water);
+        *state = TaskState::Success;
+    }
+}
+
+/// The nearest reachable water block, and where to stand to drink from it.
+pub fn find_water(
+    pos: [u32; 3],
+    flags: NavigationFlags,
+    terrain: &Terrain,
+    graph: &NavigationGraph,
+) -> Option<([u32; 3], Vec<[u32; 3]>)> {
+    let [x, y, z] = [pos[0] as i32, pos[1] as i32, pos[2] as i32];
+    let mut water = vec![];
+
+    for wx in (x - WATER_SEARCH_RADIUS)..=(x + WATER_SEARCH_RADIUS) {
+        for wy in (y - WATER_SEARCH_RADIUS)..=(y + WATER_SEARCH_RADIUS) {
+            for wz in (z - WATER_SEARCH_RADIUS)..=(z + WATER_SEARCH_RADIUS) {
+                if terrain.get_block_i32(wx, wy, wz).block == BlockType::WATER {
+                    water.push([wx, wy, wz]);
+                }
+            }
+        }
+    }
+
+    water.sort_by(|a, b| {
+        Distance::manhattan(*a, [x, y, z]).total_cmp(&Distance::manhattan(*b, [x, y, z]))
+    });
+
+    water.into_iter().find_map(|[wx, wy, wz]| {
+        let target = [wx as u32, wy as u32, wz as u32];
+        let goals = job_access_points(target, JobType::Mine);
+        let request = PartitionPathRequest {
+            start: pos,
+            goals: goals.clone(),
+            flags,
+        };
+
+        if is_reachable(&request, terrain, graph) {
+            Some((target, goals))
+        } else {
+            None
+        }
+    })
+}
@@ -0,0 +1,54 @@
+use bevy::ecs::{
+    component::Component,
+    query::{With, Without},
+    system::Query,
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{ActorRef, Blackboard, InInventory, InSlot, Item, JobHaul, TaskBuilder, TaskState},
+    Position,
+};
+
+/// Reserves the item of the haul job on the blackboard, and moves to it.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskGetHaulItem;
+
+pub fn task_get_haul_item(
+    q_jobs: Query<&JobHaul>,
+    mut q_items: Query<(&mut Item, &Position), (Without<InInventory>, Without<InSlot>)>,
+    mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskGetHaulItem>>,
+) {
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        let Some(job_entity) = blackboard.job else {
+            println!("Can't get haul item: no job on blackboard");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Ok(job_haul) = q_jobs.get(job_entity) else {
+            println!("Can't get haul item: job is not a haul job");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Ok((mut item, position)) = q_items.get_mut(job_haul.item) else {
+            println!("Can't get haul item: item is gone");
+            blackboard.job_invalid = true;
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        if item.reserved.is_some_and(|r| r != *actor) {
+            println!("Can't get haul item: item is reserved");
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        item.reserved = Some(*actor);
+        blackboard.item = Some(job_haul.item);
+        blackboard.move_goals = vec![position.as_array()];
+        blackboard.primary_goal = Some(position.as_array());
+        *state = TaskState::Success;
+    }
+}
@@ -8,10 +8,7 @@ use bevy::{
 };
 use task_derive::TaskBuilder;
 
-use crate::{
-    colonists::{Actor, ActorRef, AnimClip, Animator, TaskBuilder, TaskState},
-    ui::GameSpeed,
-};
+use crate::colonists::{Actor, ActorRef, AnimClip, Animator, TaskBuilder, TaskState};
 
 #[derive(Component, Clone, TaskBuilder)]
 pub struct TaskIdle {
@@ -30,7 +27,6 @@ impl Default for TaskIdle {
 
 pub fn task_idle(
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut q_animators: Query<&mut Animator, With<Actor>>,
     mut q_behavior: Query<(&ActorRef, &mut TaskState, &mut TaskIdle)>,
 ) {
@@ -44,6 +40,6 @@ pub fn task_idle(
             animator.clip = AnimClip::Idle;
         };
 
-        task.progress += time.delta_seconds() * game_speed.speed();
+        task.progress += time.delta_seconds();
     }
 }
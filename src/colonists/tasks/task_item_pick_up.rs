@@ -13,6 +13,7 @@ use crate::{
         Actor, ActorRef, Blackboard, InInventory, Inventory, Item, NavigationGraph, TaskBuilder,
         TaskState,
     },
+    items::InStockpile,
     Position,
 };
 
@@ -64,6 +65,7 @@ pub fn task_item_pick_up(
         inventory.items.insert(item);
         ecmd.insert(Visibility::Hidden);
         ecmd.insert(InInventory { holder: *actor });
+        ecmd.remove::<InStockpile>();
 
         *state = TaskState::Success;
     }
@@ -10,17 +10,13 @@ use bevy::{
 };
 use task_derive::TaskBuilder;
 
-use crate::{
-    colonists::{ActorRef, Blackboard, TaskBuilder, TaskState},
-    ui::GameSpeed,
-};
+use crate::colonists::{ActorRef, Blackboard, TaskBuilder, TaskState};
 
 #[derive(Component, Clone, TaskBuilder)]
 pub struct TaskLookAt;
 
 pub fn task_look_at(
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut q_transforms: Query<&mut Transform>,
     mut q_behavior: Query<(&ActorRef, &mut TaskState, &Blackboard), With<TaskLookAt>>,
 ) {
@@ -50,7 +46,7 @@ pub fn task_look_at(
 
         transform.rotation = transform
             .rotation
-            .slerp(target_rot, time.delta_seconds() * game_speed.speed() * 20.);
+            .slerp(target_rot, time.delta_seconds() * 20.);
 
         if transform.rotation.angle_between(target_rot) < 0.1 {
             transform.rotation = target_rot;
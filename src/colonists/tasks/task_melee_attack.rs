@@ -0,0 +1,90 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        event::EventWriter,
+        query::{Has, With},
+        system::{Query, Res},
+    },
+    math::Vec3,
+    time::Time,
+    transform::components::Transform,
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        is_in_reach, Actor, ActorRef, AnimClip, Animator, Blackboard, Creature, DamageEvent,
+        Downed, Melee, TaskBuilder, TaskState,
+    },
+    Position,
+};
+
+/// Strikes the blackboard's target until it dies or goes down. Fails once
+/// the target is out of reach.
+#[derive(Component, Clone, Default, TaskBuilder)]
+pub struct TaskMeleeAttack {
+    pub progress: f32,
+}
+
+#[allow(clippy::type_complexity)]
+pub fn task_melee_attack(
+    time: Res<Time>,
+    mut ev_damage: EventWriter<DamageEvent>,
+    mut q_attackers: Query<(&Position, &Melee, &mut Transform, Option<&mut Animator>), With<Actor>>,
+    q_targets: Query<(&Position, Option<&Creature>, Has<Downed>), With<Actor>>,
+    mut q_behavior: Query<(&ActorRef, &Blackboard, &mut TaskState, &mut TaskMeleeAttack)>,
+) {
+    for (ActorRef(actor), blackboard, mut state, mut task) in q_behavior.iter_mut() {
+        let Ok((position, melee, mut transform, animator)) = q_attackers.get_mut(*actor) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some(target) = blackboard.target else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        // a target that is gone has died
+        let Ok((target_position, target_creature, is_downed)) = q_targets.get(target) else {
+            *state = TaskState::Success;
+            continue;
+        };
+
+        // downed colonists are spared, downed hostiles are finished off
+        if is_downed && target_creature.is_none() {
+            *state = TaskState::Success;
+            continue;
+        }
+
+        if !is_in_reach(position.as_i32(), target_position.as_i32()) {
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        let facing = Vec3::new(
+            target_position.x as f32 + 0.5,
+            transform.translation.y,
+            target_position.z as f32 + 0.5,
+        );
+
+        if facing.distance_squared(transform.translation) > 0.01 {
+            transform.look_at(facing, Vec3::Y);
+        }
+
+        if let Some(mut animator) = animator {
+            animator.clip = AnimClip::SwingAxe;
+        }
+
+        task.progress += time.delta_seconds();
+
+        if task.progress >= melee.cooldown_s {
+            task.progress = 0.;
+
+            ev_damage.send(DamageEvent {
+                entity: target,
+                amount: melee.damage,
+            });
+        }
+    }
+}
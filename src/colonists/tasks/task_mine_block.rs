@@ -10,11 +10,15 @@ use bevy::{
 use task_derive::TaskBuilder;
 
 use crate::{
-    colonists::{Actor, ActorRef, AnimClip, Animator, Blackboard, TaskBuilder, TaskState},
+    colonists::{
+        Actor, ActorRef, AnimClip, Animator, Blackboard, DestroyItemEvent, Inventory, Item,
+        ItemTag, Skill, Skills, TaskBuilder, TaskState, SKILL_XP_PER_TASK,
+    },
     common::Rand,
-    items::{Commodity::StoneShaleBoulder, SpawnCommodityEvent},
-    ui::GameSpeed,
-    BlockType, Terrain,
+    items::{
+        tool_material, tool_work_speed, wear_tool, Durability, SpawnCommodityEvent, ToolMaterial,
+    },
+    BlockDrop, BlockType, Terrain,
 };
 
 #[derive(Component, Clone, TaskBuilder)]
@@ -22,13 +26,47 @@ pub struct TaskMineBlock {
     pub progress: f32,
 }
 
+/// Rolls each drop of a removed block, the chance raised by the skill and
+/// skipped if the tool is not good enough.
+pub fn spawn_block_drops(
+    drops: &[BlockDrop],
+    position: [u32; 3],
+    tool: Option<ToolMaterial>,
+    skill: Skill,
+    skills: &Skills,
+    rand: &mut Rand,
+    ev_spawn_commodity: &mut EventWriter<SpawnCommodityEvent>,
+) {
+    for drop in drops.iter() {
+        if !drop.is_tool_sufficient(tool) || !rand.bool(skills.drop_chance(skill, drop.chance)) {
+            continue;
+        }
+
+        let (min, max) = drop.count;
+        let count = rand.range_n(min as i32, max as i32 + 1);
+
+        for _ in 0..count {
+            ev_spawn_commodity.send(SpawnCommodityEvent {
+                entity: None,
+                commodity: drop.commodity,
+                position,
+                slot_entity: None,
+                slot_idx: None,
+            });
+        }
+    }
+}
+
 pub fn task_mine_block(
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut terrain: ResMut<Terrain>,
     mut q_animators: Query<&mut Animator, With<Actor>>,
+    mut q_skills: Query<&mut Skills, With<Actor>>,
+    mut q_inventories: Query<&mut Inventory, With<Actor>>,
+    mut q_tools: Query<(&Item, &ToolMaterial, &mut Durability)>,
     mut q_behavior: Query<(&ActorRef, &mut TaskState, &Blackboard, &mut TaskMineBlock)>,
     mut ev_spawn_commodity: EventWriter<SpawnCommodityEvent>,
+    mut ev_destroy_item: EventWriter<DestroyItemEvent>,
     mut rand: ResMut<Rand>,
 ) {
     for (ActorRef(actor), mut state, blackboard, mut task) in q_behavior.iter_mut() {
@@ -38,22 +76,46 @@ pub fn task_mine_block(
             continue;
         };
 
-        if terrain.get_block(x, y, z).is_empty() {
+        let block = terrain.get_block(x, y, z);
+
+        if block.is_empty() {
             *state = TaskState::Success;
             continue;
         }
 
-        if task.progress >= 3. {
+        let definition = block.block.definition();
+        let skills = q_skills.get(*actor).copied().unwrap_or_default();
+
+        if task.progress >= definition.hardness.unwrap_or(0.) {
             terrain.set_flag_mine(x, y, z, false);
             terrain.set_block_type(x, y, z, BlockType::EMPTY);
 
-            if rand.bool(0.15) {
-                ev_spawn_commodity.send(SpawnCommodityEvent {
-                    commodity: StoneShaleBoulder,
-                    position: [x, y, z],
-                    slot_entity: None,
-                    slot_idx: None,
-                });
+            let tool = q_inventories
+                .get(*actor)
+                .ok()
+                .and_then(|i| tool_material(i, &[ItemTag::Pickaxe], &q_tools));
+
+            spawn_block_drops(
+                &definition.drops,
+                [x, y, z],
+                tool,
+                Skill::Mining,
+                &skills,
+                &mut rand,
+                &mut ev_spawn_commodity,
+            );
+
+            if let Ok(mut skills) = q_skills.get_mut(*actor) {
+                skills.gain(Skill::Mining, SKILL_XP_PER_TASK);
+            }
+
+            if let Ok(mut inventory) = q_inventories.get_mut(*actor) {
+                wear_tool(
+                    &mut inventory,
+                    &[ItemTag::Pickaxe],
+                    &mut q_tools,
+                    &mut ev_destroy_item,
+                );
             }
 
             *state = TaskState::Success;
@@ -64,6 +126,10 @@ pub fn task_mine_block(
             animator.clip = AnimClip::SwingPick;
         };
 
-        task.progress += time.delta_seconds() * game_speed.speed();
+        let tool_speed = q_inventories
+            .get(*actor)
+            .map_or(1., |i| tool_work_speed(i, &[ItemTag::Pickaxe], &q_tools));
+
+        task.progress += time.delta_seconds() * skills.work_speed(Skill::Mining) * tool_speed;
     }
 }
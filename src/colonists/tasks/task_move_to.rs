@@ -1,15 +1,17 @@
+use std::task::Poll;
+
 use bevy::ecs::{
     component::Component,
     query::With,
-    system::{Commands, Query, Res},
+    system::{Commands, Query, Res, ResMut},
 };
 use task_derive::TaskBuilder;
 
 use crate::{
     colonists::{
-        get_block_flags, get_granular_path, get_partition_path, is_reachable, Actor, ActorRef,
-        AnimClip, Animator, Blackboard, BlockMove, GranularPathRequest, NavigationFlags,
-        NavigationGraph, PartitionPathRequest, Path, TaskBuilder, TaskState,
+        get_block_flags, is_reachable, repair_path, Actor, ActorRef, AnimClip, Animator,
+        Blackboard, BlockMove, GranularPathRequest, JobHaul, NavigationFlags, NavigationGraph,
+        PartitionPathRequest, Path, PathCache, PathQueue, Skill, Skills, TaskBuilder, TaskState,
     },
     Position, Terrain,
 };
@@ -29,14 +31,20 @@ impl Default for TaskMoveTo {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn task_move_to(
     mut cmd: Commands,
     terrain: Res<Terrain>,
     graph: Res<NavigationGraph>,
+    mut path_cache: ResMut<PathCache>,
+    mut path_queue: ResMut<PathQueue>,
     mut q_paths: Query<&mut Path, With<Actor>>,
     q_movers: Query<&BlockMove, With<Actor>>,
     mut q_animators: Query<&mut Animator, With<Actor>>,
     q_positions: Query<&Position, With<Actor>>,
+    q_flags: Query<&NavigationFlags, With<Actor>>,
+    q_skills: Query<&Skills, With<Actor>>,
+    q_haul_jobs: Query<(), With<JobHaul>>,
     mut q_behavior: Query<(&ActorRef, &Blackboard, &mut TaskState, &mut TaskMoveTo)>,
 ) {
     for (ActorRef(actor), blackboard, mut state, mut move_to) in q_behavior.iter_mut() {
@@ -52,6 +60,10 @@ pub fn task_move_to(
         }
 
         let pos = [position.x, position.y, position.z];
+        let flags = q_flags
+            .get(*actor)
+            .copied()
+            .unwrap_or(NavigationFlags::COLONIST);
 
         let Ok(mut path) = q_paths.get_mut(*actor) else {
             if blackboard.move_goals.is_empty() {
@@ -63,10 +75,15 @@ pub fn task_move_to(
             let request = PartitionPathRequest {
                 start: pos,
                 goals: blackboard.move_goals.clone(),
-                flags: NavigationFlags::COLONIST,
+                flags,
+            };
+
+            let Poll::Ready(partition_path) = path_queue.poll_partition(*actor, request.clone())
+            else {
+                continue;
             };
 
-            let Some(partition_path) = get_partition_path(&request, &terrain, &graph) else {
+            let Some(partition_path) = partition_path else {
                 if !is_reachable(&request, &terrain, &graph) {
                     *state = TaskState::Failed;
                     continue;
@@ -119,6 +136,22 @@ pub fn task_move_to(
             continue;
         };
 
+        // terrain edits rebuild partitions, so re-plan around the ones that are gone
+        let is_broken = path.partition_path[..=path.current_partition_idx]
+            .iter()
+            .any(|p| graph.get_partition(p).is_none());
+
+        if is_broken && !repair_path(&mut path, pos, &terrain, &graph, &mut path_cache) {
+            move_to.attempts += 1;
+            cmd.entity(*actor).remove::<Path>();
+
+            if move_to.attempts >= move_to.max_retries {
+                *state = TaskState::Failed;
+            }
+
+            continue;
+        }
+
         let partition_path_idx = path.partition_path.iter().position(|p| *p == partition_id);
 
         if let Some(idx) = partition_path_idx {
@@ -132,19 +165,27 @@ pub fn task_move_to(
                 continue;
             };
 
-            let Some(granular_path) = get_granular_path(
-                &graph,
-                &terrain,
-                &GranularPathRequest {
-                    start: pos,
-                    goals: path.goals.clone(),
-                    goal_partition_id: *next_partition_id,
-                    partition_path: path.partition_path.clone(),
-                    flags: NavigationFlags::COLONIST,
-                },
-            ) else {
+            let request = GranularPathRequest {
+                start: pos,
+                goals: path.goals.clone(),
+                goal_partition_id: *next_partition_id,
+                partition_path: path.partition_path.clone(),
+                flags: path.flags,
+            };
+
+            let Poll::Ready(granular_path) = path_queue.poll_granular(*actor, request) else {
+                continue;
+            };
+
+            let Some(granular_path) = granular_path else {
                 move_to.attempts += 1;
-                cmd.entity(*actor).remove::<Path>();
+
+                let is_repaired = move_to.attempts < move_to.max_retries
+                    && repair_path(&mut path, pos, &terrain, &graph, &mut path_cache);
+
+                if !is_repaired {
+                    cmd.entity(*actor).remove::<Path>();
+                }
 
                 if move_to.attempts >= move_to.max_retries {
                     println!(
@@ -170,8 +211,10 @@ pub fn task_move_to(
 
         let block_flags = get_block_flags(&terrain, next_block[0], next_block[1], next_block[2]);
 
+        // blocked since the path was found, find a new way from here
         if block_flags & path.flags == NavigationFlags::NONE {
-            cmd.entity(*actor).remove::<Path>();
+            path.blocks.clear();
+            path.current_block_idx = 0;
             continue;
         }
 
@@ -179,8 +222,15 @@ pub fn task_move_to(
             animator.clip = AnimClip::Run;
         };
 
+        // skilled haulers carry items faster
+        let is_hauling = blackboard.job.is_some_and(|j| q_haul_jobs.contains(j));
+        let speed = match q_skills.get(*actor) {
+            Ok(skills) if is_hauling => 4. * skills.work_speed(Skill::Hauling),
+            _ => 4.,
+        };
+
         cmd.entity(*actor).insert(BlockMove {
-            speed: 4.,
+            speed,
             target: path.blocks[path.current_block_idx],
             look_at: true,
         });
@@ -0,0 +1,77 @@
+use bevy::ecs::{
+    component::Component,
+    query::With,
+    system::{Query, Res},
+};
+use ordered_float::OrderedFloat;
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        alert_distance, nearest_threat, Actor, ActorRef, Blackboard, CombatantQuery, Creature,
+        NavigationFlags, NavigationGraph, TaskBuilder, TaskState,
+    },
+    common::Distance,
+    Position, Terrain,
+};
+
+/// Picks the block furthest from the nearest threat, within the actor's
+/// partition and the ones next to it.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskPickFleeSpot;
+
+pub fn task_pick_flee_spot(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_actors: Query<(&Position, &NavigationFlags, Option<&Creature>), With<Actor>>,
+    q_combatants: CombatantQuery,
+    mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskPickFleeSpot>>,
+) {
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        let Ok((position, flags, creature)) = q_actors.get(*actor) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some(threat) = nearest_threat(&q_combatants, *actor, alert_distance(creature)) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some(partition) = position
+            .partition_id
+            .and_then(|id| graph.get_partition(&id))
+        else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let spot = partition
+            .neighbor_ids
+            .iter()
+            .filter_map(|id| graph.get_partition(id))
+            .filter(|p| p.flags.intersects(*flags))
+            .chain([partition])
+            .flat_map(|p| {
+                p.blocks
+                    .iter()
+                    .map(|block_idx| terrain.get_block_world_pos(p.chunk_idx, *block_idx))
+            })
+            .max_by_key(|[x, y, z]| {
+                OrderedFloat(Distance::diagonal(
+                    [*x as i32, *y as i32, *z as i32],
+                    threat,
+                ))
+            });
+
+        let Some(spot) = spot else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        blackboard.move_goals = vec![spot];
+        blackboard.primary_goal = Some(spot);
+
+        *state = TaskState::Success;
+    }
+}
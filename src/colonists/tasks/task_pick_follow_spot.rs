@@ -0,0 +1,60 @@
+use bevy::ecs::{
+    component::Component,
+    query::With,
+    system::{Query, Res},
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        get_block_flags, Actor, ActorRef, Blackboard, Colonist, Creature, NavigationFlags,
+        TaskBuilder, TaskState,
+    },
+    Position, Terrain,
+};
+
+/// Picks the blocks around a pet's owner to move to.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskPickFollowSpot;
+
+pub fn task_pick_follow_spot(
+    terrain: Res<Terrain>,
+    q_actors: Query<(&Creature, &NavigationFlags), With<Actor>>,
+    q_colonists: Query<(&Colonist, &Position)>,
+    mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskPickFollowSpot>>,
+) {
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        let Ok((creature, flags)) = q_actors.get(*actor) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some((_, owner_position)) = q_colonists
+            .iter()
+            .find(|(c, _)| creature.owner == Some(c.id))
+        else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let [x, y, z] = owner_position.as_i32();
+
+        let goals: Vec<[u32; 3]> = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dz| [x + dx, y, z + dz]))
+            .filter(|[x, y, z]| {
+                get_block_flags(&terrain, *x, *y, *z) & *flags != NavigationFlags::NONE
+            })
+            .map(|[x, y, z]| [x as u32, y as u32, z as u32])
+            .collect();
+
+        if goals.is_empty() {
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        blackboard.move_goals = goals;
+        blackboard.primary_goal = Some(owner_position.as_array());
+
+        *state = TaskState::Success;
+    }
+}
@@ -20,17 +20,20 @@ pub fn task_pick_random_spot(
     mut rand: ResMut<Rand>,
     terrain: Res<Terrain>,
     graph: Res<NavigationGraph>,
-    q_positions: Query<&Position, With<Actor>>,
+    q_positions: Query<(&Position, Option<&NavigationFlags>), With<Actor>>,
     mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskPickRandomSpot>>,
 ) {
     for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
-        let Ok(position) = q_positions.get(*actor) else {
+        let Ok((position, flags)) = q_positions.get(*actor) else {
             println!("no transform on actor, cannot pick random spot!");
             *state = TaskState::Failed;
             continue;
         };
 
         let pos = [position.x, position.y, position.z];
+        // wander over open ground rather than onto ladders or through doors
+        let flags = flags.copied().unwrap_or(NavigationFlags::COLONIST)
+            & (NavigationFlags::SHORT | NavigationFlags::TALL);
 
         let Some(current_partition_id) = terrain.get_partition_id_u32(pos[0], pos[1], pos[2])
         else {
@@ -52,10 +55,7 @@ pub fn task_pick_random_spot(
                 .filter_map(|n| {
                     let p = graph.get_partition(n)?;
 
-                    if !p
-                        .flags
-                        .intersects(NavigationFlags::SHORT | NavigationFlags::TALL)
-                    {
+                    if !p.flags.intersects(flags) {
                         return None;
                     }
 
@@ -0,0 +1,66 @@
+use bevy::ecs::{
+    component::Component,
+    query::With,
+    system::{Query, Res},
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        get_block_flags, nearest_enemy, ActorRef, Blackboard, CombatantQuery, NavigationFlags,
+        NavigationGraph, TaskBuilder, TaskState,
+    },
+    Terrain,
+};
+
+/// Picks the nearest enemy within `max_distance` to fight, and the blocks
+/// around it to move to.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskPickTarget {
+    pub max_distance: f32,
+}
+
+pub fn task_pick_target(
+    terrain: Res<Terrain>,
+    graph: Res<NavigationGraph>,
+    q_combatants: CombatantQuery,
+    mut q_behavior: Query<
+        (&ActorRef, &mut Blackboard, &mut TaskState, &TaskPickTarget),
+        With<TaskPickTarget>,
+    >,
+) {
+    for (ActorRef(actor), mut blackboard, mut state, task) in q_behavior.iter_mut() {
+        let Ok((_, _, flags, _, _)) = q_combatants.get(*actor) else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some((target, target_pos)) =
+            nearest_enemy(&terrain, &graph, &q_combatants, *actor, task.max_distance)
+        else {
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let [x, y, z] = target_pos.map(|v| v as i32);
+
+        let goals: Vec<[u32; 3]> = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dz| [x + dx, y, z + dz]))
+            .filter(|[x, y, z]| {
+                get_block_flags(&terrain, *x, *y, *z) & *flags != NavigationFlags::NONE
+            })
+            .map(|[x, y, z]| [x as u32, y as u32, z as u32])
+            .collect();
+
+        if goals.is_empty() {
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        blackboard.target = Some(target);
+        blackboard.move_goals = goals;
+        blackboard.primary_goal = Some(target_pos);
+
+        *state = TaskState::Success;
+    }
+}
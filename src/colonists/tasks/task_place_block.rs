@@ -2,6 +2,7 @@ use bevy::{
     ecs::{
         component::Component,
         event::EventWriter,
+        query::With,
         system::{Query, Res, ResMut},
     },
     time::Time,
@@ -9,9 +10,11 @@ use bevy::{
 use task_derive::TaskBuilder;
 
 use crate::{
-    colonists::{Blackboard, DestroyItemEvent, TaskBuilder, TaskState},
-    ui::GameSpeed,
-    BlockType, Terrain,
+    colonists::{
+        Actor, ActorRef, Blackboard, DestroyItemEvent, Inventory, Skill, Skills, TaskBuilder,
+        TaskState, SKILL_XP_PER_TASK,
+    },
+    BlockRegistry, BlockType, Terrain,
 };
 
 #[derive(Component, Clone, TaskBuilder)]
@@ -22,12 +25,13 @@ pub struct TaskPlaceBlock {
 
 pub fn task_place_block(
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
     mut terrain: ResMut<Terrain>,
-    mut q_behavior: Query<(&mut TaskState, &Blackboard, &mut TaskPlaceBlock)>,
+    mut q_skills: Query<&mut Skills, With<Actor>>,
+    mut q_inventories: Query<&mut Inventory, With<Actor>>,
+    mut q_behavior: Query<(&ActorRef, &mut TaskState, &Blackboard, &mut TaskPlaceBlock)>,
     mut ev_destroy_item: EventWriter<DestroyItemEvent>,
 ) {
-    for (mut state, blackboard, mut task) in q_behavior.iter_mut() {
+    for (ActorRef(actor), mut state, blackboard, mut task) in q_behavior.iter_mut() {
         let Some([x, y, z]) = blackboard.target_block else {
             println!("Blackboard is missing target_block, cannot mine!");
             *state = TaskState::Failed;
@@ -41,7 +45,12 @@ pub fn task_place_block(
             continue;
         }
 
-        if blackboard.item.is_none() {
+        let needs_material = BlockRegistry::get()
+            .definition(task.block_type)
+            .material
+            .is_some();
+
+        if needs_material && blackboard.item.is_none() {
             println!("Blackboard is missing item, cannot place!");
             *state = TaskState::Failed;
             continue;
@@ -50,13 +59,24 @@ pub fn task_place_block(
         if task.progress >= 1. {
             terrain.set_block_type(x, y, z, task.block_type);
 
-            let item = blackboard.item.unwrap();
-            ev_destroy_item.send(DestroyItemEvent { entity: item });
+            if let Some(item) = blackboard.item.filter(|_| needs_material) {
+                if let Ok(mut inventory) = q_inventories.get_mut(*actor) {
+                    inventory.items.remove(&item);
+                }
+
+                ev_destroy_item.send(DestroyItemEvent { entity: item });
+            }
+
+            if let Ok(mut skills) = q_skills.get_mut(*actor) {
+                skills.gain(Skill::Construction, SKILL_XP_PER_TASK);
+            }
 
             *state = TaskState::Success;
             continue;
         }
 
-        task.progress += time.delta_seconds() * game_speed.speed();
+        let skills = q_skills.get(*actor).copied().unwrap_or_default();
+
+        task.progress += time.delta_seconds() * skills.work_speed(Skill::Construction);
     }
 }
@@ -0,0 +1,37 @@
+use bevy::{
+    ecs::{component::Component, query::With, system::Query},
+    math::{EulerRot, Quat},
+    transform::components::Transform,
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{ActorRef, Blackboard, TaskBuilder, TaskState},
+    structures::Bed,
+};
+
+/// Gets the colonist up, and frees the bed they were sleeping in for others.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskReleaseBed;
+
+pub fn task_release_bed(
+    mut q_beds: Query<&mut Bed>,
+    mut q_transforms: Query<&mut Transform>,
+    mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskReleaseBed>>,
+) {
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        if let Some(mut bed) = blackboard.bed.and_then(|e| q_beds.get_mut(e).ok()) {
+            if bed.reserved == Some(*actor) {
+                bed.reserved = None;
+            }
+        }
+
+        if let Ok(mut transform) = q_transforms.get_mut(*actor) {
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            transform.rotation = Quat::from_rotation_y(yaw);
+        }
+
+        blackboard.bed = None;
+        *state = TaskState::Success;
+    }
+}
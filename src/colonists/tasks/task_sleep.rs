@@ -4,37 +4,74 @@ use bevy::{
         query::With,
         system::{Query, Res},
     },
+    math::{EulerRot, Quat},
     time::Time,
+    transform::components::Transform,
 };
 use task_derive::TaskBuilder;
 
 use crate::{
-    colonists::{ActorRef, Blackboard, Fatigue, TaskBuilder, TaskState},
-    ui::GameSpeed,
+    colonists::{
+        Activity, ActorRef, AnimClip, Animator, Blackboard, DailySchedule, Fatigue, Needs,
+        TaskBuilder, TaskState,
+    },
+    TimeOfDay,
 };
 
+/// Fatigue and comfort recovered per second asleep in a bed.
+pub const SLEEP_RECOVERY: f32 = 40.;
+/// Fatigue and comfort recovered per second asleep on the floor.
+pub const FLOOR_SLEEP_RECOVERY: f32 = 10.;
+
+/// Sleeps in the bed on the blackboard, or on the floor if there is none.
 #[derive(Component, Clone, TaskBuilder)]
 pub struct TaskSleep;
 
 pub fn task_sleep(
     time: Res<Time>,
-    game_speed: Res<GameSpeed>,
-    mut q_fatigues: Query<&mut Fatigue>,
+    time_of_day: Res<TimeOfDay>,
+    mut q_fatigues: Query<(&mut Fatigue, Option<&mut Needs>, Option<&DailySchedule>)>,
+    mut q_sleepers: Query<(&mut Transform, Option<&mut Animator>)>,
     mut q_behavior: Query<(&ActorRef, &Blackboard, &mut TaskState), With<TaskSleep>>,
 ) {
     for (ActorRef(entity), blackboard, mut state) in q_behavior.iter_mut() {
-        let Ok(mut fatigue) = q_fatigues.get_mut(*entity) else {
+        let Ok((mut fatigue, needs, schedule)) = q_fatigues.get_mut(*entity) else {
             println!("Actor entity does not have a fatigue");
             *state = TaskState::Failed;
             continue;
         };
 
+        let recovery = if blackboard.bed.is_some() {
+            SLEEP_RECOVERY
+        } else {
+            FLOOR_SLEEP_RECOVERY
+        };
+
+        if let Ok((mut transform, animator)) = q_sleepers.get_mut(*entity) {
+            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            transform.rotation =
+                Quat::from_rotation_y(yaw) * Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2);
+
+            if let Some(mut animator) = animator {
+                animator.clip = AnimClip::LieDown;
+            }
+        }
+
         if fatigue.value > 0. {
-            fatigue.value -= time.delta_seconds() * 40. * game_speed.speed();
+            fatigue.value -= time.delta_seconds() * recovery;
         }
 
-        if fatigue.value <= 0. {
-            println!("slept in bed {}", blackboard.bed);
+        let mut is_comfortable = true;
+
+        if let Some(mut needs) = needs {
+            needs.comfort.satisfy(time.delta_seconds() * recovery);
+            is_comfortable = needs.comfort.value <= 0.;
+        }
+
+        // colonists sleep through the night even once rested
+        let is_sleep_hours = schedule.is_some_and(|s| s.at(time_of_day.hour()) == Activity::Sleep);
+
+        if fatigue.value <= 0. && is_comfortable && !is_sleep_hours {
             fatigue.value = 0.;
             *state = TaskState::Success;
         }
@@ -0,0 +1,118 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        query::With,
+        system::{Commands, Query, ResMut},
+    },
+    hierarchy::BuildChildren,
+    math::Vec3,
+    render::view::Visibility,
+    transform::components::Transform,
+};
+use task_derive::TaskBuilder;
+
+use crate::{
+    colonists::{
+        Actor, ActorRef, Blackboard, InInventory, Inventory, Item, JobHaul, JobLocation,
+        NavigationGraph, Skill, Skills, TaskBuilder, TaskState, SKILL_XP_PER_TASK,
+    },
+    items::{InStockpile, Stockpile},
+    Position, Terrain,
+};
+
+/// Sets the item on the blackboard down in the haul job's stockpile.
+#[derive(Component, Clone, TaskBuilder)]
+pub struct TaskStoreItem;
+
+pub fn task_store_item(
+    mut cmd: Commands,
+    mut terrain: ResMut<Terrain>,
+    mut graph: ResMut<NavigationGraph>,
+    q_jobs: Query<(&JobHaul, &JobLocation)>,
+    q_stockpiles: Query<&Stockpile>,
+    mut q_items: Query<(&mut Item, &mut Position, &mut Transform)>,
+    mut q_actors: Query<(&mut Inventory, Option<&mut Skills>), With<Actor>>,
+    mut q_behavior: Query<(&ActorRef, &mut Blackboard, &mut TaskState), With<TaskStoreItem>>,
+) {
+    for (ActorRef(actor), mut blackboard, mut state) in q_behavior.iter_mut() {
+        let Some(item_entity) = blackboard.item else {
+            println!("No item on blackboard, cannot store!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Some((job_haul, job_location)) = blackboard.job.and_then(|j| q_jobs.get(j).ok()) else {
+            println!("No haul job on blackboard, cannot store!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let tile = job_location.primary_target;
+
+        if !q_stockpiles
+            .get(job_haul.stockpile)
+            .is_ok_and(|s| s.contains(tile))
+        {
+            println!("Stockpile is gone, cannot store!");
+            blackboard.job_invalid = true;
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        let Ok((mut inventory, skills)) = q_actors.get_mut(*actor) else {
+            println!("Actor does not have an inventory, cannot store!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        let Ok((mut item, mut position, mut transform)) = q_items.get_mut(item_entity) else {
+            println!("Item does not exist, cannot store!");
+            *state = TaskState::Failed;
+            continue;
+        };
+
+        if !inventory.items.remove(&item_entity) {
+            println!("Item is not in inventory, cannot store!");
+            *state = TaskState::Failed;
+            continue;
+        }
+
+        item.reserved = None;
+        transform.translation =
+            Vec3::new(tile[0] as f32 + 0.5, tile[1] as f32, tile[2] as f32 + 0.5);
+
+        // the item is moved here rather than in update_positions, it may be
+        // set down in the partition it was picked up from
+        let [chunk_idx, block_idx] = terrain.get_block_indexes(tile[0], tile[1], tile[2]);
+        terrain.remove_item(position.chunk_idx, position.block_idx, &item_entity);
+        terrain.add_item(chunk_idx, block_idx, item_entity);
+
+        if let Some(partition_id) = position.partition_id {
+            graph.remove_item_from_partition(&partition_id, &item_entity);
+        }
+
+        let partition_id = terrain.get_partition_id(chunk_idx, block_idx);
+
+        if let Some(partition_id) = partition_id {
+            graph.add_item_to_partition(&partition_id, item_entity);
+        }
+
+        position.x = tile[0];
+        position.y = tile[1];
+        position.z = tile[2];
+        position.chunk_idx = chunk_idx;
+        position.block_idx = block_idx;
+        position.partition_id = partition_id;
+
+        cmd.entity(item_entity)
+            .remove::<InInventory>()
+            .remove_parent()
+            .insert((Visibility::Inherited, InStockpile(job_haul.stockpile)));
+
+        if let Some(mut skills) = skills {
+            skills.gain(Skill::Hauling, SKILL_XP_PER_TASK);
+        }
+
+        *state = TaskState::Success;
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        event::{Event, EventReader},
+        system::Query,
+    },
+    reflect::Reflect,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{Colonist, JOB_PRIORITY_DEFAULT};
+
+/// Most preferred rank, colonists favour work ranked 1 over work ranked 5.
+pub const WORK_RANK_MIN: u8 = 1;
+pub const WORK_RANK_MAX: u8 = 5;
+
+/// What a ready colonist scores a job of default priority and middle rank.
+pub const WORK_SCORE: f32 = 0.6;
+
+/// Readiness of work that needs a tool or material fetched first.
+pub const WORK_READINESS_FETCH: f32 = 1. / 3.;
+
+/// Score change for each step of job priority. Larger than the rank step, so
+/// the player's priorities outweigh a colonist's preferences.
+const WORK_SCORE_PER_PRIORITY: f32 = 0.1;
+const WORK_SCORE_PER_RANK: f32 = 0.05;
+
+/// The kinds of labor a colonist can be assigned to. Supplying a structure
+/// is part of building it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum WorkType {
+    Build,
+    Mine,
+    Chop,
+    PlaceBlock,
+    Haul,
+    Craft,
+}
+
+impl WorkType {
+    pub const ALL: [WorkType; 6] = [
+        WorkType::Build,
+        WorkType::Mine,
+        WorkType::Chop,
+        WorkType::PlaceBlock,
+        WorkType::Haul,
+        WorkType::Craft,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WorkType::Build => "Build",
+            WorkType::Mine => "Mine",
+            WorkType::Chop => "Chop",
+            WorkType::PlaceBlock => "Place",
+            WorkType::Haul => "Haul",
+            WorkType::Craft => "Craft",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct WorkPreference {
+    pub is_enabled: bool,
+    pub rank: u8,
+}
+
+impl WorkPreference {
+    pub fn new(rank: u8) -> Self {
+        Self {
+            is_enabled: true,
+            rank: rank.clamp(WORK_RANK_MIN, WORK_RANK_MAX),
+        }
+    }
+}
+
+/// Which work a colonist takes on, and how much they prefer each kind.
+#[derive(Component, Clone, Debug, Reflect, Serialize, Deserialize)]
+pub struct WorkPreferences {
+    pub preferences: Vec<(WorkType, WorkPreference)>,
+}
+
+impl Default for WorkPreferences {
+    fn default() -> Self {
+        Self {
+            preferences: vec![
+                (WorkType::Build, WorkPreference::new(1)),
+                (WorkType::Mine, WorkPreference::new(2)),
+                (WorkType::Chop, WorkPreference::new(2)),
+                (WorkType::PlaceBlock, WorkPreference::new(2)),
+                (WorkType::Haul, WorkPreference::new(3)),
+                (WorkType::Craft, WorkPreference::new(2)),
+            ],
+        }
+    }
+}
+
+impl WorkPreferences {
+    pub fn get(&self, work_type: WorkType) -> WorkPreference {
+        self.preferences
+            .iter()
+            .find(|(w, _)| *w == work_type)
+            .map(|(_, p)| *p)
+            .unwrap_or(WorkPreference::new(WORK_RANK_MAX))
+    }
+
+    pub fn set(&mut self, work_type: WorkType, preference: WorkPreference) {
+        if let Some((_, p)) = self.preferences.iter_mut().find(|(w, _)| *w == work_type) {
+            *p = preference;
+        } else {
+            self.preferences.push((work_type, preference));
+        }
+    }
+
+    pub fn is_enabled(&self, work_type: WorkType) -> bool {
+        self.get(work_type).is_enabled
+    }
+
+    /// Score for taking on work at a job priority. `readiness` is 1 when the
+    /// colonist can start right away, and less when it must first fetch a
+    /// tool or material.
+    pub fn score(&self, work_type: WorkType, priority: u8, readiness: f32) -> f32 {
+        let preference = self.get(work_type);
+
+        if !preference.is_enabled {
+            return 0.;
+        }
+
+        let middle_rank = (WORK_RANK_MIN + WORK_RANK_MAX) as f32 / 2.;
+        let priority_bonus =
+            (priority as f32 - JOB_PRIORITY_DEFAULT as f32) * WORK_SCORE_PER_PRIORITY;
+        let rank_bonus = (middle_rank - preference.rank as f32) * WORK_SCORE_PER_RANK;
+
+        readiness * (WORK_SCORE + priority_bonus + rank_bonus)
+    }
+}
+
+#[derive(Event)]
+pub struct SetWorkPreferenceEvent {
+    /// the colonist's `Colonist::id`
+    pub colonist: u32,
+    pub work_type: WorkType,
+    pub preference: WorkPreference,
+}
+
+pub fn on_set_work_preference(
+    mut ev_set_work_preference: EventReader<SetWorkPreferenceEvent>,
+    mut q_colonists: Query<(&Colonist, &mut WorkPreferences)>,
+) {
+    for ev in ev_set_work_preference.read() {
+        let Some((_, mut work)) = q_colonists.iter_mut().find(|(c, _)| c.id == ev.colonist) else {
+            println!("No colonist with id {}", ev.colonist);
+            continue;
+        };
+
+        let preference = WorkPreference {
+            is_enabled: ev.preference.is_enabled,
+            rank: ev.preference.rank.clamp(WORK_RANK_MIN, WORK_RANK_MAX),
+        };
+
+        work.set(ev.work_type, preference);
+    }
+}
@@ -1,5 +1,5 @@
 use ordered_float::*;
-use std::collections::HashMap;
+use std::{cmp::Reverse, collections::HashMap};
 
 use crate::common::PriorityQueue;
 
@@ -50,7 +50,7 @@ where
         return result;
     }
 
-    open.put(settings.start, OrderedFloat(0.));
+    open.put(settings.start, Reverse(OrderedFloat(0.)));
     costs.insert(settings.start, OrderedFloat(0.));
 
     while !open.is_empty() {
@@ -87,8 +87,8 @@ where
             if !costs.contains_key(&next) || new_cost < *costs.get(&next).unwrap() {
                 costs.insert(next, new_cost);
 
-                // todo: use a min priority queue and remove hard-coded float here
-                let priority = OrderedFloat(100000.0) - new_cost * (settings.heuristic)(next);
+                // the queue pops the highest priority, so cheapest first is reversed
+                let priority = Reverse(new_cost + (settings.heuristic)(next));
 
                 open.put(next, priority);
                 from.insert(next, current);
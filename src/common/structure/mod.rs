@@ -1,3 +1,5 @@
 mod priority_queue;
+mod stable_hash;
 
 pub use priority_queue::*;
+pub use stable_hash::*;
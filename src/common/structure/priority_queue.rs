@@ -14,7 +14,7 @@ impl<T, A: Ord + PartialEq + Eq + PartialOrd> PartialEq for PriorityQueueItem<T,
 }
 impl<T, A: Ord + PartialEq + Eq + PartialOrd> PartialOrd for PriorityQueueItem<T, A> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cost.cmp(&other.cost))
+        Some(self.cmp(other))
     }
 }
 impl<T, A: Ord + PartialEq + Eq + PartialOrd> Ord for PriorityQueueItem<T, A> {
@@ -0,0 +1,7 @@
+use bevy::utils::{hashbrown, FixedState};
+
+/// Bevy's `HashMap` and `HashSet` are seeded per process, so their iteration
+/// order changes between runs. The simulation iterates these to make
+/// decisions, and uses fixed seeds instead to stay deterministic.
+pub type StableHashMap<K, V> = hashbrown::HashMap<K, V, FixedState>;
+pub type StableHashSet<K> = hashbrown::HashSet<K, FixedState>;
@@ -1,5 +1,5 @@
 use bevy::{
-    core_pipeline::prepass::{DepthPrepass, MotionVectorPrepass, NormalPrepass},
+    core_pipeline::prepass::{DepthPrepass, NormalPrepass},
     input::mouse::{MouseMotion, MouseWheel},
     pbr::NotShadowCaster,
     prelude::*,
@@ -7,7 +7,10 @@ use bevy::{
 };
 use bevy_mod_picking::picking_core::Pickable;
 
-use crate::TerrainSlice;
+use crate::{
+    simulation::{SimInput, SimInputEvent},
+    ChunkStreaming, Terrain, TerrainSlice,
+};
 
 use super::{PrepassOutputMaterial, ShowPrepassSettings};
 
@@ -136,19 +139,55 @@ fn get_primary_window_size(window: &Window) -> Vec2 {
     Vec2::new(window.width(), window.height())
 }
 
+/// Moves the chunks that are kept loaded along with the camera, see
+/// `ChunkStreaming`. Only a change of chunk is sent, to keep the input log
+/// small.
+pub fn update_view_focus(
+    q_camera: Query<&MainCamera>,
+    terrain: Res<Terrain>,
+    streaming: Res<ChunkStreaming>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+    mut sent_column: Local<Option<[u32; 2]>>,
+) {
+    let Ok(camera) = q_camera.get_single() else {
+        return;
+    };
+
+    let x = camera
+        .focus
+        .x
+        .clamp(0., (terrain.world_size_x() - 1) as f32) as u32;
+    let z = camera
+        .focus
+        .z
+        .clamp(0., (terrain.world_size_z() - 1) as f32) as u32;
+    let column = [x / terrain.chunk_size, z / terrain.chunk_size];
+
+    if column == sent_column.unwrap_or_else(|| streaming.focus_column(&terrain)) {
+        return;
+    }
+
+    ev_sim_input.send(SimInputEvent(SimInput::SetViewFocus([x, z])));
+    *sent_column = Some(column);
+}
+
 pub fn setup_camera(
     mut cmd: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut depth_materials: ResMut<Assets<PrepassOutputMaterial>>,
+    streaming: Res<ChunkStreaming>,
 ) {
-    let translation = Vec3::new(0., 64., 0.);
-    let radius = translation.length();
-    let focus = Vec3::new(32., 50., 32.);
+    // start over the chunks that were generated around the view focus
+    let [focus_x, focus_z] = streaming.focus;
+    let origin = Vec3::new(focus_x as f32 - 32., 0., focus_z as f32 - 32.);
+    let translation = origin + Vec3::new(0., 64., 0.);
+    let radius = 64.;
+    let focus = origin + Vec3::new(32., 50., 32.);
 
     let camera = cmd
         .spawn((
             Camera3dBundle {
-                transform: Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
+                transform: Transform::from_translation(translation).looking_at(origin, Vec3::Y),
                 projection: Projection::Perspective(PerspectiveProjection {
                     fov: std::f32::consts::PI / 5.0,
                     ..Default::default()
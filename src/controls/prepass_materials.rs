@@ -1,3 +1,6 @@
+// ShaderType generates unused field checks
+#![allow(dead_code)]
+
 use bevy::{
     asset::{Asset, Assets, Handle},
     ecs::system::{Local, Query, Res, ResMut},
@@ -19,11 +19,14 @@ use crate::{
     Position,
 };
 
-use super::{image_loader_settings, Commodity, SpawnCommodityEvent};
+use super::{image_loader_settings, Commodity, Durability, SpawnCommodityEvent, ToolMaterial};
 
 #[derive(Event)]
 pub struct SpawnAxeEvent {
     pub pos: [u32; 3],
+    pub material: ToolMaterial,
+    /// uses left, a new tool when `None`
+    pub durability: Option<u32>,
 }
 
 pub fn on_spawn_axe(
@@ -46,7 +49,11 @@ pub fn on_spawn_axe(
             .spawn((
                 Name::new("Axe"),
                 Inspectable {
-                    display_name: "Axe".into(),
+                    display_name: format!("{} axe", ev.material.label()),
+                },
+                ev.material,
+                Durability {
+                    value: ev.durability.unwrap_or(ev.material.durability()),
                 },
                 MaterialMeshBundle {
                     mesh: mesh.clone(),
@@ -82,12 +89,14 @@ pub fn on_spawn_axe(
             .id();
 
         ev_spawn_commodity.send(SpawnCommodityEvent {
+            entity: None,
             commodity: Commodity::StoneShaleBoulder,
             position: ev.pos,
             slot_entity: Some(entity),
             slot_idx: Some(SlotIndex::Slot0),
         });
         ev_spawn_commodity.send(SpawnCommodityEvent {
+            entity: None,
             commodity: Commodity::WoodBirchLog,
             position: ev.pos,
             slot_entity: Some(entity),
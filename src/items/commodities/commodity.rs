@@ -13,6 +13,7 @@ use bevy::{
     transform::components::Transform,
     utils::HashMap,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
     colonists::{Faller, InSlot, Item, ItemTag},
@@ -22,10 +23,19 @@ use crate::{
     Position,
 };
 
-#[derive(Component, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Component, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum Commodity {
     WoodBirchLog,
     StoneShaleBoulder,
+    Berries,
+    WoodBirchPlank,
+    StoneShaleCut,
+    StoneAshlarBlock,
+    Dirt,
+    OreCoal,
+    OreCopper,
+    OreGold,
+    OreIron,
 }
 
 pub struct CommodityData {
@@ -42,6 +52,7 @@ pub struct Commodities(pub HashMap<Commodity, CommodityData>);
 
 #[derive(Event)]
 pub struct SpawnCommodityEvent {
+    pub entity: Option<Entity>,
     pub commodity: Commodity,
     pub position: [u32; 3],
     pub slot_entity: Option<Entity>,
@@ -66,8 +77,7 @@ pub fn on_spawn_commodity(
             ..Default::default()
         });
 
-        // let entity = ev.entity_id.unwrap_or_else(|| cmd.spawn_empty().id());
-        let entity = cmd.spawn_empty().id();
+        let entity = ev.entity.unwrap_or_else(|| cmd.spawn_empty().id());
 
         let mut ecmd = cmd.entity(entity);
 
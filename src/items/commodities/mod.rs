@@ -1,7 +1,25 @@
+mod berries;
 mod commodity;
+mod dirt;
+mod ore_coal;
+mod ore_copper;
+mod ore_gold;
+mod ore_iron;
+mod stone_ashlar_block;
 mod stone_shale_boulder;
+mod stone_shale_cut;
 mod wood_birch_log;
+mod wood_birch_plank;
 
+pub use berries::*;
 pub use commodity::*;
+pub use dirt::*;
+pub use ore_coal::*;
+pub use ore_copper::*;
+pub use ore_gold::*;
+pub use ore_iron::*;
+pub use stone_ashlar_block::*;
 pub use stone_shale_boulder::*;
+pub use stone_shale_cut::*;
 pub use wood_birch_log::*;
+pub use wood_birch_plank::*;
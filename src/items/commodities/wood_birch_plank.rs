@@ -0,0 +1,29 @@
+use bevy::{
+    asset::AssetServer,
+    ecs::system::{Res, ResMut},
+    render::color::Color,
+};
+
+use crate::{colonists::ItemTag, items::image_loader_settings};
+
+use super::{Commodities, Commodity, CommodityData};
+
+pub fn setup_commodity_wood_birch_plank(
+    mut commodities: ResMut<Commodities>,
+    asset_server: Res<AssetServer>,
+) {
+    let texture = asset_server.load_with_settings("textures/wood.png", image_loader_settings);
+    let mesh = asset_server.load("log.gltf#Mesh0/Primitive0");
+
+    commodities.0.insert(
+        Commodity::WoodBirchPlank,
+        CommodityData {
+            name: "Birch plank".to_string(),
+            texture,
+            texture_idx: 34,
+            mesh,
+            color: Color::rgb(1.0, 0.9, 0.7),
+            tags: vec![ItemTag::Plank, ItemTag::BasicBuildMaterial],
+        },
+    );
+}
@@ -1,7 +1,13 @@
 mod axe;
 mod commodities;
 mod pickaxe;
+mod recipes;
+mod stockpile;
+mod tools;
 
 pub use axe::*;
 pub use commodities::*;
 pub use pickaxe::*;
+pub use recipes::*;
+pub use stockpile::*;
+pub use tools::*;
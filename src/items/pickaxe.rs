@@ -26,11 +26,14 @@ use crate::{
     Position,
 };
 
-use super::{Commodity, SpawnCommodityEvent};
+use super::{Commodity, Durability, SpawnCommodityEvent, ToolMaterial};
 
 #[derive(Event)]
 pub struct SpawnPickaxeEvent {
     pub pos: [u32; 3],
+    pub material: ToolMaterial,
+    /// uses left, a new tool when `None`
+    pub durability: Option<u32>,
 }
 
 pub fn image_loader_settings(s: &mut ImageLoaderSettings) {
@@ -68,7 +71,11 @@ pub fn on_spawn_pickaxe(
             .spawn((
                 Name::new("Pickaxe"),
                 Inspectable {
-                    display_name: "Pickaxe".into(),
+                    display_name: format!("{} pickaxe", ev.material.label()),
+                },
+                ev.material,
+                Durability {
+                    value: ev.durability.unwrap_or(ev.material.durability()),
                 },
                 MaterialMeshBundle {
                     mesh: mesh.clone(),
@@ -104,12 +111,14 @@ pub fn on_spawn_pickaxe(
             .id();
 
         ev_spawn_commodity.send(SpawnCommodityEvent {
+            entity: None,
             commodity: Commodity::StoneShaleBoulder,
             position: ev.pos,
             slot_entity: Some(entity),
             slot_idx: Some(SlotIndex::Slot0),
         });
         ev_spawn_commodity.send(SpawnCommodityEvent {
+            entity: None,
             commodity: Commodity::WoodBirchLog,
             position: ev.pos,
             slot_entity: Some(entity),
@@ -0,0 +1,289 @@
+use bevy::{
+    ecs::{
+        component::Component,
+        event::{Event, EventReader},
+        system::{Query, Res, ResMut, Resource},
+    },
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    colonists::{test_item_tags, Item, ItemTag},
+    structures::{BlueprintType, Structure},
+};
+
+use super::{Commodity, ToolMaterial};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RecipeType {
+    StoneAxe,
+    StonePickaxe,
+    BirchPlank,
+    CutShale,
+    AshlarBlock,
+    CopperAxe,
+    CopperPickaxe,
+    IronAxe,
+    IronPickaxe,
+}
+
+impl RecipeType {
+    pub const ALL: [RecipeType; 9] = [
+        RecipeType::StoneAxe,
+        RecipeType::StonePickaxe,
+        RecipeType::CopperAxe,
+        RecipeType::CopperPickaxe,
+        RecipeType::IronAxe,
+        RecipeType::IronPickaxe,
+        RecipeType::BirchPlank,
+        RecipeType::CutShale,
+        RecipeType::AshlarBlock,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecipeType::StoneAxe => "Stone axe",
+            RecipeType::StonePickaxe => "Stone pickaxe",
+            RecipeType::BirchPlank => "Birch plank",
+            RecipeType::CutShale => "Cut shale",
+            RecipeType::AshlarBlock => "Ashlar block",
+            RecipeType::CopperAxe => "Copper axe",
+            RecipeType::CopperPickaxe => "Copper pickaxe",
+            RecipeType::IronAxe => "Iron axe",
+            RecipeType::IronPickaxe => "Iron pickaxe",
+        }
+    }
+}
+
+/// One item consumed by a recipe.
+#[derive(Clone)]
+pub enum Ingredient {
+    /// any item with all of the tags
+    Tags(Vec<ItemTag>),
+    Commodity(Commodity),
+}
+
+impl Ingredient {
+    pub fn matches(&self, item: &Item, commodity: Option<&Commodity>) -> bool {
+        match self {
+            Ingredient::Tags(tags) => test_item_tags(&item.tags, tags),
+            Ingredient::Commodity(c) => commodity == Some(c),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum RecipeOutput {
+    Commodity(Commodity),
+    Axe(ToolMaterial),
+    Pickaxe(ToolMaterial),
+}
+
+pub struct Recipe {
+    pub inputs: Vec<Ingredient>,
+    pub output: RecipeOutput,
+    /// seconds of work for an unskilled colonist
+    pub work_time: f32,
+    pub station: BlueprintType,
+}
+
+#[derive(Resource, Default)]
+pub struct Recipes(pub HashMap<RecipeType, Recipe>);
+
+pub fn setup_recipes(mut recipes: ResMut<Recipes>) {
+    recipes.0.insert(
+        RecipeType::StoneAxe,
+        Recipe {
+            inputs: vec![
+                Ingredient::Tags(vec![ItemTag::Stone]),
+                Ingredient::Commodity(Commodity::WoodBirchLog),
+            ],
+            output: RecipeOutput::Axe(ToolMaterial::Stone),
+            work_time: 4.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::StonePickaxe,
+        Recipe {
+            inputs: vec![
+                Ingredient::Tags(vec![ItemTag::Stone]),
+                Ingredient::Commodity(Commodity::WoodBirchLog),
+            ],
+            output: RecipeOutput::Pickaxe(ToolMaterial::Stone),
+            work_time: 4.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::CopperAxe,
+        Recipe {
+            inputs: vec![
+                Ingredient::Commodity(Commodity::OreCopper),
+                Ingredient::Commodity(Commodity::WoodBirchLog),
+            ],
+            output: RecipeOutput::Axe(ToolMaterial::Copper),
+            work_time: 6.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::CopperPickaxe,
+        Recipe {
+            inputs: vec![
+                Ingredient::Commodity(Commodity::OreCopper),
+                Ingredient::Commodity(Commodity::WoodBirchLog),
+            ],
+            output: RecipeOutput::Pickaxe(ToolMaterial::Copper),
+            work_time: 6.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    // iron is smelted with coal, and its ore takes a copper pickaxe
+    recipes.0.insert(
+        RecipeType::IronAxe,
+        Recipe {
+            inputs: vec![
+                Ingredient::Commodity(Commodity::OreIron),
+                Ingredient::Commodity(Commodity::OreCoal),
+                Ingredient::Commodity(Commodity::WoodBirchLog),
+            ],
+            output: RecipeOutput::Axe(ToolMaterial::Iron),
+            work_time: 8.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::IronPickaxe,
+        Recipe {
+            inputs: vec![
+                Ingredient::Commodity(Commodity::OreIron),
+                Ingredient::Commodity(Commodity::OreCoal),
+                Ingredient::Commodity(Commodity::WoodBirchLog),
+            ],
+            output: RecipeOutput::Pickaxe(ToolMaterial::Iron),
+            work_time: 8.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::BirchPlank,
+        Recipe {
+            inputs: vec![Ingredient::Commodity(Commodity::WoodBirchLog)],
+            output: RecipeOutput::Commodity(Commodity::WoodBirchPlank),
+            work_time: 2.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::CutShale,
+        Recipe {
+            inputs: vec![Ingredient::Commodity(Commodity::StoneShaleBoulder)],
+            output: RecipeOutput::Commodity(Commodity::StoneShaleCut),
+            work_time: 3.,
+            station: BlueprintType::Workbench,
+        },
+    );
+    recipes.0.insert(
+        RecipeType::AshlarBlock,
+        Recipe {
+            inputs: vec![Ingredient::Commodity(Commodity::StoneShaleCut)],
+            output: RecipeOutput::Commodity(Commodity::StoneAshlarBlock),
+            work_time: 3.,
+            station: BlueprintType::Workbench,
+        },
+    );
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CraftBill {
+    pub recipe: RecipeType,
+    pub remaining: u32,
+}
+
+/// Queue of recipes a station is asked to craft, worked front to back.
+#[derive(Component, Clone, Default)]
+pub struct CraftBills {
+    pub bills: Vec<CraftBill>,
+}
+
+impl CraftBills {
+    pub fn current(&self) -> Option<&CraftBill> {
+        self.bills.first()
+    }
+
+    pub fn add(&mut self, recipe: RecipeType, count: u32) {
+        match self.bills.last_mut() {
+            Some(last) if last.recipe == recipe => last.remaining += count,
+            _ => self.bills.push(CraftBill {
+                recipe,
+                remaining: count,
+            }),
+        }
+    }
+
+    /// Counts one craft of the recipe against the first bill for it.
+    pub fn complete(&mut self, recipe: RecipeType) {
+        let Some(idx) = self.bills.iter().position(|b| b.recipe == recipe) else {
+            return;
+        };
+
+        self.bills[idx].remaining = self.bills[idx].remaining.saturating_sub(1);
+
+        if self.bills[idx].remaining == 0 {
+            self.bills.remove(idx);
+        }
+    }
+}
+
+/// Queues crafts of a recipe at the built station at `station`.
+#[derive(Event)]
+pub struct AddCraftBillEvent {
+    pub station: [u32; 3],
+    pub recipe: RecipeType,
+    pub count: u32,
+}
+
+#[derive(Event)]
+pub struct ClearCraftBillsEvent {
+    pub station: [u32; 3],
+}
+
+pub fn on_add_craft_bill(
+    recipes: Res<Recipes>,
+    mut ev_add_craft_bill: EventReader<AddCraftBillEvent>,
+    mut q_stations: Query<(&Structure, &mut CraftBills)>,
+) {
+    for ev in ev_add_craft_bill.read() {
+        let Some(recipe) = recipes.0.get(&ev.recipe) else {
+            println!("No recipe {:?}", ev.recipe);
+            continue;
+        };
+
+        let Some((_, mut bills)) = q_stations
+            .iter_mut()
+            .find(|(s, _)| s.position == ev.station && s.blueprint_type == recipe.station)
+        else {
+            println!("No station for {} at {:?}", ev.recipe.label(), ev.station);
+            continue;
+        };
+
+        if ev.count > 0 {
+            bills.add(ev.recipe, ev.count);
+        }
+    }
+}
+
+pub fn on_clear_craft_bills(
+    mut ev_clear_craft_bills: EventReader<ClearCraftBillsEvent>,
+    mut q_stations: Query<(&Structure, &mut CraftBills)>,
+) {
+    for ev in ev_clear_craft_bills.read() {
+        for (structure, mut bills) in q_stations.iter_mut() {
+            if structure.position == ev.station {
+                bills.bills.clear();
+            }
+        }
+    }
+}
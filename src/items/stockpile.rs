@@ -0,0 +1,113 @@
+use bevy::{
+    asset::{AssetServer, Assets},
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::{Event, EventReader},
+        system::{Commands, Res, ResMut},
+    },
+    hierarchy::BuildChildren,
+    math::Vec3,
+    pbr::MaterialMeshBundle,
+    prelude::default,
+    render::{color::Color, prelude::SpatialBundle},
+    transform::components::Transform,
+};
+
+use crate::{colonists::ItemTag, rendering::BasicMaterial, ui::Inspectable, Terrain};
+
+/// Items a stockpile made with the stockpile tool stores on each tile.
+pub const STOCKPILE_TILE_CAPACITY: u32 = 4;
+
+/// A zone that colonists haul loose items into.
+#[derive(Component)]
+pub struct Stockpile {
+    pub tiles: Vec<[u32; 3]>,
+    /// items with any of these tags are stored here, or every item when empty
+    pub tags: Vec<ItemTag>,
+    /// items stored on each tile
+    pub capacity: u32,
+}
+
+impl Stockpile {
+    pub fn accepts(&self, item_tags: &[ItemTag]) -> bool {
+        self.tags.is_empty() || self.tags.iter().any(|t| item_tags.contains(t))
+    }
+
+    pub fn contains(&self, pos: [u32; 3]) -> bool {
+        self.tiles.contains(&pos)
+    }
+}
+
+/// An item stored in a stockpile, which is not hauled again.
+#[derive(Component)]
+pub struct InStockpile(pub Entity);
+
+#[derive(Event)]
+pub struct SpawnStockpileEvent {
+    pub entity: Option<Entity>,
+    pub tiles: Vec<[u32; 3]>,
+    pub tags: Vec<ItemTag>,
+    pub capacity: u32,
+}
+
+/// The blocks between `min` and `max` an item can be stored on.
+pub fn stockpile_tiles(terrain: &Terrain, min: [u32; 3], max: [u32; 3]) -> Vec<[u32; 3]> {
+    let mut tiles = vec![];
+
+    for y in min[1].max(1)..=max[1] {
+        for z in min[2]..=max[2] {
+            for x in min[0]..=max[0] {
+                if terrain.get_block(x, y, z).is_empty()
+                    && terrain.get_block(x, y - 1, z).is_walkable()
+                {
+                    tiles.push([x, y, z]);
+                }
+            }
+        }
+    }
+
+    tiles
+}
+
+pub fn on_spawn_stockpile(
+    mut cmd: Commands,
+    mut ev_spawn_stockpile: EventReader<SpawnStockpileEvent>,
+    mut materials: ResMut<Assets<BasicMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    for ev in ev_spawn_stockpile.read() {
+        let entity = ev.entity.unwrap_or_else(|| cmd.spawn_empty().id());
+        let mesh = asset_server.load("tile_wire.gltf#Mesh0/Primitive0");
+        let material = materials.add(BasicMaterial::from_color(Color::YELLOW));
+
+        cmd.entity(entity)
+            .insert((
+                Name::new("Stockpile"),
+                Inspectable {
+                    display_name: "Stockpile".into(),
+                },
+                Stockpile {
+                    tiles: ev.tiles.clone(),
+                    tags: ev.tags.clone(),
+                    capacity: ev.capacity,
+                },
+                SpatialBundle::default(),
+            ))
+            .with_children(|parent| {
+                for [x, y, z] in ev.tiles.iter() {
+                    parent.spawn(MaterialMeshBundle {
+                        mesh: mesh.clone(),
+                        material: material.clone(),
+                        transform: Transform::from_translation(Vec3::new(
+                            *x as f32 + 0.5,
+                            *y as f32,
+                            *z as f32 + 0.5,
+                        )),
+                        ..default()
+                    });
+                }
+            });
+    }
+}
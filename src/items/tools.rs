@@ -0,0 +1,107 @@
+use bevy::ecs::{component::Component, entity::Entity, event::EventWriter, system::Query};
+use serde::{Deserialize, Serialize};
+
+use crate::colonists::{test_item_tags, DestroyItemEvent, Inventory, Item, ItemTag};
+
+/// What a tool is made of, better materials work faster and last longer.
+#[derive(
+    Component, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum ToolMaterial {
+    #[default]
+    Stone,
+    Copper,
+    Iron,
+}
+
+impl ToolMaterial {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ToolMaterial::Stone => "Stone",
+            ToolMaterial::Copper => "Copper",
+            ToolMaterial::Iron => "Iron",
+        }
+    }
+
+    /// Multiplier for the progress of tasks using the tool.
+    pub fn work_speed(&self) -> f32 {
+        match self {
+            ToolMaterial::Stone => 1.,
+            ToolMaterial::Copper => 1.25,
+            ToolMaterial::Iron => 1.5,
+        }
+    }
+
+    /// Number of tasks a new tool lasts.
+    pub fn durability(&self) -> u32 {
+        match self {
+            ToolMaterial::Stone => 30,
+            ToolMaterial::Copper => 60,
+            ToolMaterial::Iron => 120,
+        }
+    }
+}
+
+/// Uses left before the tool breaks.
+#[derive(Component, Clone, Copy)]
+pub struct Durability {
+    pub value: u32,
+}
+
+/// The first tool in the inventory with all of the tags.
+pub fn find_tool(
+    inventory: &Inventory,
+    tags: &[ItemTag],
+    q_tools: &Query<(&Item, &ToolMaterial, &mut Durability)>,
+) -> Option<Entity> {
+    inventory.items.iter().copied().find(|e| {
+        q_tools
+            .get(*e)
+            .is_ok_and(|(item, _, _)| test_item_tags(&item.tags, tags))
+    })
+}
+
+/// Material of the first tool in the inventory with all of the tags.
+pub fn tool_material(
+    inventory: &Inventory,
+    tags: &[ItemTag],
+    q_tools: &Query<(&Item, &ToolMaterial, &mut Durability)>,
+) -> Option<ToolMaterial> {
+    find_tool(inventory, tags, q_tools)
+        .and_then(|e| q_tools.get(e).ok())
+        .map(|(_, material, _)| *material)
+}
+
+/// Work speed of the actor's tool, 1 without one.
+pub fn tool_work_speed(
+    inventory: &Inventory,
+    tags: &[ItemTag],
+    q_tools: &Query<(&Item, &ToolMaterial, &mut Durability)>,
+) -> f32 {
+    tool_material(inventory, tags, q_tools).map_or(1., |m| m.work_speed())
+}
+
+/// Uses up one durability of the actor's tool, breaking it once none is
+/// left.
+pub fn wear_tool(
+    inventory: &mut Inventory,
+    tags: &[ItemTag],
+    q_tools: &mut Query<(&Item, &ToolMaterial, &mut Durability)>,
+    ev_destroy_item: &mut EventWriter<DestroyItemEvent>,
+) {
+    let Some(tool) = find_tool(inventory, tags, q_tools) else {
+        return;
+    };
+
+    let Ok((_, _, mut durability)) = q_tools.get_mut(tool) else {
+        return;
+    };
+
+    durability.value = durability.value.saturating_sub(1);
+
+    if durability.value == 0 {
+        println!("Tool broke! {}", tool.index());
+        inventory.items.remove(&tool);
+        ev_destroy_item.send(DestroyItemEvent { entity: tool });
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    env, fs,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::Chunk;
+
+use super::{encode_chunk_runs, read_file_with_header, write_file_with_header, BlockRun};
+
+pub const PAGE_MAGIC: [u8; 4] = *b"BRPG";
+pub const PAGE_VERSION: u32 = 2;
+
+static NEXT_PAGER_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Blocks of chunks that are far from the colony, kept on disk until they
+/// are needed again. Every terrain pages to its own directory, which is
+/// removed along with the terrain.
+pub struct ChunkPager {
+    dir: PathBuf,
+}
+
+impl Default for ChunkPager {
+    fn default() -> Self {
+        let id = NEXT_PAGER_ID.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            dir: env::temp_dir().join(format!("boris-pages-{}-{}", process::id(), id)),
+        }
+    }
+}
+
+impl ChunkPager {
+    pub fn write(&self, chunk: &Chunk) -> Result<(), String> {
+        let runs = encode_chunk_runs(chunk);
+        let body = bincode::serialize(&runs).map_err(|e| e.to_string())?;
+
+        write_file_with_header(&self.path(chunk.chunk_idx), PAGE_MAGIC, PAGE_VERSION, &body)
+    }
+
+    pub fn read(&self, chunk_idx: u32) -> Result<Vec<BlockRun>, String> {
+        let (version, body) = read_file_with_header(&self.path(chunk_idx), PAGE_MAGIC)?;
+
+        match version {
+            PAGE_VERSION => bincode::deserialize(&body).map_err(|e| e.to_string()),
+            v => Err(format!("unsupported page version {}", v)),
+        }
+    }
+
+    fn path(&self, chunk_idx: u32) -> PathBuf {
+        self.dir.join(format!("{}.page", chunk_idx))
+    }
+}
+
+impl Drop for ChunkPager {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Block, BlockType, Chunk};
+
+/// A run of identical, consecutive blocks in a chunk. Partition ids are not
+/// stored, the navigation graph is rebuilt from the blocks on load.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BlockRun {
+    pub count: u32,
+    pub block: BlockType,
+    pub light: u8,
+    pub sunlight: u8,
+    pub flag_mine: bool,
+    pub flag_chop: bool,
+    pub fluid_level: u8,
+}
+
+impl BlockRun {
+    fn from_block(block: &Block) -> Self {
+        Self {
+            count: 1,
+            block: block.block,
+            light: block.light,
+            sunlight: block.sunlight,
+            flag_mine: block.flag_mine,
+            flag_chop: block.flag_chop,
+            fluid_level: block.fluid_level,
+        }
+    }
+
+    fn matches(&self, block: &Block) -> bool {
+        self.block == block.block
+            && self.light == block.light
+            && self.sunlight == block.sunlight
+            && self.flag_mine == block.flag_mine
+            && self.flag_chop == block.flag_chop
+            && self.fluid_level == block.fluid_level
+    }
+}
+
+pub fn encode_chunk_runs(chunk: &Chunk) -> Vec<BlockRun> {
+    let mut runs: Vec<BlockRun> = vec![];
+
+    for block in chunk.blocks.iter() {
+        if let Some(run) = runs.last_mut() {
+            if run.matches(block) {
+                run.count += 1;
+                continue;
+            }
+        }
+
+        runs.push(BlockRun::from_block(block));
+    }
+
+    runs
+}
+
+pub fn decode_chunk_runs(chunk: &mut Chunk, runs: &[BlockRun]) -> Result<(), String> {
+    let total = runs.iter().map(|r| r.count as usize).sum::<usize>();
+
+    if total != chunk.blocks.len() {
+        return Err(format!(
+            "chunk {} has {} saved blocks, expected {}",
+            chunk.chunk_idx,
+            total,
+            chunk.blocks.len()
+        ));
+    }
+
+    let mut block_idx = 0;
+
+    for run in runs.iter() {
+        for _ in 0..run.count {
+            chunk.blocks[block_idx] = Block {
+                block: run.block,
+                light: run.light,
+                sunlight: run.sunlight,
+                partition_id: None,
+                flag_mine: run.flag_mine,
+                flag_chop: run.flag_chop,
+                fluid_level: run.fluid_level,
+            };
+            block_idx += 1;
+        }
+    }
+
+    Ok(())
+}
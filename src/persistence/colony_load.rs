@@ -0,0 +1,501 @@
+use std::path::PathBuf;
+
+use bevy::{
+    core::Name,
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader, EventWriter},
+        query::{Or, With},
+        system::{Commands, Query, ResMut, Resource},
+    },
+    hierarchy::DespawnRecursiveExt,
+    utils::HashMap,
+};
+
+use crate::{
+    colonists::{
+        ActorRef, Colonist, Corpse, Creature, Drafted, Fatigue, Health, Item, Job, JobBuild,
+        JobChop, JobLocation, JobMine, JobPlaceBlock, JobSupply, Mood, NavigationGraph, Needs,
+        PathCache, PathQueue, Skills, SpawnColonistEvent, SpawnCreatureEvent, WorkPreferences,
+    },
+    common::Rand,
+    items::{
+        CraftBills, SetSlotEvent, SpawnAxeEvent, SpawnCommodityEvent, SpawnPickaxeEvent,
+        SpawnStockpileEvent, Stockpile,
+    },
+    rendering::SlotIndex,
+    simulation::{InputLog, SimClock},
+    structures::{BuildStructureEvent, SpawnStructureEvent, Structure, StructureMode},
+    FallingBlock, Lamps, Terrain, TimeOfDay, Tree,
+};
+
+use super::{
+    decode_chunk_runs, read_save_file, ColonistSave, ColonySave, ItemKindSave, JobDetailSave,
+    JobSave, SaveId, StructureSave,
+};
+
+type ColonyEntityFilter = Or<(
+    With<Colonist>,
+    With<Corpse>,
+    With<Creature>,
+    With<FallingBlock>,
+    With<Item>,
+    With<Structure>,
+    With<Tree>,
+    With<Job>,
+    With<ActorRef>,
+    With<Stockpile>,
+)>;
+
+#[derive(Event)]
+pub struct LoadColonyEvent {
+    pub path: PathBuf,
+}
+
+enum ColonyLoadStage {
+    /// waiting for the spawn events to create the saved entities
+    Spawning,
+    /// waiting for structures to be checked against the loaded terrain
+    Building,
+}
+
+/// Relations between loaded entities that can only be restored once the
+/// entities have been spawned by their respective `on_spawn_*` systems.
+#[derive(Resource)]
+pub struct PendingColonyLoad {
+    stage: ColonyLoadStage,
+    entities: HashMap<SaveId, Entity>,
+    colonists: Vec<(Entity, ColonistSave)>,
+    structures: Vec<(Entity, StructureSave)>,
+    items: Vec<Entity>,
+    slots: Vec<(Entity, SaveId, SlotIndex)>,
+    jobs: Vec<JobSave>,
+}
+
+pub fn on_load_colony(
+    mut cmd: Commands,
+    mut ev_load_colony: EventReader<LoadColonyEvent>,
+    (mut ev_spawn_colonist, mut ev_spawn_creature): (
+        EventWriter<SpawnColonistEvent>,
+        EventWriter<SpawnCreatureEvent>,
+    ),
+    mut ev_spawn_commodity: EventWriter<SpawnCommodityEvent>,
+    mut ev_spawn_axe: EventWriter<SpawnAxeEvent>,
+    mut ev_spawn_pickaxe: EventWriter<SpawnPickaxeEvent>,
+    mut ev_spawn_structure: EventWriter<SpawnStructureEvent>,
+    mut ev_spawn_stockpile: EventWriter<SpawnStockpileEvent>,
+    mut terrain: ResMut<Terrain>,
+    (mut graph, mut path_cache, mut path_queue): (
+        ResMut<NavigationGraph>,
+        ResMut<PathCache>,
+        ResMut<PathQueue>,
+    ),
+    mut lamps: ResMut<Lamps>,
+    mut clock: ResMut<SimClock>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut log: ResMut<InputLog>,
+    mut rand: ResMut<Rand>,
+    q_colony: Query<Entity, ColonyEntityFilter>,
+) {
+    let Some(ev) = ev_load_colony.read().last() else {
+        return;
+    };
+
+    let save = match read_save_file(&ev.path) {
+        Ok(save) => save,
+        Err(e) => {
+            println!("Could not load colony from {}: {}", ev.path.display(), e);
+            return;
+        }
+    };
+
+    // the rest of the world is generated from the same settings, so the size
+    // must match
+    if save.terrain.chunk_count_x != terrain.chunk_count_x
+        || save.terrain.chunk_count_y != terrain.chunk_count_y
+        || save.terrain.chunk_count_z != terrain.chunk_count_z
+        || save.terrain.chunk_size != terrain.chunk_size
+    {
+        println!("Could not load colony, saved world size does not match");
+        return;
+    }
+
+    match load_terrain(&save) {
+        Ok(loaded) => *terrain = loaded,
+        Err(e) => {
+            println!("Could not load colony terrain: {}", e);
+            return;
+        }
+    }
+
+    for entity in q_colony.iter() {
+        cmd.entity(entity).despawn_recursive();
+    }
+
+    *graph = NavigationGraph::default();
+    *path_cache = PathCache::default();
+    *path_queue = PathQueue::default();
+    lamps.0.clear();
+
+    // the input log restarts from the loaded colony
+    clock.tick = 0;
+    time_of_day.elapsed = save.time_of_day;
+    *rand = Rand::seed(log.world.seed);
+    log.loaded_from = Some(ev.path.clone());
+    log.entries.clear();
+
+    let mut pending = PendingColonyLoad {
+        stage: ColonyLoadStage::Spawning,
+        entities: HashMap::new(),
+        colonists: vec![],
+        structures: vec![],
+        items: vec![],
+        slots: vec![],
+        jobs: vec![],
+    };
+
+    let ColonySave {
+        trees,
+        colonists,
+        creatures,
+        items,
+        structures,
+        jobs,
+        stockpiles,
+        ..
+    } = save;
+
+    for tree in trees {
+        let entity = cmd.spawn_empty().id();
+
+        for [x, y, z] in tree.trunk.iter().chain(tree.canopy.iter()) {
+            let [chunk_idx, block_idx] = terrain.get_block_indexes(*x, *y, *z);
+            terrain.add_tree(chunk_idx, block_idx, entity);
+        }
+
+        cmd.entity(entity).insert((
+            Name::new("Tree"),
+            Tree {
+                trunk: tree.trunk,
+                canopy: tree.canopy,
+            },
+        ));
+        pending.entities.insert(tree.id, entity);
+    }
+
+    for colonist in colonists {
+        let entity = cmd.spawn_empty().id();
+
+        ev_spawn_colonist.send(SpawnColonistEvent {
+            entity: Some(entity),
+            pos: colonist.position,
+        });
+        pending.colonists.push((entity, colonist));
+    }
+
+    for creature in creatures {
+        ev_spawn_creature.send(SpawnCreatureEvent {
+            entity: None,
+            pos: creature.position,
+            creature_type: creature.creature_type,
+            owner: creature.owner,
+            health: Some(creature.health),
+        });
+    }
+
+    for structure in structures {
+        let entity = cmd.spawn_empty().id();
+
+        // kept when the station is built again
+        if !structure.bills.is_empty() {
+            cmd.entity(entity).insert(CraftBills {
+                bills: structure.bills.clone(),
+            });
+        }
+
+        ev_spawn_structure.send(SpawnStructureEvent {
+            pos: structure.position,
+            entity,
+            blueprint_type: structure.blueprint_type,
+            rotation: structure.rotation,
+            is_flipped: structure.is_flipped,
+            mode: StructureMode::Placed,
+        });
+        pending.entities.insert(structure.id, entity);
+        pending.structures.push((entity, structure));
+    }
+
+    for item in items {
+        match item.kind {
+            ItemKindSave::Commodity(commodity) => {
+                let entity = cmd.spawn_empty().id();
+
+                ev_spawn_commodity.send(SpawnCommodityEvent {
+                    entity: Some(entity),
+                    commodity,
+                    position: item.position,
+                    slot_entity: None,
+                    slot_idx: None,
+                });
+                pending.entities.insert(item.id, entity);
+                pending.items.push(entity);
+
+                if let Some((holder, slot_idx)) = item.slot {
+                    pending.slots.push((entity, holder, slot_idx));
+                }
+            }
+            ItemKindSave::Axe {
+                material,
+                durability,
+            } => {
+                ev_spawn_axe.send(SpawnAxeEvent {
+                    pos: item.position,
+                    material,
+                    durability: Some(durability),
+                });
+            }
+            ItemKindSave::Pickaxe {
+                material,
+                durability,
+            } => {
+                ev_spawn_pickaxe.send(SpawnPickaxeEvent {
+                    pos: item.position,
+                    material,
+                    durability: Some(durability),
+                });
+            }
+        }
+    }
+
+    for stockpile in stockpiles {
+        ev_spawn_stockpile.send(SpawnStockpileEvent {
+            entity: None,
+            tiles: stockpile.tiles,
+            tags: stockpile.tags,
+            capacity: stockpile.capacity,
+        });
+    }
+
+    pending.jobs = jobs;
+
+    cmd.insert_resource(pending);
+
+    println!("Colony loaded from {}", ev.path.display());
+}
+
+fn load_terrain(save: &ColonySave) -> Result<Terrain, String> {
+    let mut terrain = Terrain::new(
+        save.terrain.chunk_count_x,
+        save.terrain.chunk_count_y,
+        save.terrain.chunk_count_z,
+        save.terrain.chunk_size,
+    );
+
+    if save.terrain.chunks.len() != terrain.chunk_count as usize {
+        return Err(format!(
+            "{} chunks saved, expected {}",
+            save.terrain.chunks.len(),
+            terrain.chunk_count
+        ));
+    }
+
+    for chunk_idx in 0..terrain.chunk_count {
+        let runs = &save.terrain.chunks[chunk_idx as usize];
+
+        // the chunk had not been generated when the colony was saved
+        if runs.is_empty() {
+            continue;
+        }
+
+        terrain.init_chunk(chunk_idx);
+
+        let Some(chunk) = terrain.get_chunk_mut(chunk_idx) else {
+            return Err(format!("missing chunk {}", chunk_idx));
+        };
+
+        decode_chunk_runs(chunk, runs)?;
+        terrain.set_chunk_dirty(chunk_idx);
+    }
+
+    terrain.fluid_queue = save.terrain.fluid_queue.clone();
+    terrain.support_queue = save.terrain.support_queue.clone();
+
+    // their source blocks were cleared when they started falling
+    for falling in save.terrain.falling_blocks.iter() {
+        let [x, mut y, z] = falling.target;
+
+        while y < terrain.world_size_y() - 1 && terrain.get_block(x, y, z).is_supportive() {
+            y += 1;
+        }
+
+        terrain.set_block_type(x, y, z, falling.block_type);
+    }
+
+    Ok(terrain)
+}
+
+pub fn apply_colony_load(
+    mut cmd: Commands,
+    pending: Option<ResMut<PendingColonyLoad>>,
+    mut ev_set_slot: EventWriter<SetSlotEvent>,
+    mut ev_build_structure: EventWriter<BuildStructureEvent>,
+    q_structures: Query<&Structure>,
+    mut q_colonists: Query<(
+        &mut Colonist,
+        &mut Fatigue,
+        &mut Needs,
+        &mut Mood,
+        &mut WorkPreferences,
+        &mut Skills,
+        &mut Health,
+    )>,
+    q_items: Query<(), With<Item>>,
+) {
+    let Some(mut pending) = pending else {
+        return;
+    };
+
+    match pending.stage {
+        ColonyLoadStage::Spawning => {
+            let is_spawned = pending
+                .structures
+                .iter()
+                .all(|(e, _)| q_structures.contains(*e))
+                && pending
+                    .colonists
+                    .iter()
+                    .all(|(e, _)| q_colonists.contains(*e))
+                && pending.items.iter().all(|e| q_items.contains(*e));
+
+            if !is_spawned {
+                return;
+            }
+
+            for (entity, colonist) in pending.colonists.iter() {
+                if let Ok((
+                    mut c,
+                    mut fatigue,
+                    mut needs,
+                    mut mood,
+                    mut work,
+                    mut skills,
+                    mut health,
+                )) = q_colonists.get_mut(*entity)
+                {
+                    c.id = colonist.id;
+                    fatigue.value = colonist.fatigue;
+                    *needs = colonist.needs;
+                    mood.value = colonist.mood;
+                    *work = colonist.work.clone();
+                    *skills = colonist.skills;
+                    health.value = colonist.health;
+
+                    if colonist.is_drafted {
+                        cmd.entity(*entity).insert(Drafted);
+                    }
+                }
+            }
+
+            for (content, holder, slot_idx) in pending.slots.iter() {
+                let Some(target) = pending.entities.get(holder) else {
+                    println!("Cannot load slot, holder missing");
+                    continue;
+                };
+
+                ev_set_slot.send(SetSlotEvent {
+                    target_slot: *slot_idx,
+                    target: *target,
+                    content: *content,
+                });
+            }
+
+            for job in pending.jobs.iter() {
+                spawn_saved_job(&mut cmd, job, &pending.entities);
+            }
+
+            pending.stage = ColonyLoadStage::Building;
+        }
+        ColonyLoadStage::Building => {
+            let is_checked = pending
+                .structures
+                .iter()
+                .all(|(e, _)| q_structures.get(*e).map(|s| !s.is_dirty).unwrap_or(true));
+
+            if !is_checked {
+                return;
+            }
+
+            for (entity, saved) in pending.structures.iter() {
+                if saved.is_built && q_structures.contains(*entity) {
+                    ev_build_structure.send(BuildStructureEvent { entity: *entity });
+                }
+            }
+
+            cmd.remove_resource::<PendingColonyLoad>();
+        }
+    }
+}
+
+fn spawn_saved_job(cmd: &mut Commands, job: &JobSave, entities: &HashMap<SaveId, Entity>) {
+    let resolve = |id: &SaveId| {
+        let entity = entities.get(id).copied();
+
+        if entity.is_none() {
+            println!("Cannot load job, referenced entity missing");
+        }
+
+        entity
+    };
+
+    let mut ecmd = cmd.spawn((
+        Job {
+            job_type: job.job_type,
+            assignee: None,
+            priority: job.priority,
+        },
+        JobLocation {
+            targets: job.targets.clone(),
+            primary_target: job.primary_target,
+            source: job.source,
+            last_accessibility_check: 0.,
+        },
+    ));
+
+    match &job.detail {
+        JobDetailSave::Mine => {
+            ecmd.insert(JobMine);
+        }
+        JobDetailSave::PlaceBlock => {
+            ecmd.insert(JobPlaceBlock);
+        }
+        JobDetailSave::Chop { tree } => {
+            let Some(tree) = resolve(tree) else {
+                ecmd.despawn();
+                return;
+            };
+            ecmd.insert(JobChop { tree });
+        }
+        JobDetailSave::Build { structure } => {
+            let Some(structure) = resolve(structure) else {
+                ecmd.despawn();
+                return;
+            };
+            ecmd.insert(JobBuild { structure });
+        }
+        JobDetailSave::Supply {
+            flags,
+            slot_target_idx,
+            target,
+        } => {
+            let Some(target) = resolve(target) else {
+                ecmd.despawn();
+                return;
+            };
+            ecmd.insert(JobSupply {
+                flags: flags.clone(),
+                slot_target_idx: *slot_target_idx,
+                target,
+            });
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::path::PathBuf;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        event::{Event, EventReader},
+        query::{Has, Without},
+        system::{Query, Res},
+    },
+    transform::components::Transform,
+    utils::HashMap,
+};
+
+use crate::{
+    colonists::{
+        BlockMove, Colonist, Creature, Drafted, Fatigue, Health, InSlot, IsJobCancelled,
+        IsJobCompleted, Item, ItemTag, Job, JobBuild, JobChop, JobLocation, JobSupply, JobType,
+        Mood, Needs, Skills, WorkPreferences,
+    },
+    items::{Commodity, CraftBills, Durability, Stockpile, ToolMaterial},
+    structures::{Structure, StructureMode},
+    ChunkState, FallingBlock, Position, Terrain, TimeOfDay, Tree,
+};
+
+use super::{
+    encode_chunk_runs, write_save_file, BlockRun, ColonistSave, ColonySave, CreatureSave,
+    FallingBlockSave, ItemKindSave, ItemSave, JobDetailSave, JobSave, SaveId, StockpileSave,
+    StructureSave, TerrainSave, TreeSave,
+};
+
+#[derive(Event)]
+pub struct SaveColonyEvent {
+    pub path: PathBuf,
+}
+
+pub fn on_save_colony(
+    mut ev_save_colony: EventReader<SaveColonyEvent>,
+    terrain: Res<Terrain>,
+    time_of_day: Res<TimeOfDay>,
+    q_colonists: Query<(
+        &Colonist,
+        &Position,
+        &Fatigue,
+        &Needs,
+        &Mood,
+        &WorkPreferences,
+        &Skills,
+        &Health,
+        Has<Drafted>,
+    )>,
+    q_creatures: Query<(&Creature, &Position, &Health)>,
+    q_trees: Query<(Entity, &Tree)>,
+    q_structures: Query<(Entity, &Structure, Option<&CraftBills>)>,
+    q_items: Query<(
+        Entity,
+        &Item,
+        &Position,
+        Option<&Commodity>,
+        Option<&InSlot>,
+        Option<(&ToolMaterial, &Durability)>,
+    )>,
+    q_jobs: Query<
+        (
+            &Job,
+            &JobLocation,
+            Option<&JobChop>,
+            Option<&JobBuild>,
+            Option<&JobSupply>,
+        ),
+        (Without<IsJobCancelled>, Without<IsJobCompleted>),
+    >,
+    q_stockpiles: Query<&Stockpile>,
+    q_falling: Query<(&FallingBlock, &Transform, Option<&BlockMove>)>,
+) {
+    for ev in ev_save_colony.read() {
+        let mut ids: HashMap<Entity, SaveId> = HashMap::new();
+
+        let chunks = match save_chunks(&terrain) {
+            Ok(chunks) => chunks,
+            Err(e) => {
+                println!("Could not save colony terrain: {}", e);
+                continue;
+            }
+        };
+
+        let terrain_save = TerrainSave {
+            chunk_count_x: terrain.chunk_count_x,
+            chunk_count_y: terrain.chunk_count_y,
+            chunk_count_z: terrain.chunk_count_z,
+            chunk_size: terrain.chunk_size,
+            chunks,
+            fluid_queue: terrain.fluid_queue.clone(),
+            support_queue: terrain.support_queue.clone(),
+            falling_blocks: q_falling
+                .iter()
+                .map(|(falling, transform, block_move)| FallingBlockSave {
+                    block_type: falling.block_type,
+                    target: match block_move {
+                        Some(block_move) => block_move.target.map(|v| v as u32),
+                        None => transform.translation.to_array().map(|v| v as u32),
+                    },
+                })
+                .collect(),
+        };
+
+        let trees = q_trees
+            .iter()
+            .map(|(entity, tree)| TreeSave {
+                id: assign_save_id(&mut ids, entity),
+                trunk: tree.trunk.clone(),
+                canopy: tree.canopy.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        // structures still being positioned by the player are not part of the colony
+        let structures = q_structures
+            .iter()
+            .filter(|(_, s, _)| !matches!(s.mode, StructureMode::Placing))
+            .map(|(entity, structure, bills)| StructureSave {
+                id: assign_save_id(&mut ids, entity),
+                blueprint_type: structure.blueprint_type,
+                position: structure.position,
+                rotation: structure.rotation,
+                is_flipped: structure.is_flipped,
+                is_built: structure.is_built(),
+                bills: bills.map(|b| b.bills.clone()).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+
+        let colonists = q_colonists
+            .iter()
+            .map(
+                |(colonist, position, fatigue, needs, mood, work, skills, health, is_drafted)| {
+                    ColonistSave {
+                        position: position.as_array(),
+                        fatigue: fatigue.value,
+                        id: colonist.id,
+                        needs: *needs,
+                        mood: mood.value,
+                        work: work.clone(),
+                        skills: *skills,
+                        health: health.value,
+                        is_drafted,
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let creatures = q_creatures
+            .iter()
+            .map(|(creature, position, health)| CreatureSave {
+                position: position.as_array(),
+                creature_type: creature.creature_type,
+                owner: creature.owner,
+                health: health.value,
+            })
+            .collect::<Vec<_>>();
+
+        let mut items = vec![];
+
+        for (entity, item, position, commodity, in_slot, tool) in q_items.iter() {
+            let (material, durability) = tool
+                .map(|(m, d)| (*m, d.value))
+                .unwrap_or((ToolMaterial::Stone, ToolMaterial::Stone.durability()));
+
+            let kind = if let Some(commodity) = commodity {
+                ItemKindSave::Commodity(*commodity)
+            } else if item.tags.contains(&ItemTag::Axe) {
+                ItemKindSave::Axe {
+                    material,
+                    durability,
+                }
+            } else if item.tags.contains(&ItemTag::Pickaxe) {
+                ItemKindSave::Pickaxe {
+                    material,
+                    durability,
+                }
+            } else {
+                println!("Cannot save item, unknown kind {}", entity.index());
+                continue;
+            };
+
+            // tool parts are rebuilt when the tool is spawned, and items
+            // held by colonists are saved where they stand.
+            let slot = match in_slot {
+                Some(in_slot) => {
+                    if q_items.contains(in_slot.holder) {
+                        continue;
+                    }
+
+                    let Some(holder_id) = ids.get(&in_slot.holder) else {
+                        println!("Cannot save item slot, holder was not saved");
+                        continue;
+                    };
+
+                    Some((*holder_id, in_slot.slot_idx))
+                }
+                None => None,
+            };
+
+            items.push(ItemSave {
+                id: assign_save_id(&mut ids, entity),
+                kind,
+                position: position.as_array(),
+                slot,
+            });
+        }
+
+        let mut jobs = vec![];
+
+        for (job, location, chop, build, supply) in q_jobs.iter() {
+            let detail = match job.job_type {
+                // haul jobs are spawned again for any loose items, and craft
+                // jobs for the stations' bills
+                JobType::Haul | JobType::Craft => continue,
+                JobType::Mine => JobDetailSave::Mine,
+                JobType::PlaceBlock(_) => JobDetailSave::PlaceBlock,
+                JobType::Chop => {
+                    let Some(tree) = chop.and_then(|c| ids.get(&c.tree)) else {
+                        println!("Cannot save chop job, tree missing");
+                        continue;
+                    };
+                    JobDetailSave::Chop { tree: *tree }
+                }
+                JobType::Build => {
+                    let Some(structure) = build.and_then(|b| ids.get(&b.structure)) else {
+                        println!("Cannot save build job, structure missing");
+                        continue;
+                    };
+                    JobDetailSave::Build {
+                        structure: *structure,
+                    }
+                }
+                JobType::Supply => {
+                    let Some(supply) = supply else {
+                        println!("Cannot save supply job, missing JobSupply");
+                        continue;
+                    };
+                    let Some(target) = ids.get(&supply.target) else {
+                        println!("Cannot save supply job, target missing");
+                        continue;
+                    };
+                    JobDetailSave::Supply {
+                        flags: supply.flags.clone(),
+                        slot_target_idx: supply.slot_target_idx,
+                        target: *target,
+                    }
+                }
+            };
+
+            jobs.push(JobSave {
+                job_type: job.job_type,
+                priority: job.priority,
+                detail,
+                targets: location.targets.clone(),
+                primary_target: location.primary_target,
+                source: location.source,
+            });
+        }
+
+        let stockpiles = q_stockpiles
+            .iter()
+            .map(|stockpile| StockpileSave {
+                tiles: stockpile.tiles.clone(),
+                tags: stockpile.tags.clone(),
+                capacity: stockpile.capacity,
+            })
+            .collect::<Vec<_>>();
+
+        let save = ColonySave {
+            terrain: terrain_save,
+            trees,
+            colonists,
+            creatures,
+            items,
+            structures,
+            jobs,
+            stockpiles,
+            time_of_day: time_of_day.elapsed,
+        };
+
+        match write_save_file(&ev.path, &save) {
+            Ok(()) => println!("Colony saved to {}", ev.path.display()),
+            Err(e) => println!("Could not save colony to {}: {}", ev.path.display(), e),
+        }
+    }
+}
+
+/// Chunks that have not been generated yet are saved without any runs.
+fn save_chunks(terrain: &Terrain) -> Result<Vec<Vec<BlockRun>>, String> {
+    terrain
+        .chunks
+        .iter()
+        .enumerate()
+        .map(|(chunk_idx, chunk)| match chunk.state {
+            ChunkState::Resident => Ok(encode_chunk_runs(chunk)),
+            ChunkState::PagedOut => terrain.pager.read(chunk_idx as u32),
+            ChunkState::Ungenerated => Ok(vec![]),
+        })
+        .collect()
+}
+
+fn assign_save_id(ids: &mut HashMap<Entity, SaveId>, entity: Entity) -> SaveId {
+    let id = ids.len() as SaveId;
+    ids.insert(entity, id);
+    id
+}
@@ -0,0 +1,13 @@
+mod chunk_pages;
+mod chunk_runs;
+mod colony_load;
+mod colony_save;
+mod replay_file;
+mod save_file;
+
+pub use chunk_pages::*;
+pub use chunk_runs::*;
+pub use colony_load::*;
+pub use colony_save::*;
+pub use replay_file::*;
+pub use save_file::*;
@@ -0,0 +1,46 @@
+use std::path::{Path, PathBuf};
+
+use bevy::ecs::{
+    event::{Event, EventReader},
+    system::Res,
+};
+
+use crate::simulation::InputLog;
+
+use super::{read_file_with_header, write_file_with_header};
+
+pub const REPLAY_MAGIC: [u8; 4] = *b"BRRP";
+pub const REPLAY_VERSION: u32 = 3;
+pub const DEFAULT_REPLAY_PATH: &str = "saves/colony.replay";
+
+#[derive(Event)]
+pub struct SaveReplayEvent {
+    pub path: PathBuf,
+}
+
+pub fn write_replay_file(path: &Path, log: &InputLog) -> Result<(), String> {
+    let body = bincode::serialize(log).map_err(|e| e.to_string())?;
+
+    write_file_with_header(path, REPLAY_MAGIC, REPLAY_VERSION, &body)
+}
+
+pub fn read_replay_file(path: &Path) -> Result<InputLog, String> {
+    let (version, body) = read_file_with_header(path, REPLAY_MAGIC)?;
+
+    match version {
+        REPLAY_VERSION => bincode::deserialize(&body).map_err(|e| e.to_string()),
+        v => Err(format!(
+            "unsupported replay version {} (expected {})",
+            v, REPLAY_VERSION
+        )),
+    }
+}
+
+pub fn on_save_replay(mut ev_save_replay: EventReader<SaveReplayEvent>, log: Res<InputLog>) {
+    for ev in ev_save_replay.read() {
+        match write_replay_file(&ev.path, &log) {
+            Ok(()) => println!("Replay saved to {}", ev.path.display()),
+            Err(e) => println!("Could not save replay to {}: {}", ev.path.display(), e),
+        }
+    }
+}
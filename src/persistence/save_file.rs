@@ -0,0 +1,209 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    colonists::{CreatureType, ItemTag, JobType, Needs, Skills, WorkPreferences},
+    items::{Commodity, CraftBill, ToolMaterial},
+    rendering::SlotIndex,
+    structures::BlueprintType,
+    BlockType,
+};
+
+use super::BlockRun;
+
+pub const SAVE_MAGIC: [u8; 4] = *b"BRSV";
+pub const SAVE_VERSION: u32 = 14;
+pub const DEFAULT_SAVE_PATH: &str = "saves/colony.sav";
+
+/// Saved entities reference each other by index into the list of entities
+/// spawned on load, rather than by their (unstable) bevy `Entity`.
+pub type SaveId = u32;
+
+#[derive(Serialize, Deserialize)]
+pub struct ColonySave {
+    pub terrain: TerrainSave,
+    pub trees: Vec<TreeSave>,
+    pub colonists: Vec<ColonistSave>,
+    pub creatures: Vec<CreatureSave>,
+    pub items: Vec<ItemSave>,
+    pub structures: Vec<StructureSave>,
+    pub jobs: Vec<JobSave>,
+    pub stockpiles: Vec<StockpileSave>,
+    /// seconds since midnight
+    pub time_of_day: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TerrainSave {
+    pub chunk_count_x: u32,
+    pub chunk_count_y: u32,
+    pub chunk_count_z: u32,
+    pub chunk_size: u32,
+    pub chunks: Vec<Vec<BlockRun>>,
+    pub fluid_queue: Vec<[u32; 3]>,
+    pub support_queue: Vec<[u32; 3]>,
+    /// blocks caught mid-fall, set down where they were going to land
+    pub falling_blocks: Vec<FallingBlockSave>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FallingBlockSave {
+    pub block_type: BlockType,
+    pub target: [u32; 3],
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TreeSave {
+    pub id: SaveId,
+    pub trunk: Vec<[u32; 3]>,
+    pub canopy: Vec<[u32; 3]>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ColonistSave {
+    pub position: [u32; 3],
+    pub fatigue: f32,
+    pub id: u32,
+    pub needs: Needs,
+    pub mood: f32,
+    pub work: WorkPreferences,
+    pub skills: Skills,
+    pub health: f32,
+    pub is_drafted: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreatureSave {
+    pub position: [u32; 3],
+    pub creature_type: CreatureType,
+    /// id of the colonist a pet belongs to
+    pub owner: Option<u32>,
+    pub health: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum ItemKindSave {
+    Commodity(Commodity),
+    Axe {
+        material: ToolMaterial,
+        durability: u32,
+    },
+    Pickaxe {
+        material: ToolMaterial,
+        durability: u32,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ItemSave {
+    pub id: SaveId,
+    pub kind: ItemKindSave,
+    pub position: [u32; 3],
+    pub slot: Option<(SaveId, SlotIndex)>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StructureSave {
+    pub id: SaveId,
+    pub blueprint_type: BlueprintType,
+    pub position: [u32; 3],
+    pub rotation: u8,
+    pub is_flipped: bool,
+    pub is_built: bool,
+    pub bills: Vec<CraftBill>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StockpileSave {
+    pub tiles: Vec<[u32; 3]>,
+    pub tags: Vec<ItemTag>,
+    pub capacity: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum JobDetailSave {
+    Mine,
+    Chop {
+        tree: SaveId,
+    },
+    PlaceBlock,
+    Build {
+        structure: SaveId,
+    },
+    Supply {
+        flags: Vec<ItemTag>,
+        slot_target_idx: SlotIndex,
+        target: SaveId,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JobSave {
+    pub job_type: JobType,
+    pub priority: u8,
+    pub detail: JobDetailSave,
+    pub targets: Vec<[u32; 3]>,
+    pub primary_target: [u32; 3],
+    pub source: Option<[u32; 3]>,
+}
+
+pub fn write_save_file(path: &Path, save: &ColonySave) -> Result<(), String> {
+    let body = bincode::serialize(save).map_err(|e| e.to_string())?;
+
+    write_file_with_header(path, SAVE_MAGIC, SAVE_VERSION, &body)
+}
+
+pub fn read_save_file(path: &Path) -> Result<ColonySave, String> {
+    let (version, body) = read_file_with_header(path, SAVE_MAGIC)?;
+
+    match version {
+        SAVE_VERSION => bincode::deserialize(&body).map_err(|e| e.to_string()),
+        v => Err(format!(
+            "unsupported save version {} (expected {})",
+            v, SAVE_VERSION
+        )),
+    }
+}
+
+/// Writes `magic`, then `version` (little endian), then `body`.
+pub fn write_file_with_header(
+    path: &Path,
+    magic: [u8; 4],
+    version: u32,
+    body: &[u8],
+) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let mut file = fs::File::create(path).map_err(|e| e.to_string())?;
+
+    file.write_all(&magic).map_err(|e| e.to_string())?;
+    file.write_all(&version.to_le_bytes())
+        .map_err(|e| e.to_string())?;
+    file.write_all(body).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Returns the version and body of a file written by `write_file_with_header`.
+pub fn read_file_with_header(path: &Path, magic: [u8; 4]) -> Result<(u32, Vec<u8>), String> {
+    let mut bytes = vec![];
+
+    fs::File::open(path)
+        .and_then(|mut f| f.read_to_end(&mut bytes))
+        .map_err(|e| e.to_string())?;
+
+    if bytes.len() < 8 || bytes[0..4] != magic {
+        return Err("unrecognized file type".into());
+    }
+
+    let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+
+    Ok((version, bytes.split_off(8)))
+}
@@ -1,8 +1,9 @@
 use bevy::{
-    asset::{Asset, Assets, Handle},
+    asset::{Asset, AssetEvent, Assets, Handle},
     ecs::{
+        event::EventReader,
         query::Changed,
-        system::{Query, Res, ResMut},
+        system::{Local, Query, Res, ResMut},
     },
     pbr::{Material, MaterialPipeline, MaterialPipelineKey},
     reflect::TypePath,
@@ -16,8 +17,9 @@ use bevy::{
         texture::Image,
     },
 };
+use serde::{Deserialize, Serialize};
 
-use crate::{colonists::ChildMaterials, Position, Terrain};
+use crate::{colonists::ChildMaterials, Position, Terrain, TimeOfDay};
 
 #[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
 #[bind_group_data(BasicMaterialKey)]
@@ -51,6 +53,10 @@ pub struct BasicMaterial {
 
     #[uniform[11]]
     pub slot_indexes: u32,
+
+    /// scales `sunlight` with the time of day
+    #[uniform[12]]
+    pub daylight: f32,
 }
 
 impl Default for BasicMaterial {
@@ -69,6 +75,7 @@ impl Default for BasicMaterial {
             slot_1_color: Color::WHITE,
             slot_2_color: Color::WHITE,
             slots_uv_scale: 2.0,
+            daylight: 1.,
         }
     }
 }
@@ -94,7 +101,7 @@ impl From<&BasicMaterial> for BasicMaterialKey {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SlotIndex {
     Slot0,
     Slot1,
@@ -117,6 +124,7 @@ impl BasicMaterial {
             slot_2_color: Color::WHITE,
             slot_indexes: 0,
             slots_uv_scale: 2.0,
+            daylight: 1.,
         }
     }
 
@@ -158,6 +166,9 @@ impl BasicMaterial {
     }
 }
 
+/// Daylight change too small to be worth updating materials for.
+pub const DAYLIGHT_EPSILON: f32 = 0.01;
+
 pub const ATTRIBUTE_SLOTS: MeshVertexAttribute =
     MeshVertexAttribute::new("SlotData", 9911128712, VertexFormat::Float32x4);
 
@@ -278,3 +289,47 @@ pub fn update_basic_material_children_lighting(
         material.sunlight = block.sunlight as u32;
     }
 }
+
+/// A material whose sunlight is scaled with the time of day.
+pub trait DaylightMaterial: Asset {
+    fn set_daylight(&mut self, daylight: f32);
+}
+
+impl DaylightMaterial for BasicMaterial {
+    fn set_daylight(&mut self, daylight: f32) {
+        self.daylight = daylight;
+    }
+}
+
+/// Keeps the daylight of every material of a kind in step with the time of
+/// day. Materials are only touched when the light has visibly changed since
+/// it was last applied, or when they are new.
+pub fn update_material_daylight<M: DaylightMaterial>(
+    time_of_day: Res<TimeOfDay>,
+    mut applied: Local<Option<f32>>,
+    mut ev_asset: EventReader<AssetEvent<M>>,
+    mut materials: ResMut<Assets<M>>,
+) {
+    let daylight = time_of_day.daylight();
+
+    if let Some(applied) = *applied {
+        if (applied - daylight).abs() <= DAYLIGHT_EPSILON {
+            for ev in ev_asset.read() {
+                if let AssetEvent::Added { id } = ev {
+                    if let Some(material) = materials.get_mut(*id) {
+                        material.set_daylight(applied);
+                    }
+                }
+            }
+
+            return;
+        }
+    }
+
+    ev_asset.clear();
+    *applied = Some(daylight);
+
+    for (_, material) in materials.iter_mut() {
+        material.set_daylight(daylight);
+    }
+}
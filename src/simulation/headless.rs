@@ -0,0 +1,165 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    time::Duration,
+};
+
+use bevy::{
+    app::{App, AppExit, Last, Plugin, Startup},
+    asset::{AssetApp, AssetPlugin},
+    ecs::{
+        event::EventWriter,
+        query::With,
+        system::{Query, Res, ResMut, Resource},
+    },
+    hierarchy::HierarchyPlugin,
+    render::{mesh::Mesh, texture::Image},
+    scene::Scene,
+    time::TimeUpdateStrategy,
+    transform::{components::Transform, TransformPlugin},
+    MinimalPlugins,
+};
+
+use crate::{
+    persistence::{read_replay_file, LoadColonyEvent},
+    rendering::BasicMaterial,
+    Position, Terrain,
+};
+
+use super::{arg_value, InputLog, InputReplay, SimClock, SimulationPlugin, SIM_TICK_SECONDS};
+
+pub const HEADLESS_DEFAULT_TICKS: u32 = 600;
+
+/// Runs the app without a window or GPU for a fixed number of frames, each
+/// advancing time by one simulation tick. Assets are registered so the spawn
+/// systems can create handles, but nothing is ever loaded.
+pub struct HeadlessPlugin {
+    pub ticks: u32,
+    pub load: Option<PathBuf>,
+    pub replay: Option<InputLog>,
+}
+
+#[derive(Resource)]
+pub struct SimulationTicks {
+    pub remaining: u32,
+}
+
+impl HeadlessPlugin {
+    /// `--headless [--ticks N] [--load PATH] [--replay PATH]`
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|a| a == "--headless") {
+            return None;
+        }
+
+        let replay = arg_value(args, "--replay").and_then(|path| {
+            match read_replay_file(&PathBuf::from(path)) {
+                Ok(log) => Some(log),
+                Err(e) => {
+                    println!("Could not read replay {}: {}", path, e);
+                    None
+                }
+            }
+        });
+
+        Some(Self {
+            ticks: arg_value(args, "--ticks")
+                .and_then(|t| t.parse().ok())
+                .unwrap_or(HEADLESS_DEFAULT_TICKS),
+            load: arg_value(args, "--load").map(PathBuf::from),
+            replay,
+        })
+    }
+
+    /// A replay must run in the world it was recorded in.
+    pub fn simulation(&self, args: &[String]) -> SimulationPlugin {
+        match &self.replay {
+            Some(log) => SimulationPlugin {
+                world: log.world.clone(),
+                generate_terrain: true,
+            },
+            None => SimulationPlugin::from_args(args),
+        }
+    }
+}
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            HierarchyPlugin,
+            AssetPlugin::default(),
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<Image>()
+        .init_asset::<Scene>()
+        .init_asset::<BasicMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            SIM_TICK_SECONDS,
+        )))
+        .insert_resource(SimulationTicks {
+            remaining: self.ticks,
+        })
+        .add_systems(Last, count_simulation_ticks);
+
+        let mut load = self.load.clone();
+
+        if let Some(log) = self.replay.clone() {
+            load = load.or(log.loaded_from);
+            app.insert_resource(InputReplay {
+                entries: log.entries,
+                cursor: 0,
+            });
+        }
+
+        if let Some(path) = load {
+            app.add_systems(
+                Startup,
+                move |mut ev_load_colony: EventWriter<LoadColonyEvent>| {
+                    ev_load_colony.send(LoadColonyEvent { path: path.clone() });
+                },
+            );
+        }
+    }
+}
+
+pub fn count_simulation_ticks(
+    mut ticks: ResMut<SimulationTicks>,
+    mut ev_app_exit: EventWriter<AppExit>,
+    clock: Res<SimClock>,
+    terrain: Res<Terrain>,
+    q_positions: Query<&Transform, With<Position>>,
+) {
+    if ticks.remaining > 0 {
+        ticks.remaining -= 1;
+        return;
+    }
+
+    println!(
+        "Headless simulation finished, tick={} checksum={:016x}",
+        clock.tick,
+        colony_checksum(&terrain, q_positions.iter())
+    );
+    ev_app_exit.send(AppExit);
+}
+
+/// Hash of the terrain and everything positioned in it, to compare runs.
+pub fn colony_checksum<'a>(
+    terrain: &Terrain,
+    transforms: impl Iterator<Item = &'a Transform>,
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    for chunk in terrain.chunks.iter() {
+        chunk.blocks.hash(&mut hasher);
+    }
+
+    for transform in transforms {
+        transform.translation.x.to_bits().hash(&mut hasher);
+        transform.translation.y.to_bits().hash(&mut hasher);
+        transform.translation.z.to_bits().hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
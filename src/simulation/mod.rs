@@ -0,0 +1,13 @@
+mod headless;
+mod sim_input;
+mod simulation_plugin;
+#[cfg(test)]
+mod test_harness;
+#[cfg(test)]
+mod tests;
+
+pub use headless::*;
+pub use sim_input::*;
+pub use simulation_plugin::*;
+#[cfg(test)]
+pub use test_harness::*;
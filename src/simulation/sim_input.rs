@@ -0,0 +1,349 @@
+use std::path::PathBuf;
+
+use bevy::ecs::{
+    event::{Event, EventReader, EventWriter},
+    system::{Commands, ResMut, Resource},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    colonists::{
+        CreatureType, IsAwaitingBuildJob, ItemTag, OrderMoveEvent, SetDraftedEvent,
+        SetJobPriorityEvent, SetWorkPreferenceEvent, SpawnColonistEvent, SpawnCreatureEvent,
+        SpawnJobChopEvent, SpawnJobMineEvent, SpawnJobPlaceBlockEvent, WorkPreference, WorkType,
+    },
+    items::{
+        stockpile_tiles, AddCraftBillEvent, ClearCraftBillsEvent, RecipeType, SpawnAxeEvent,
+        SpawnPickaxeEvent, SpawnStockpileEvent, ToolMaterial,
+    },
+    structures::{BlueprintType, SpawnStructureEvent, StructureMode},
+    ui::GameSpeed,
+    BlockType, ChunkStreaming, Terrain, WorldGenSettings,
+};
+
+/// Everything the player can do that changes the simulation. Tools send
+/// these instead of touching the world directly, so they are applied at the
+/// start of a tick and can be recorded and replayed.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub enum SimInput {
+    SetPaused(bool),
+    SetBlocks {
+        min: [u32; 3],
+        max: [u32; 3],
+        block_type: BlockType,
+    },
+    SpawnColonist([u32; 3]),
+    SpawnAxe([u32; 3]),
+    SpawnPickaxe([u32; 3]),
+    Mine {
+        min: [u32; 3],
+        max: [u32; 3],
+    },
+    Chop {
+        min: [u32; 3],
+        max: [u32; 3],
+    },
+    PlaceBlock {
+        pos: [u32; 3],
+        block_type: BlockType,
+    },
+    PlaceStructure {
+        blueprint_type: BlueprintType,
+        pos: [u32; 3],
+        rotation: u8,
+        is_flipped: bool,
+    },
+    /// block x and z the player is looking at, chunks around it are loaded
+    SetViewFocus([u32; 2]),
+    CreateStockpile {
+        min: [u32; 3],
+        max: [u32; 3],
+        tags: Vec<ItemTag>,
+        /// items stored on each tile
+        capacity: u32,
+    },
+    SetJobPriority {
+        min: [u32; 3],
+        max: [u32; 3],
+        priority: u8,
+    },
+    SetWorkPreference {
+        colonist: u32,
+        work_type: WorkType,
+        preference: WorkPreference,
+    },
+    /// `station` is the position of the structure crafting the recipe
+    AddCraftBill {
+        station: [u32; 3],
+        recipe: RecipeType,
+        count: u32,
+    },
+    ClearCraftBills {
+        station: [u32; 3],
+    },
+    /// `owner` is the id of the colonist a pet belongs to
+    SpawnCreature {
+        pos: [u32; 3],
+        creature_type: CreatureType,
+        owner: Option<u32>,
+    },
+    SetDrafted {
+        colonist: u32,
+        is_drafted: bool,
+    },
+    /// sends a drafted colonist to `pos`
+    OrderMove {
+        colonist: u32,
+        pos: [u32; 3],
+    },
+}
+
+#[derive(Event)]
+pub struct SimInputEvent(pub SimInput);
+
+/// Number of fixed ticks simulated since the world was generated or loaded.
+#[derive(Resource, Default)]
+pub struct SimClock {
+    pub tick: u64,
+}
+
+/// The world and every input applied so far. Together with the save the
+/// colony was loaded from (if any) this reproduces the simulation exactly.
+#[derive(Resource, Serialize, Deserialize, Clone)]
+pub struct InputLog {
+    pub world: WorldGenSettings,
+    pub loaded_from: Option<PathBuf>,
+    pub entries: Vec<(u64, SimInput)>,
+}
+
+/// Inputs fed back into the simulation instead of the player's.
+#[derive(Resource)]
+pub struct InputReplay {
+    pub entries: Vec<(u64, SimInput)>,
+    pub cursor: usize,
+}
+
+pub fn apply_sim_inputs(
+    mut cmd: Commands,
+    mut ev_sim_input: EventReader<SimInputEvent>,
+    mut clock: ResMut<SimClock>,
+    mut log: ResMut<InputLog>,
+    replay: Option<ResMut<InputReplay>>,
+    mut terrain: ResMut<Terrain>,
+    mut streaming: ResMut<ChunkStreaming>,
+    mut game_speed: ResMut<GameSpeed>,
+    (mut ev_spawn_colonist, mut ev_spawn_creature): (
+        EventWriter<SpawnColonistEvent>,
+        EventWriter<SpawnCreatureEvent>,
+    ),
+    (mut ev_spawn_axe, mut ev_spawn_pickaxe): (
+        EventWriter<SpawnAxeEvent>,
+        EventWriter<SpawnPickaxeEvent>,
+    ),
+    (mut ev_spawn_job_mine, mut ev_spawn_job_chop, mut ev_spawn_job_place_block): (
+        EventWriter<SpawnJobMineEvent>,
+        EventWriter<SpawnJobChopEvent>,
+        EventWriter<SpawnJobPlaceBlockEvent>,
+    ),
+    (mut ev_set_job_priority, mut ev_set_work_preference): (
+        EventWriter<SetJobPriorityEvent>,
+        EventWriter<SetWorkPreferenceEvent>,
+    ),
+    (mut ev_add_craft_bill, mut ev_clear_craft_bills): (
+        EventWriter<AddCraftBillEvent>,
+        EventWriter<ClearCraftBillsEvent>,
+    ),
+    mut ev_spawn_structure: EventWriter<SpawnStructureEvent>,
+    mut ev_spawn_stockpile: EventWriter<SpawnStockpileEvent>,
+    (mut ev_set_drafted, mut ev_order_move): (
+        EventWriter<SetDraftedEvent>,
+        EventWriter<OrderMoveEvent>,
+    ),
+) {
+    let tick = clock.tick;
+    clock.tick += 1;
+
+    let mut inputs = ev_sim_input
+        .read()
+        .map(|ev| ev.0.clone())
+        .collect::<Vec<_>>();
+
+    if let Some(mut replay) = replay {
+        while let Some((input_tick, input)) = replay.entries.get(replay.cursor) {
+            if *input_tick > tick {
+                break;
+            }
+
+            inputs.push(input.clone());
+            replay.cursor += 1;
+        }
+    }
+
+    for input in inputs {
+        match &input {
+            SimInput::SetPaused(is_paused) => {
+                game_speed.is_paused = *is_paused;
+            }
+            SimInput::SetBlocks {
+                min,
+                max,
+                block_type,
+            } => {
+                for x in min[0]..=max[0] {
+                    for y in min[1]..=max[1] {
+                        for z in min[2]..=max[2] {
+                            terrain.set_block_type(x, y, z, *block_type);
+                        }
+                    }
+                }
+            }
+            SimInput::SpawnColonist(pos) => {
+                ev_spawn_colonist.send(SpawnColonistEvent {
+                    entity: None,
+                    pos: *pos,
+                });
+            }
+            SimInput::SpawnAxe(pos) => {
+                ev_spawn_axe.send(SpawnAxeEvent {
+                    pos: *pos,
+                    material: ToolMaterial::Stone,
+                    durability: None,
+                });
+            }
+            SimInput::SpawnPickaxe(pos) => {
+                ev_spawn_pickaxe.send(SpawnPickaxeEvent {
+                    pos: *pos,
+                    material: ToolMaterial::Stone,
+                    durability: None,
+                });
+            }
+            SimInput::Mine { min, max } => {
+                for x in min[0]..=max[0] {
+                    for y in min[1]..=max[1] {
+                        for z in min[2]..=max[2] {
+                            if !terrain.get_block(x, y, z).is_empty() {
+                                ev_spawn_job_mine.send(SpawnJobMineEvent { pos: [x, y, z] });
+                            }
+                        }
+                    }
+                }
+            }
+            SimInput::Chop { min, max } => {
+                for x in min[0]..=max[0] {
+                    for y in min[1]..=max[1] {
+                        for z in min[2]..=max[2] {
+                            if !terrain.get_block(x, y, z).is_empty() {
+                                ev_spawn_job_chop.send(SpawnJobChopEvent { pos: [x, y, z] });
+                            }
+                        }
+                    }
+                }
+            }
+            SimInput::PlaceBlock { pos, block_type } => {
+                ev_spawn_job_place_block.send(SpawnJobPlaceBlockEvent {
+                    pos: *pos,
+                    block_type: *block_type,
+                });
+            }
+            SimInput::PlaceStructure {
+                blueprint_type,
+                pos,
+                rotation,
+                is_flipped,
+            } => {
+                let entity = cmd.spawn(IsAwaitingBuildJob).id();
+
+                ev_spawn_structure.send(SpawnStructureEvent {
+                    pos: *pos,
+                    entity,
+                    blueprint_type: *blueprint_type,
+                    rotation: *rotation,
+                    is_flipped: *is_flipped,
+                    mode: StructureMode::Placed,
+                });
+            }
+            SimInput::SetViewFocus(focus) => {
+                streaming.focus = *focus;
+            }
+            SimInput::CreateStockpile {
+                min,
+                max,
+                tags,
+                capacity,
+            } => {
+                let tiles = stockpile_tiles(&terrain, *min, *max);
+
+                if !tiles.is_empty() {
+                    ev_spawn_stockpile.send(SpawnStockpileEvent {
+                        entity: None,
+                        tiles,
+                        tags: tags.clone(),
+                        capacity: *capacity,
+                    });
+                }
+            }
+            SimInput::SetJobPriority { min, max, priority } => {
+                ev_set_job_priority.send(SetJobPriorityEvent {
+                    min: *min,
+                    max: *max,
+                    priority: *priority,
+                });
+            }
+            SimInput::SetWorkPreference {
+                colonist,
+                work_type,
+                preference,
+            } => {
+                ev_set_work_preference.send(SetWorkPreferenceEvent {
+                    colonist: *colonist,
+                    work_type: *work_type,
+                    preference: *preference,
+                });
+            }
+            SimInput::AddCraftBill {
+                station,
+                recipe,
+                count,
+            } => {
+                ev_add_craft_bill.send(AddCraftBillEvent {
+                    station: *station,
+                    recipe: *recipe,
+                    count: *count,
+                });
+            }
+            SimInput::ClearCraftBills { station } => {
+                ev_clear_craft_bills.send(ClearCraftBillsEvent { station: *station });
+            }
+            SimInput::SpawnCreature {
+                pos,
+                creature_type,
+                owner,
+            } => {
+                ev_spawn_creature.send(SpawnCreatureEvent {
+                    entity: None,
+                    pos: *pos,
+                    creature_type: *creature_type,
+                    owner: *owner,
+                    health: None,
+                });
+            }
+            SimInput::SetDrafted {
+                colonist,
+                is_drafted,
+            } => {
+                ev_set_drafted.send(SetDraftedEvent {
+                    colonist: *colonist,
+                    is_drafted: *is_drafted,
+                });
+            }
+            SimInput::OrderMove { colonist, pos } => {
+                ev_order_move.send(OrderMoveEvent {
+                    colonist: *colonist,
+                    pos: *pos,
+                });
+            }
+        }
+
+        log.entries.push((tick, input));
+    }
+}
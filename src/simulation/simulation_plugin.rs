@@ -0,0 +1,445 @@
+use bevy::{
+    app::{App, FixedUpdate, Plugin, Startup},
+    ecs::{
+        schedule::{ExecutorKind, IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
+        system::Res,
+    },
+    time::{Fixed, Time},
+    transform::systems::{propagate_transforms, sync_simple_transforms},
+};
+
+use crate::{
+    colonists::{
+        apply_falling, behavior_pick_system, behavior_system, block_move_system,
+        check_job_build_valid, check_job_craft_valid, check_job_haul_valid, check_job_supply_valid,
+        combat_alert_system, destroy_items, drafted_system, fatigue_system, health_system,
+        hostile_spawn_system, job_accessibility, job_despawn_cancelled, job_despawn_complete,
+        mood_system, needs_system, on_cancel_job, on_damage, on_order_move, on_set_drafted,
+        on_set_job_priority, on_set_work_preference, on_spawn_colonist, on_spawn_creature,
+        on_spawn_job_build, on_spawn_job_chop, on_spawn_job_mine, on_spawn_job_place_block,
+        on_spawn_job_supply, partition, path_results_system, path_search_system, score_build,
+        score_chop, score_craft, score_drink, score_eat, score_fight, score_flee,
+        score_follow_owner, score_haul, score_hunt, score_mine, score_place_block, score_sleep,
+        score_supply, score_wander, spawn_awaiting_build_jobs, spawn_craft_jobs, spawn_haul_jobs,
+        starvation_system, task_animate, task_build, task_check_has_ingredient,
+        task_check_has_item, task_chop_tree, task_craft, task_debug, task_drink, task_eat,
+        task_find_bed, task_find_ingredient, task_find_nearest_item, task_find_water,
+        task_get_haul_item, task_get_job_location, task_idle, task_is_target_empty,
+        task_item_equip, task_item_pick_up, task_job_assign, task_job_cancel, task_job_complete,
+        task_job_unassign, task_look_at, task_melee_attack, task_mine_block, task_move_to,
+        task_pick_flee_spot, task_pick_follow_spot, task_pick_random_spot, task_pick_target,
+        task_place_block, task_release_bed, task_sleep, task_store_item, task_supply, ActorRef,
+        Blackboard, DamageEvent, DestroyItemEvent, HasBehavior, InInventory, Inventory, Item,
+        ItemTag, JobCancelEvent, NavigationGraph, OrderMoveEvent, PartitionPathRequest, Path,
+        PathCache, PathQueue, Score, ScorerPlugin, Scorers, SetDraftedEvent, SetJobPriorityEvent,
+        SetWorkPreferenceEvent, SpawnColonistEvent, SpawnCreatureEvent, SpawnJobBuildEvent,
+        SpawnJobChopEvent, SpawnJobMineEvent, SpawnJobPlaceBlockEvent, SpawnJobSupplyEvent,
+        TaskState,
+    },
+    common::Rand,
+    fluid_system,
+    items::{
+        on_add_craft_bill, on_clear_craft_bills, on_set_slot, on_spawn_axe, on_spawn_commodity,
+        on_spawn_pickaxe, on_spawn_stockpile, setup_commodity_berries, setup_commodity_dirt,
+        setup_commodity_ore_coal, setup_commodity_ore_copper, setup_commodity_ore_gold,
+        setup_commodity_ore_iron, setup_commodity_stone_ashlar_block,
+        setup_commodity_stone_shale_boulder, setup_commodity_stone_shale_cut,
+        setup_commodity_wood_birch_log, setup_commodity_wood_birch_plank, setup_recipes,
+        AddCraftBillEvent, ClearCraftBillsEvent, Commodities, Recipes, SetSlotEvent, SpawnAxeEvent,
+        SpawnCommodityEvent, SpawnPickaxeEvent, SpawnStockpileEvent,
+    },
+    land_falling_blocks, light_system, on_moved_lamp, on_removed_lamp, on_spawn_tree,
+    persistence::{
+        apply_colony_load, on_load_colony, on_save_colony, on_save_replay, LoadColonyEvent,
+        SaveColonyEvent, SaveReplayEvent,
+    },
+    setup_terrain, stream_chunks,
+    structures::{
+        check_structures, on_build_structure, on_remove_structure, on_spawn_structure,
+        setup_blueprint_bed, setup_blueprint_door, setup_blueprint_ladder, setup_blueprint_torches,
+        setup_blueprint_workbench, setup_structure_bed, setup_structure_torch,
+        setup_structure_workbench, Blueprints, BuildStructureEvent, BuiltStructureEvent,
+        RemoveStructureEvent, SpawnStructureEvent,
+    },
+    support_system, time_of_day_system,
+    ui::GameSpeed,
+    update_positions, BlockRegistry, ChunkStreaming, Lamps, Position, SpawnTreeEvent, Terrain,
+    TimeOfDay, WorldGenSettings, WorldPreset,
+};
+
+use super::{apply_sim_inputs, InputLog, SimClock, SimInputEvent};
+
+pub const SIM_TICK_SECONDS: f64 = 1. / 60.;
+
+/// Order of the simulation within a `FixedUpdate` tick.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimSet {
+    /// loading, saving, and player input
+    Input,
+    /// behavior trees advance and colonists are scored
+    Behavior,
+    Update,
+    /// job cleanup, navigation, and positions
+    PostUpdate,
+}
+
+/// Everything needed to run a colony, without any rendering or input. Runs
+/// under `DefaultPlugins` for the game, or `HeadlessPlugin` for CI.
+///
+/// The colony only changes in `FixedUpdate`, on a single thread, with `Rand`
+/// seeded from the world's seed, so the same world and `InputLog` always
+/// reproduce the same colony.
+pub struct SimulationPlugin {
+    pub world: WorldGenSettings,
+    /// when false, the `Terrain` resource is expected to be replaced after
+    /// the plugin is added, as the test harness does with its fixtures
+    pub generate_terrain: bool,
+}
+
+impl SimulationPlugin {
+    /// `[--seed N] [--preset standard|flat|mountains|caverns]`, or a random
+    /// seed and the standard preset.
+    pub fn from_args(args: &[String]) -> Self {
+        let seed = arg_value(args, "--seed")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_else(rand::random);
+
+        let preset = match arg_value(args, "--preset") {
+            Some(name) => WorldPreset::from_name(name).unwrap_or_else(|| {
+                println!("Unknown world preset {}, using standard", name);
+                WorldPreset::Standard
+            }),
+            None => WorldPreset::Standard,
+        };
+
+        Self {
+            world: WorldGenSettings::new(seed, preset),
+            generate_terrain: true,
+        }
+    }
+}
+
+pub fn arg_value<'a>(args: &'a [String], name: &str) -> Option<&'a String> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|idx| args.get(idx + 1))
+}
+
+/// Ticks still run while paused so player input is applied, but colonists
+/// make no progress.
+pub fn is_sim_running(game_speed: Res<GameSpeed>) -> bool {
+    !game_speed.is_paused
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let [chunks_x, chunks_y, chunks_z] = self.world.chunk_counts;
+
+        println!("Simulation seed {}", self.world.seed);
+
+        // fail on startup rather than on first use if blocks.ron is invalid
+        if let Err(e) = BlockRegistry::init() {
+            println!("Could not load blocks: {}", e);
+            std::process::exit(1);
+        }
+
+        app.insert_resource(Terrain::new(
+            chunks_x,
+            chunks_y,
+            chunks_z,
+            self.world.chunk_size,
+        ))
+        .init_resource::<ChunkStreaming>()
+        .insert_resource(Rand::seed(self.world.seed))
+        .insert_resource(self.world.clone())
+        .insert_resource(InputLog {
+            world: self.world.clone(),
+            loaded_from: None,
+            entries: vec![],
+        })
+        .insert_resource(Time::<Fixed>::from_seconds(SIM_TICK_SECONDS))
+        .insert_resource(Blueprints::default())
+        .insert_resource(Commodities::default())
+        .insert_resource(Recipes::default())
+        .init_resource::<SimClock>()
+        .init_resource::<NavigationGraph>()
+        .init_resource::<PathCache>()
+        .init_resource::<PathQueue>()
+        .init_resource::<GameSpeed>()
+        .init_resource::<Lamps>()
+        .init_resource::<TimeOfDay>()
+        .register_type::<Position>()
+        .register_type::<HasBehavior>()
+        .register_type::<ActorRef>()
+        .register_type::<Path>()
+        .register_type::<PartitionPathRequest>()
+        .register_type::<Score>()
+        .register_type::<Scorers>()
+        .register_type::<Inventory>()
+        .register_type::<Item>()
+        .register_type::<InInventory>()
+        .register_type::<ItemTag>()
+        .register_type::<Blackboard>()
+        .register_type::<TaskState>()
+        .add_event::<SimInputEvent>()
+        .add_event::<SpawnTreeEvent>()
+        .add_event::<SpawnColonistEvent>()
+        .add_event::<SpawnCreatureEvent>()
+        .add_event::<SpawnAxeEvent>()
+        .add_event::<SpawnPickaxeEvent>()
+        .add_event::<DestroyItemEvent>()
+        .add_event::<DamageEvent>()
+        .add_event::<SetDraftedEvent>()
+        .add_event::<OrderMoveEvent>()
+        .add_event::<SpawnJobPlaceBlockEvent>()
+        .add_event::<SpawnJobMineEvent>()
+        .add_event::<SpawnJobChopEvent>()
+        .add_event::<SpawnJobSupplyEvent>()
+        .add_event::<SpawnJobBuildEvent>()
+        .add_event::<SpawnStructureEvent>()
+        .add_event::<RemoveStructureEvent>()
+        .add_event::<BuildStructureEvent>()
+        .add_event::<BuiltStructureEvent>()
+        .add_event::<JobCancelEvent>()
+        .add_event::<SetJobPriorityEvent>()
+        .add_event::<SetWorkPreferenceEvent>()
+        .add_event::<AddCraftBillEvent>()
+        .add_event::<ClearCraftBillsEvent>()
+        .add_event::<SpawnCommodityEvent>()
+        .add_event::<SpawnStockpileEvent>()
+        .add_event::<SetSlotEvent>()
+        .add_event::<SaveColonyEvent>()
+        .add_event::<LoadColonyEvent>()
+        .add_event::<SaveReplayEvent>()
+        .add_plugins(ScorerPlugin)
+        .edit_schedule(FixedUpdate, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        })
+        .configure_sets(
+            FixedUpdate,
+            (
+                SimSet::Input,
+                SimSet::Behavior,
+                SimSet::Update,
+                SimSet::PostUpdate,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Startup,
+            (
+                setup_blueprint_ladder,
+                setup_blueprint_torches,
+                setup_blueprint_workbench,
+                setup_blueprint_door,
+                setup_blueprint_bed,
+                setup_commodity_wood_birch_log,
+                setup_commodity_stone_shale_boulder,
+                setup_commodity_berries,
+                setup_commodity_wood_birch_plank,
+                setup_commodity_stone_shale_cut,
+                setup_commodity_stone_ashlar_block,
+                setup_commodity_dirt,
+                setup_commodity_ore_coal,
+                setup_commodity_ore_copper,
+                setup_commodity_ore_gold,
+                setup_commodity_ore_iron,
+                setup_recipes,
+            )
+                .chain(),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                on_save_colony,
+                on_save_replay,
+                on_load_colony,
+                apply_sim_inputs,
+            )
+                .chain()
+                .in_set(SimSet::Input),
+        )
+        .add_systems(
+            FixedUpdate,
+            behavior_system
+                .run_if(is_sim_running)
+                .in_set(SimSet::Behavior),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                (
+                    score_wander,
+                    score_mine,
+                    score_chop,
+                    score_place_block,
+                    score_build,
+                    score_supply,
+                    score_haul,
+                    score_craft,
+                    score_eat,
+                    score_drink,
+                    score_sleep,
+                    score_follow_owner,
+                    score_flee,
+                    score_fight,
+                    score_hunt,
+                ),
+                behavior_pick_system,
+                drafted_system,
+            )
+                .chain()
+                .in_set(SimSet::Behavior)
+                .after(behavior_system),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                (
+                    setup_structure_torch,
+                    setup_structure_bed,
+                    setup_structure_workbench,
+                ),
+                on_remove_structure,
+                on_build_structure,
+                on_removed_lamp,
+                light_system,
+                (
+                    check_job_supply_valid,
+                    check_job_build_valid,
+                    check_job_haul_valid,
+                    check_job_craft_valid,
+                ),
+                on_spawn_commodity,
+                on_spawn_stockpile,
+                on_moved_lamp,
+                on_spawn_tree,
+                (on_spawn_colonist, on_spawn_creature),
+                on_spawn_pickaxe,
+                on_spawn_axe,
+                on_spawn_structure,
+                on_cancel_job,
+                apply_falling,
+                job_accessibility,
+                destroy_items,
+                apply_colony_load,
+            )
+                .in_set(SimSet::Update),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                fatigue_system,
+                (needs_system, starvation_system),
+                mood_system,
+                block_move_system,
+                fluid_system,
+                support_system,
+                land_falling_blocks,
+                (on_damage, health_system, combat_alert_system).chain(),
+                time_of_day_system,
+                hostile_spawn_system,
+            )
+                .run_if(is_sim_running)
+                .in_set(SimSet::Update),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                on_spawn_job_place_block,
+                on_spawn_job_mine,
+                on_spawn_job_chop,
+                on_spawn_job_supply,
+                spawn_haul_jobs,
+                spawn_craft_jobs,
+                on_set_job_priority,
+                on_set_work_preference,
+                (on_set_drafted, on_order_move).chain(),
+                (on_add_craft_bill, on_clear_craft_bills),
+                (
+                    check_structures,
+                    spawn_awaiting_build_jobs,
+                    on_spawn_job_build,
+                )
+                    .chain(),
+            )
+                .in_set(SimSet::Update),
+        )
+        .add_systems(
+            FixedUpdate,
+            path_results_system
+                .before(task_move_to)
+                .in_set(SimSet::Update),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                task_job_assign,
+                (task_supply, on_set_slot).chain(),
+                task_find_bed,
+                task_find_water,
+                task_release_bed,
+                (
+                    task_pick_random_spot,
+                    task_pick_follow_spot,
+                    task_pick_flee_spot,
+                    task_pick_target,
+                ),
+                task_move_to,
+                task_get_job_location,
+                task_get_haul_item,
+                task_store_item,
+                task_debug,
+                task_job_unassign,
+                task_job_cancel,
+                task_job_complete,
+                (task_check_has_item, task_check_has_ingredient),
+                (task_find_nearest_item, task_find_ingredient),
+                task_item_pick_up,
+                task_item_equip,
+                task_is_target_empty,
+                task_animate,
+            )
+                .in_set(SimSet::Update),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                task_sleep,
+                task_eat,
+                task_drink,
+                task_idle,
+                task_look_at,
+                task_chop_tree,
+                task_build,
+                task_mine_block,
+                task_place_block,
+                task_craft,
+                task_melee_attack,
+            )
+                .run_if(is_sim_running)
+                .in_set(SimSet::Update),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                job_despawn_complete,
+                job_despawn_cancelled,
+                (
+                    sync_simple_transforms,
+                    propagate_transforms,
+                    stream_chunks,
+                    partition,
+                    update_positions,
+                    path_search_system,
+                )
+                    .chain(),
+            )
+                .in_set(SimSet::PostUpdate),
+        );
+
+        if self.generate_terrain {
+            app.add_systems(Startup, setup_terrain);
+        }
+    }
+}
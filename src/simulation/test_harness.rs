@@ -0,0 +1,232 @@
+use bevy::{
+    app::App,
+    ecs::{event::Event, query::With, world::World},
+    transform::components::Transform,
+};
+
+use crate::{
+    colonists::CreatureType,
+    items::{Commodity, SpawnCommodityEvent},
+    BlockType, ChunkStreaming, Position, SpawnTreeEvent, Terrain, TreeSettings, WorldGenSettings,
+    WorldPreset,
+};
+
+use super::{colony_checksum, HeadlessPlugin, SimClock, SimInput, SimInputEvent, SimulationPlugin};
+
+const FIXTURE_CHUNK_SIZE: u32 = 8;
+
+/// Something in a fixture that is spawned into an empty block.
+enum FixtureEntity {
+    Colonist,
+    /// pets belong to the first colonist
+    Creature(CreatureType, Option<u32>),
+    Pickaxe,
+    Axe,
+    Commodity(Commodity),
+    Tree,
+}
+
+/// A headless simulation of a small hand-built colony, stepped one fixed
+/// tick at a time.
+///
+/// The fixture is a list of ascii layers, from the bottom (y = 0) up,
+/// separated by blank lines. Each layer is a row per z, and a character per
+/// x. Blocks are `.` empty, `#` stone, `d` dirt, `g` grass, `a` ashlar,
+/// `m` magma and `w` water. `C` colonist, `K` pet cat, `R` wild rabbit,
+/// `H` crawler, `P` pickaxe, `A` axe, `L` log, `B` boulder, `F` berries and
+/// `T` tree are spawned in an empty block.
+pub struct TestSim {
+    pub app: App,
+}
+
+impl TestSim {
+    pub fn new(fixture: &str) -> Self {
+        Self::with_seed(fixture, 0)
+    }
+
+    pub fn with_seed(fixture: &str, seed: u64) -> Self {
+        let (terrain, entities) = parse_fixture(fixture);
+        let mut app = App::new();
+
+        app.add_plugins(HeadlessPlugin {
+            ticks: u32::MAX,
+            load: None,
+            replay: None,
+        })
+        .add_plugins(SimulationPlugin {
+            world: WorldGenSettings::new(seed, WorldPreset::Standard),
+            generate_terrain: false,
+        })
+        .insert_resource(ChunkStreaming::new(&terrain))
+        .insert_resource(terrain);
+
+        app.finish();
+        app.cleanup();
+
+        // blueprints and commodities are set up on the first update
+        app.update();
+
+        let mut sim = Self { app };
+
+        for (pos, entity) in entities {
+            match entity {
+                FixtureEntity::Colonist => sim.input(SimInput::SpawnColonist(pos)),
+                FixtureEntity::Creature(creature_type, owner) => {
+                    sim.input(SimInput::SpawnCreature {
+                        pos,
+                        creature_type,
+                        owner,
+                    })
+                }
+                FixtureEntity::Pickaxe => sim.input(SimInput::SpawnPickaxe(pos)),
+                FixtureEntity::Axe => sim.input(SimInput::SpawnAxe(pos)),
+                FixtureEntity::Commodity(commodity) => sim.send(SpawnCommodityEvent {
+                    entity: None,
+                    commodity,
+                    position: pos,
+                    slot_entity: None,
+                    slot_idx: None,
+                }),
+                FixtureEntity::Tree => sim.send(SpawnTreeEvent {
+                    position: pos,
+                    settings: TreeSettings {
+                        height: 4,
+                        canopy_radius: 1,
+                    },
+                }),
+            }
+        }
+
+        sim
+    }
+
+    pub fn world(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.app.world.resource::<SimClock>().tick
+    }
+
+    pub fn send<E: Event>(&mut self, ev: E) {
+        self.app.world.send_event(ev);
+    }
+
+    pub fn input(&mut self, input: SimInput) {
+        self.send(SimInputEvent(input));
+    }
+
+    pub fn block(&self, pos: [u32; 3]) -> BlockType {
+        self.app
+            .world
+            .resource::<Terrain>()
+            .get_block(pos[0], pos[1], pos[2])
+            .block
+    }
+
+    /// The checksum headless runs print, to compare runs.
+    pub fn checksum(&mut self) -> u64 {
+        let transforms = self
+            .app
+            .world
+            .query_filtered::<&Transform, With<Position>>()
+            .iter(&self.app.world)
+            .collect::<Vec<_>>();
+
+        colony_checksum(self.app.world.resource::<Terrain>(), transforms.into_iter())
+    }
+
+    pub fn step(&mut self, ticks: u64) {
+        let end = self.tick() + ticks;
+
+        while self.tick() < end {
+            self.app.update();
+        }
+    }
+
+    /// Steps until `done` is true, for at most `max_ticks`. Returns whether
+    /// `done` was reached.
+    pub fn run_until(&mut self, max_ticks: u64, mut done: impl FnMut(&mut Self) -> bool) -> bool {
+        for _ in 0..max_ticks {
+            if done(self) {
+                return true;
+            }
+
+            self.step(1);
+        }
+
+        done(self)
+    }
+}
+
+fn parse_fixture(fixture: &str) -> (Terrain, Vec<([u32; 3], FixtureEntity)>) {
+    let mut layers: Vec<Vec<&str>> = vec![vec![]];
+
+    for line in fixture.lines().map(str::trim) {
+        if !line.is_empty() {
+            layers.last_mut().unwrap().push(line);
+        } else if !layers.last().unwrap().is_empty() {
+            layers.push(vec![]);
+        }
+    }
+
+    layers.retain(|l| !l.is_empty());
+
+    let size_x = layers.iter().flatten().map(|r| r.len()).max().unwrap_or(0) as u32;
+    let size_z = layers.iter().map(|l| l.len()).max().unwrap_or(0) as u32;
+    // leave room above the fixture for colonists to stand
+    let size_y = layers.len() as u32 + 2;
+
+    let mut terrain = Terrain::new(
+        size_x.div_ceil(FIXTURE_CHUNK_SIZE),
+        size_y.div_ceil(FIXTURE_CHUNK_SIZE),
+        size_z.div_ceil(FIXTURE_CHUNK_SIZE),
+        FIXTURE_CHUNK_SIZE,
+    );
+
+    for chunk_idx in 0..terrain.chunk_count {
+        terrain.init_chunk(chunk_idx);
+    }
+
+    let mut entities = vec![];
+
+    for (y, layer) in layers.iter().enumerate() {
+        for (z, row) in layer.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                let pos = [x as u32, y as u32, z as u32];
+                let block_type = match c {
+                    '#' => BlockType::STONE,
+                    'd' => BlockType::DIRT,
+                    'g' => BlockType::GRASS,
+                    'a' => BlockType::ASHLAR,
+                    'm' => BlockType::MAGMA,
+                    'w' => BlockType::WATER,
+                    _ => BlockType::EMPTY,
+                };
+
+                let entity = match c {
+                    'C' => Some(FixtureEntity::Colonist),
+                    'K' => Some(FixtureEntity::Creature(CreatureType::Cat, Some(0))),
+                    'R' => Some(FixtureEntity::Creature(CreatureType::Rabbit, None)),
+                    'H' => Some(FixtureEntity::Creature(CreatureType::Crawler, None)),
+                    'P' => Some(FixtureEntity::Pickaxe),
+                    'A' => Some(FixtureEntity::Axe),
+                    'L' => Some(FixtureEntity::Commodity(Commodity::WoodBirchLog)),
+                    'B' => Some(FixtureEntity::Commodity(Commodity::StoneShaleBoulder)),
+                    'F' => Some(FixtureEntity::Commodity(Commodity::Berries)),
+                    'T' => Some(FixtureEntity::Tree),
+                    '.' | '#' | 'd' | 'g' | 'a' | 'm' | 'w' => None,
+                    _ => panic!("unknown fixture character '{}'", c),
+                };
+
+                terrain.init_block(pos[0], pos[1], pos[2], block_type);
+
+                if let Some(entity) = entity {
+                    entities.push((pos, entity));
+                }
+            }
+        }
+    }
+
+    (terrain, entities)
+}
@@ -0,0 +1,1752 @@
+use std::task::Poll;
+
+use bevy::{
+    core::Name,
+    ecs::{
+        entity::Entity,
+        query::{With, Without},
+    },
+};
+
+use crate::{
+    colonists::{
+        Colonist, Corpse, Creature, Downed, Fatigue, HasBehavior, Health, InSlot, Inventory,
+        IsJobCancelled, IsJobCompleted, Item, ItemTag, Job, JobHaul, JobLocation, NavigationFlags,
+        Needs, PartitionPathRequest, Path, PathQueue, Skill, Skills, WorkPreference, WorkType,
+        HEALTH_MAX, HOSTILE_SPAWN_DISTANCE, HOSTILE_SPAWN_INTERVAL_S, JOB_PRIORITY_MAX, NEED_MAX,
+        PATH_SEARCH_BUDGET, SKILL_XP_PER_TASK, WORK_RANK_MIN,
+    },
+    common::Distance,
+    items::{
+        Commodity, CraftBills, Durability, InStockpile, RecipeType, SpawnCommodityEvent,
+        SpawnPickaxeEvent, Stockpile, ToolMaterial,
+    },
+    persistence::{LoadColonyEvent, PendingColonyLoad, SaveColonyEvent},
+    structures::{Bed, BlueprintType, Structure},
+    BlockRegistry, BlockType, Position, Terrain, TerrainGenerator, TimeOfDay, Tree,
+    WorldGenSettings, WorldPreset, DAY_LENGTH, HOURS_PER_DAY,
+};
+
+use super::{SimInput, TestSim, SIM_TICK_SECONDS};
+
+const HOSTILE_SPAWN_TICKS: u64 = (HOSTILE_SPAWN_INTERVAL_S / SIM_TICK_SECONDS) as u64;
+
+const FLOOR: &str = "
+    ##########
+    ##########
+    ##########
+    ##########
+    ##########
+";
+
+fn fixture(layer: &str) -> String {
+    format!("{}\n{}", FLOOR, layer)
+}
+
+fn colonist_positions(sim: &mut TestSim) -> Vec<[u32; 3]> {
+    sim.world()
+        .query_filtered::<&Position, With<Colonist>>()
+        .iter(&sim.app.world)
+        .map(|p| p.as_array())
+        .collect()
+}
+
+#[test]
+fn colonist_mines_block() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn mined_block_drops_from_its_table() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......a...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+
+    sim.step(2);
+
+    // the pickaxe's parts are slotted commodities
+    let commodities = sim
+        .world()
+        .query_filtered::<&Commodity, Without<InSlot>>()
+        .iter(&sim.app.world)
+        .copied()
+        .collect::<Vec<_>>();
+
+    assert!(commodities == vec![Commodity::StoneAshlarBlock]);
+}
+
+#[test]
+fn copper_pickaxe_mines_gold_ore() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C........
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    let gold_ore = BlockRegistry::get().find("gold ore").unwrap();
+
+    sim.send(SpawnPickaxeEvent {
+        pos: [3, 1, 1],
+        material: ToolMaterial::Copper,
+        durability: None,
+    });
+    sim.input(SimInput::SetBlocks {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+        block_type: gold_ore,
+    });
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    sim.step(1);
+    assert_eq!(sim.block([6, 1, 2]), gold_ore);
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "gold ore was not mined by tick {}",
+        sim.tick()
+    );
+
+    sim.step(2);
+
+    let gold = sim
+        .world()
+        .query_filtered::<&Commodity, Without<InSlot>>()
+        .iter(&sim.app.world)
+        .filter(|c| **c == Commodity::OreGold)
+        .count();
+
+    assert_eq!(gold, 1);
+}
+
+#[test]
+fn mining_gains_experience() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY));
+
+    let skills = *sim
+        .world()
+        .query_filtered::<&Skills, With<Colonist>>()
+        .single(&sim.app.world);
+
+    assert_eq!(skills.xp(Skill::Mining), SKILL_XP_PER_TASK);
+    assert_eq!(skills.xp(Skill::Woodcutting), 0.);
+    assert!(skills.work_speed(Skill::Mining) >= skills.work_speed(Skill::Woodcutting));
+}
+
+#[test]
+fn worn_out_pickaxe_breaks() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+
+    for mut durability in sim
+        .world()
+        .query::<&mut Durability>()
+        .iter_mut(&mut sim.app.world)
+    {
+        durability.value = 1;
+    }
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY));
+    sim.step(2);
+
+    let tools = sim
+        .world()
+        .query::<&Durability>()
+        .iter(&sim.app.world)
+        .count();
+    let parts = sim
+        .world()
+        .query_filtered::<&Commodity, With<InSlot>>()
+        .iter(&sim.app.world)
+        .count();
+
+    assert_eq!(tools, 0);
+    assert_eq!(parts, 0);
+}
+
+#[test]
+fn colonist_chops_tree() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.A......
+        ......T...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+    assert_eq!(sim.block([6, 1, 2]), BlockType::TREE_TRUNK);
+
+    sim.input(SimInput::Chop {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            sim.world().query::<&Tree>().iter(&sim.app.world).count() == 0
+        }),
+        "tree was not chopped by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn colonist_places_block() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.B......
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.send(SpawnCommodityEvent {
+        entity: None,
+        commodity: Commodity::StoneAshlarBlock,
+        position: [3, 1, 3],
+        slot_entity: None,
+        slot_idx: None,
+    });
+
+    sim.input(SimInput::PlaceBlock {
+        pos: [6, 1, 2],
+        block_type: BlockType::ASHLAR,
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::ASHLAR),
+        "block was not placed by tick {}",
+        sim.tick()
+    );
+
+    sim.step(2);
+
+    let commodities = sim
+        .world()
+        .query::<&Commodity>()
+        .iter(&sim.app.world)
+        .copied()
+        .collect::<Vec<_>>();
+
+    // the ashlar block is used up, the boulder is not
+    assert!(commodities == vec![Commodity::StoneShaleBoulder]);
+}
+
+#[test]
+fn colonist_builds_structure() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.B.B....
+        ..........
+        ##########
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::PlaceStructure {
+        blueprint_type: BlueprintType::Ladder,
+        pos: [6, 1, 2],
+        rotation: 0,
+        is_flipped: false,
+    });
+
+    assert!(
+        sim.run_until(5000, |sim| {
+            sim.world()
+                .query::<&Structure>()
+                .iter(&sim.app.world)
+                .any(|s| s.is_built())
+        }),
+        "structure was not built by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn colonist_crafts_at_workbench() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.B.B.B..
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::PlaceStructure {
+        blueprint_type: BlueprintType::Workbench,
+        pos: [6, 1, 3],
+        rotation: 0,
+        is_flipped: false,
+    });
+
+    assert!(
+        sim.run_until(8000, |sim| {
+            sim.world()
+                .query::<&CraftBills>()
+                .iter(&sim.app.world)
+                .count()
+                == 1
+        }),
+        "workbench was not built by tick {}",
+        sim.tick()
+    );
+
+    sim.input(SimInput::AddCraftBill {
+        station: [6, 1, 3],
+        recipe: RecipeType::StoneAxe,
+        count: 1,
+    });
+
+    for (commodity, position) in [
+        (Commodity::StoneShaleBoulder, [2, 1, 4]),
+        (Commodity::WoodBirchLog, [3, 1, 4]),
+    ] {
+        sim.send(SpawnCommodityEvent {
+            entity: None,
+            commodity,
+            position,
+            slot_entity: None,
+            slot_idx: None,
+        });
+    }
+
+    assert!(
+        sim.run_until(8000, |sim| {
+            sim.world()
+                .query::<&ToolMaterial>()
+                .iter(&sim.app.world)
+                .count()
+                == 1
+        }),
+        "axe was not crafted by tick {}",
+        sim.tick()
+    );
+
+    sim.step(2);
+
+    let bills = sim
+        .world()
+        .query::<&CraftBills>()
+        .single(&sim.app.world)
+        .bills
+        .len();
+
+    assert_eq!(bills, 0);
+}
+
+#[test]
+fn colonist_crafts_copper_pickaxe_from_mined_ore() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .CPB.B.B..
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    let copper_ore = BlockRegistry::get().find("copper ore").unwrap();
+
+    sim.input(SimInput::SetBlocks {
+        min: [8, 1, 1],
+        max: [8, 1, 1],
+        block_type: copper_ore,
+    });
+    sim.input(SimInput::Mine {
+        min: [8, 1, 1],
+        max: [8, 1, 1],
+    });
+    sim.input(SimInput::PlaceStructure {
+        blueprint_type: BlueprintType::Workbench,
+        pos: [6, 1, 3],
+        rotation: 0,
+        is_flipped: false,
+    });
+
+    assert!(
+        sim.run_until(8000, |sim| {
+            sim.world()
+                .query::<&CraftBills>()
+                .iter(&sim.app.world)
+                .count()
+                == 1
+        }),
+        "workbench was not built by tick {}",
+        sim.tick()
+    );
+
+    sim.input(SimInput::AddCraftBill {
+        station: [6, 1, 3],
+        recipe: RecipeType::CopperPickaxe,
+        count: 1,
+    });
+    sim.send(SpawnCommodityEvent {
+        entity: None,
+        commodity: Commodity::WoodBirchLog,
+        position: [3, 1, 4],
+        slot_entity: None,
+        slot_idx: None,
+    });
+
+    assert!(
+        sim.run_until(8000, |sim| {
+            sim.world()
+                .query::<&ToolMaterial>()
+                .iter(&sim.app.world)
+                .any(|m| *m == ToolMaterial::Copper)
+        }),
+        "copper pickaxe was not crafted by tick {}",
+        sim.tick()
+    );
+}
+
+fn fluid_volume(sim: &TestSim, fluid: BlockType) -> u32 {
+    sim.app
+        .world
+        .resource::<Terrain>()
+        .chunks
+        .iter()
+        .flat_map(|c| c.blocks.iter())
+        .filter(|b| b.block == fluid)
+        .map(|b| b.fluid_level as u32)
+        .sum()
+}
+
+#[test]
+fn water_floods_dug_tunnel() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P...#..
+        ......#w#.
+        .......#..
+        ..........
+        ",
+    ));
+
+    let volume = fluid_volume(&sim, BlockType::WATER);
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(5000, |sim| sim.block([5, 1, 2]) == BlockType::WATER),
+        "water did not flood the tunnel by tick {}",
+        sim.tick()
+    );
+
+    assert_eq!(fluid_volume(&sim, BlockType::WATER), volume);
+}
+
+#[test]
+fn magma_destroys_items() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P...#..
+        .....B#m#.
+        .......#..
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    // the pickaxe's parts are slotted commodities
+    assert!(
+        sim.run_until(8000, |sim| {
+            sim.world()
+                .query_filtered::<&Commodity, Without<InSlot>>()
+                .iter(&sim.app.world)
+                .count()
+                == 0
+        }),
+        "boulder was not destroyed by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn unsupported_blocks_cave_in() {
+    let mut sim = TestSim::new(&format!(
+        "{}\n{}",
+        fixture(
+            "
+            ..........
+            .C.P......
+            ......#B..
+            ..........
+            ..........
+            ",
+        ),
+        "
+        ..........
+        ..........
+        ......##..
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(5000, |sim| {
+            sim.block([6, 2, 2]) == BlockType::EMPTY && sim.block([7, 1, 2]) == BlockType::STONE
+        }),
+        "ceiling did not cave in by tick {}",
+        sim.tick()
+    );
+
+    sim.step(2);
+
+    // the boulder is crushed, the pickaxe's parts are slotted commodities
+    let crushed = sim
+        .world()
+        .query_filtered::<&Commodity, Without<InSlot>>()
+        .iter(&sim.app.world)
+        .all(|c| *c != Commodity::StoneShaleBoulder);
+
+    assert!(crushed);
+}
+
+#[test]
+fn colonist_paths_around_magma() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        ..........
+        .CP..m...#
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [9, 1, 2],
+        max: [9, 1, 2],
+    });
+
+    let near_magma = [[4, 1, 2], [6, 1, 2], [5, 1, 1], [5, 1, 3]];
+    let mut visited = vec![];
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            visited.extend(colonist_positions(sim));
+            sim.block([9, 1, 2]) == BlockType::EMPTY
+        }),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+
+    assert!(
+        !visited.iter().any(|p| near_magma.contains(p)),
+        "colonist walked next to the magma"
+    );
+}
+
+#[test]
+fn colonist_keeps_to_paved_row() {
+    let mut sim = TestSim::new(
+        "
+        ########################
+        aaaaaaaaaaaaaaaaaaaaaaaa
+        ########################
+
+        ........................
+        ........................
+        .C......................
+        ",
+    );
+
+    sim.step(10);
+
+    sim.input(SimInput::SetDrafted {
+        colonist: 0,
+        is_drafted: true,
+    });
+    sim.input(SimInput::OrderMove {
+        colonist: 0,
+        pos: [21, 1, 2],
+    });
+
+    let mut visited = vec![];
+
+    assert!(
+        sim.run_until(1000, |sim| {
+            visited.extend(colonist_positions(sim));
+            colonist_positions(sim) == vec![[21, 1, 2]]
+        }),
+        "colonist did not move to the ordered spot by tick {}",
+        sim.tick()
+    );
+
+    // the paved row is cheaper, so it is only left for the last step
+    assert!(
+        visited.iter().all(|p| p[2] == 1 || p[0] < 2 || p[0] > 20),
+        "colonist left the paved row early"
+    );
+}
+
+#[test]
+fn colonist_repairs_path_after_terrain_edit() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        ..........
+        .CP......#
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [9, 1, 2],
+        max: [9, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(1000, |sim| {
+            let has_path = sim
+                .world()
+                .query_filtered::<(), (With<Colonist>, With<Path>)>()
+                .iter(&sim.app.world)
+                .count()
+                > 0;
+
+            has_path && colonist_positions(sim).iter().any(|p| p[0] >= 3)
+        }),
+        "colonist did not set out by tick {}",
+        sim.tick()
+    );
+
+    // wall off the way ahead, leaving a gap along the edge
+    sim.input(SimInput::SetBlocks {
+        min: [6, 1, 0],
+        max: [6, 2, 3],
+        block_type: BlockType::STONE,
+    });
+
+    let mut last_path: Option<(Vec<[u32; 3]>, [u32; 3])> = None;
+    let mut dropped_early = false;
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            let path = sim
+                .world()
+                .query_filtered::<(&Path, &Position), With<Colonist>>()
+                .iter(&sim.app.world)
+                .map(|(path, position)| (path.goals.clone(), position.as_array()))
+                .next();
+
+            // paths are only let go of on arrival, never for being blocked
+            if let (None, Some((goals, pos))) = (&path, &last_path) {
+                dropped_early |= !goals.contains(pos);
+            }
+            last_path = path;
+
+            sim.block([9, 1, 2]) == BlockType::EMPTY
+        }),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+
+    assert!(!dropped_early, "colonist gave up on a blocked path");
+}
+
+#[test]
+fn path_searches_are_budgeted_and_shared() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        ..........
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+
+    let request = |goal: [u32; 3]| PartitionPathRequest {
+        start: [0, 1, 0],
+        goals: vec![goal],
+        flags: NavigationFlags::COLONIST,
+    };
+
+    // two actors after the same path share a search, the rest fill the budget
+    let shared = [
+        sim.world().spawn_empty().id(),
+        sim.world().spawn_empty().id(),
+    ];
+    let others: Vec<_> = (0..PATH_SEARCH_BUDGET)
+        .map(|_| sim.world().spawn_empty().id())
+        .collect();
+    let goal = |i: usize| [(i % 10) as u32, 1, (i / 10) as u32 + 1];
+
+    let mut queue = sim.world().resource_mut::<PathQueue>();
+
+    for actor in shared {
+        assert!(queue.poll_partition(actor, request([9, 1, 4])).is_pending());
+    }
+
+    for (i, actor) in others.iter().enumerate() {
+        assert!(queue.poll_partition(*actor, request(goal(i))).is_pending());
+    }
+
+    // searches start at the end of one tick and are collected on the next
+    sim.step(2);
+
+    let mut queue = sim.world().resource_mut::<PathQueue>();
+
+    for actor in shared {
+        assert!(matches!(
+            queue.poll_partition(actor, request([9, 1, 4])),
+            Poll::Ready(Some(_))
+        ));
+    }
+
+    let last = others.len() - 1;
+
+    assert!(queue
+        .poll_partition(others[last], request(goal(last)))
+        .is_pending());
+
+    sim.step(1);
+
+    let mut queue = sim.world().resource_mut::<PathQueue>();
+
+    assert!(queue
+        .poll_partition(others[last], request(goal(last)))
+        .is_ready());
+}
+
+fn creature_positions(sim: &mut TestSim) -> Vec<[u32; 3]> {
+    sim.world()
+        .query_filtered::<&Position, With<Creature>>()
+        .iter(&sim.app.world)
+        .map(|p| p.as_array())
+        .collect()
+}
+
+#[test]
+fn pet_follows_owner_through_low_gap() {
+    // the wall is two blocks high, with a one block high gap that only fits
+    // the cat
+    let mut sim = TestSim::new(&fixture(
+        "
+        ....#.....
+        ....#.....
+        .C.......K
+        ....#.....
+        ....#.....
+
+        ....#.....
+        ....#.....
+        ....#.....
+        ....#.....
+        ....#.....
+        ",
+    ));
+
+    sim.step(10);
+    assert_eq!(creature_positions(&mut sim), vec![[9, 1, 2]]);
+
+    let mut colonist_crossed = false;
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            colonist_crossed |= colonist_positions(sim).iter().any(|p| p[0] > 4);
+            creature_positions(sim).iter().any(|p| p[0] < 4)
+        }),
+        "cat did not reach its owner by tick {}",
+        sim.tick()
+    );
+
+    assert!(!colonist_crossed, "colonist squeezed through the gap");
+}
+
+#[test]
+fn wild_creature_flees_colonist() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        ..........
+        .C.R......
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(10);
+    assert_eq!(creature_positions(&mut sim), vec![[3, 1, 2]]);
+
+    assert!(
+        sim.run_until(1000, |sim| creature_positions(sim)
+            .iter()
+            .any(|p| p[0] >= 8)),
+        "rabbit did not run off by tick {}",
+        sim.tick()
+    );
+}
+
+fn colonist_health(sim: &mut TestSim) -> f32 {
+    sim.world()
+        .query_filtered::<&Health, With<Colonist>>()
+        .single(&sim.app.world)
+        .value
+}
+
+#[test]
+fn colonist_fights_off_crawler() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        ..........
+        .C......H.
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(10);
+
+    let mut was_hurt = false;
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            was_hurt |= colonist_health(sim) < HEALTH_MAX;
+            creature_positions(sim).is_empty()
+        }),
+        "crawler was not killed by tick {}",
+        sim.tick()
+    );
+
+    assert!(was_hurt, "crawler never landed a hit");
+    assert_eq!(colonist_positions(&mut sim).len(), 1);
+}
+
+#[test]
+fn colonist_works_near_walled_off_crawler() {
+    let mut sim = TestSim::new(&format!(
+        "{}\n{}\n{}",
+        fixture(
+            "
+            ..........
+            ...P.C....
+            ..#....###
+            .......#H#
+            .......###
+            ",
+        ),
+        "
+        ..........
+        ..........
+        .......###
+        .......#.#
+        .......###
+        ",
+        "
+        ..........
+        ..........
+        .......###
+        .......###
+        .......###
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [2, 1, 2],
+        max: [2, 1, 2],
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([2, 1, 2]) == BlockType::EMPTY),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+    assert_eq!(creature_positions(&mut sim), vec![[8, 1, 3]]);
+}
+
+#[test]
+fn drafted_colonist_follows_orders() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(10);
+
+    sim.input(SimInput::SetDrafted {
+        colonist: 0,
+        is_drafted: true,
+    });
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+    sim.input(SimInput::OrderMove {
+        colonist: 0,
+        pos: [8, 1, 4],
+    });
+
+    assert!(
+        sim.run_until(1000, |sim| colonist_positions(sim) == vec![[8, 1, 4]]),
+        "colonist did not move to the ordered spot by tick {}",
+        sim.tick()
+    );
+
+    // drafted colonists leave work alone and stay put
+    sim.step(600);
+    assert_eq!(colonist_positions(&mut sim), vec![[8, 1, 4]]);
+    assert_eq!(sim.block([6, 1, 2]), BlockType::STONE);
+
+    sim.input(SimInput::SetDrafted {
+        colonist: 0,
+        is_drafted: false,
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "released colonist did not mine by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn crawler_comes_out_of_the_caverns() {
+    let mut sim = TestSim::new(
+        "
+        ########################################
+        ########################################
+        ########################################
+
+        .C......................................
+        ........................................
+        ........................................
+        ",
+    );
+
+    sim.step(10);
+
+    // fixtures are not generated, crawlers only come once a generator says
+    // where the caverns are
+    let generator = TerrainGenerator::new(&WorldGenSettings::new(0, WorldPreset::Standard));
+    let cavern_level = generator.cavern_level(sim.world().resource::<Terrain>());
+    sim.world().insert_resource(generator);
+
+    let mut colonist = [0; 3];
+
+    assert!(
+        sim.run_until(HOSTILE_SPAWN_TICKS + 10, |sim| {
+            colonist = colonist_positions(sim)[0];
+            !creature_positions(sim).is_empty()
+        }),
+        "no crawler came by tick {}",
+        sim.tick()
+    );
+
+    sim.step(1);
+
+    let crawler = creature_positions(&mut sim)[0];
+    let distance = Distance::diagonal(crawler.map(|v| v as i32), colonist.map(|v| v as i32));
+
+    assert!(crawler[1] < cavern_level);
+    assert!(
+        distance >= HOSTILE_SPAWN_DISTANCE,
+        "crawler came {} blocks from the colonist",
+        distance
+    );
+}
+
+fn set_colonist_health(sim: &mut TestSim, value: f32) {
+    for mut health in sim
+        .world()
+        .query_filtered::<&mut Health, With<Colonist>>()
+        .iter_mut(&mut sim.app.world)
+    {
+        health.value = value;
+    }
+}
+
+#[test]
+fn downed_colonist_recovers_and_works() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(10);
+    set_colonist_health(&mut sim, 20.);
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    sim.step(600);
+
+    assert_eq!(
+        sim.world()
+            .query_filtered::<(), (With<Downed>, Without<HasBehavior>)>()
+            .iter(&sim.app.world)
+            .count(),
+        1,
+        "colonist was not downed"
+    );
+    assert_eq!(sim.block([6, 1, 2]), BlockType::STONE);
+
+    assert!(
+        sim.run_until(8000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "block was not mined after recovering by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn colonist_dies_in_magma() {
+    let mut sim = TestSim::new(&format!(
+        "{}\n{}",
+        fixture(
+            "
+            ..........
+            ..........
+            .....m....
+            ..........
+            ..........
+            ",
+        ),
+        "
+        ..........
+        .....#....
+        ....#C#...
+        .....#....
+        ..........
+        ",
+    ));
+
+    sim.step(10);
+    assert_eq!(colonist_positions(&mut sim), vec![[5, 2, 2]]);
+
+    assert!(
+        sim.run_until(1000, |sim| colonist_positions(sim).is_empty()),
+        "colonist did not die by tick {}",
+        sim.tick()
+    );
+
+    assert_eq!(
+        sim.world()
+            .query_filtered::<(), With<Corpse>>()
+            .iter(&sim.app.world)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn dead_colonists_tool_is_picked_up_again() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        .C........
+        ",
+    ));
+
+    sim.step(10);
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    let mut carrier = None;
+
+    assert!(
+        sim.run_until(1000, |sim| {
+            carrier = sim
+                .world()
+                .query_filtered::<(Entity, &Inventory), With<Colonist>>()
+                .iter(&sim.app.world)
+                .find(|(_, i)| !i.items.is_empty())
+                .map(|(e, _)| e);
+            carrier.is_some()
+        }),
+        "pickaxe was not picked up by tick {}",
+        sim.tick()
+    );
+
+    sim.app
+        .world
+        .get_mut::<Health>(carrier.unwrap())
+        .unwrap()
+        .value = 0.;
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+    assert_eq!(colonist_positions(&mut sim).len(), 1);
+}
+
+fn colonist_needs(sim: &mut TestSim) -> Needs {
+    *sim.world()
+        .query_filtered::<&Needs, With<Colonist>>()
+        .single(&sim.app.world)
+}
+
+fn set_colonist_needs(sim: &mut TestSim, set: impl Fn(&mut Needs)) {
+    for mut needs in sim
+        .world()
+        .query_filtered::<&mut Needs, With<Colonist>>()
+        .iter_mut(&mut sim.app.world)
+    {
+        set(&mut needs);
+    }
+}
+
+#[test]
+fn hungry_colonist_eats() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C....F...
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+    set_colonist_needs(&mut sim, |needs| needs.hunger.value = 90.);
+
+    assert!(
+        sim.run_until(3000, |sim| colonist_needs(sim).hunger.value < 50.),
+        "colonist did not eat by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn thirsty_colonist_drinks() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C........
+        ......w...
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+    set_colonist_needs(&mut sim, |needs| needs.thirst.value = 90.);
+
+    assert!(
+        sim.run_until(3000, |sim| colonist_needs(sim).thirst.value < 10.),
+        "colonist did not drink by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn starving_colonist_is_downed() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C........
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+    set_colonist_needs(&mut sim, |needs| needs.hunger.value = NEED_MAX);
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            sim.world()
+                .query_filtered::<(), (With<Colonist>, With<Downed>)>()
+                .iter(&sim.app.world)
+                .count()
+                == 1
+        }),
+        "starving colonist was not downed by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn tired_colonist_sleeps_in_bed() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.L.L....
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+    sim.input(SimInput::PlaceStructure {
+        blueprint_type: BlueprintType::Bed,
+        pos: [6, 1, 2],
+        rotation: 0,
+        is_flipped: false,
+    });
+
+    // the colonist sleeps in the bed once it is built
+    assert!(
+        sim.run_until(8000, |sim| {
+            let is_owned = sim
+                .world()
+                .query::<&Bed>()
+                .iter(&sim.app.world)
+                .any(|b| b.owner.is_some());
+
+            let fatigue = sim
+                .world()
+                .query_filtered::<&Fatigue, With<Colonist>>()
+                .single(&sim.app.world)
+                .value;
+
+            is_owned && fatigue < 5.
+        }),
+        "colonist did not sleep in a bed by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn tired_colonist_sleeps_on_the_floor_without_a_bed() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C........
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.step(1);
+
+    for mut fatigue in sim
+        .world()
+        .query_filtered::<&mut Fatigue, With<Colonist>>()
+        .iter_mut(&mut sim.app.world)
+    {
+        fatigue.value = 90.;
+    }
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            sim.world()
+                .query_filtered::<&Fatigue, With<Colonist>>()
+                .single(&sim.app.world)
+                .value
+                < 5.
+        }),
+        "colonist did not recover without a bed by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn colonist_hauls_items_to_stockpile() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.B..L...
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::CreateStockpile {
+        min: [7, 1, 3],
+        max: [8, 1, 4],
+        tags: vec![ItemTag::Stone],
+        capacity: 1,
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            sim.world()
+                .query_filtered::<&Position, With<InStockpile>>()
+                .iter(&sim.app.world)
+                .any(|p| p.x >= 7 && p.z >= 3)
+        }),
+        "boulder was not hauled by tick {}",
+        sim.tick()
+    );
+
+    // only stone is stored here, the log stays where it is
+    sim.step(600);
+
+    let logs = sim
+        .world()
+        .query_filtered::<&Position, With<Commodity>>()
+        .iter(&sim.app.world)
+        .filter(|p| p.as_array() == [6, 1, 1])
+        .count();
+
+    assert_eq!(logs, 1);
+}
+
+#[test]
+fn full_stockpile_gets_no_haul_jobs() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.B.B....
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::CreateStockpile {
+        min: [8, 1, 3],
+        max: [8, 1, 3],
+        tags: vec![ItemTag::Stone],
+        capacity: 1,
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| {
+            sim.world()
+                .query::<&InStockpile>()
+                .iter(&sim.app.world)
+                .count()
+                == 1
+        }),
+        "boulder was not hauled by tick {}",
+        sim.tick()
+    );
+
+    sim.step(600);
+
+    let stored = sim
+        .world()
+        .query::<&InStockpile>()
+        .iter(&sim.app.world)
+        .count();
+    let haul_jobs = sim.world().query::<&JobHaul>().iter(&sim.app.world).count();
+
+    assert_eq!(stored, 1);
+    assert_eq!(haul_jobs, 0);
+}
+
+#[test]
+fn colonist_mines_urgent_job_first() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ..#.......
+        ..........
+        .........#
+        ",
+    ));
+
+    sim.input(SimInput::Mine {
+        min: [0, 1, 0],
+        max: [9, 1, 4],
+    });
+    sim.input(SimInput::SetJobPriority {
+        min: [9, 1, 4],
+        max: [9, 1, 4],
+        priority: JOB_PRIORITY_MAX,
+    });
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([9, 1, 4]) == BlockType::EMPTY),
+        "urgent block was not mined by tick {}",
+        sim.tick()
+    );
+    assert_eq!(sim.block([2, 1, 2]), BlockType::STONE);
+}
+
+#[test]
+fn colonist_works_during_work_hours() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    // evenings are for recreation
+    sim.world().resource_mut::<TimeOfDay>().elapsed = DAY_LENGTH * 19. / HOURS_PER_DAY as f32;
+
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    sim.step(1200);
+    assert_eq!(sim.block([6, 1, 2]), BlockType::STONE);
+
+    sim.world().resource_mut::<TimeOfDay>().elapsed = DAY_LENGTH * 8. / HOURS_PER_DAY as f32;
+
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 2]) == BlockType::EMPTY),
+        "block was not mined during work hours by tick {}",
+        sim.tick()
+    );
+}
+
+#[test]
+fn colonist_skips_disabled_work() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P......
+        ......#...
+        ..........
+        ..........
+        ",
+    ));
+
+    // the colonist is spawned on the first tick
+    sim.step(1);
+    sim.input(SimInput::SetWorkPreference {
+        colonist: 0,
+        work_type: WorkType::Mine,
+        preference: WorkPreference {
+            is_enabled: false,
+            rank: WORK_RANK_MIN,
+        },
+    });
+    sim.input(SimInput::Mine {
+        min: [6, 1, 2],
+        max: [6, 1, 2],
+    });
+
+    sim.step(1200);
+    assert_ne!(sim.block([6, 1, 2]), BlockType::EMPTY);
+}
+
+#[test]
+fn same_seed_same_colony() {
+    let colony = fixture(
+        "
+        ..........
+        .C......C.
+        ..........
+        ....C.....
+        ..........
+        ",
+    );
+
+    let mut a = TestSim::with_seed(&colony, 7);
+    let mut b = TestSim::with_seed(&colony, 7);
+
+    a.step(600);
+    b.step(600);
+
+    assert_eq!(colonist_positions(&mut a), colonist_positions(&mut b));
+}
+
+const CHECKSUM_CHILD_VAR: &str = "COLONY_CHECKSUM_CHILD";
+
+/// Runs in separate processes, so anything seeded per process, like hash
+/// set iteration, shows up as a different checksum.
+#[test]
+fn same_seed_same_colony_across_processes() {
+    if std::env::var(CHECKSUM_CHILD_VAR).is_ok() {
+        let mut sim = TestSim::with_seed(
+            &fixture(
+                "
+                ..........
+                .C.P.P..C.
+                ......#...
+                .F..L.....
+                ....C.#...
+                ",
+            ),
+            7,
+        );
+
+        sim.input(SimInput::Mine {
+            min: [6, 1, 2],
+            max: [6, 1, 4],
+        });
+
+        sim.step(1200);
+        println!("checksum={:016x}", sim.checksum());
+        return;
+    }
+
+    let checksum = || {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "simulation::tests::same_seed_same_colony_across_processes",
+                "--nocapture",
+                "--test-threads=1",
+            ])
+            .env(CHECKSUM_CHILD_VAR, "1")
+            .output()
+            .unwrap();
+
+        assert!(output.status.success(), "child run failed");
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|l| l.starts_with("checksum="))
+            .map(String::from)
+            .expect("child run printed no checksum")
+    };
+
+    assert_eq!(checksum(), checksum());
+}
+
+/// What a save is expected to keep, sorted so the order entities were
+/// spawned in does not matter.
+struct ColonySnapshot {
+    blocks: Vec<BlockType>,
+    colonists: Vec<(u32, [u32; 3])>,
+    items: Vec<(String, [u32; 3])>,
+    stockpiles: Vec<Vec<[u32; 3]>>,
+    jobs: Vec<(String, [u32; 3])>,
+}
+
+fn colony_snapshot(sim: &mut TestSim) -> ColonySnapshot {
+    let terrain = sim.app.world.resource::<Terrain>();
+    let mut blocks = vec![];
+
+    for x in 0..terrain.world_size_x() {
+        for y in 0..terrain.world_size_y() {
+            for z in 0..terrain.world_size_z() {
+                blocks.push(terrain.get_block(x, y, z).block);
+            }
+        }
+    }
+
+    let mut colonists = sim
+        .world()
+        .query::<(&Colonist, &Position)>()
+        .iter(&sim.app.world)
+        .map(|(c, p)| (c.id, p.as_array()))
+        .collect::<Vec<_>>();
+    let mut items = sim
+        .world()
+        .query_filtered::<(&Name, &Position), With<Item>>()
+        .iter(&sim.app.world)
+        .map(|(n, p)| (n.to_string(), p.as_array()))
+        .collect::<Vec<_>>();
+    let mut stockpiles = sim
+        .world()
+        .query::<&Stockpile>()
+        .iter(&sim.app.world)
+        .map(|s| s.tiles.clone())
+        .collect::<Vec<_>>();
+    let mut jobs = sim
+        .world()
+        .query_filtered::<(&Job, &JobLocation), (Without<IsJobCancelled>, Without<IsJobCompleted>)>(
+        )
+        .iter(&sim.app.world)
+        .map(|(j, l)| (format!("{:?}", j.job_type), l.primary_target))
+        .collect::<Vec<_>>();
+
+    colonists.sort();
+    items.sort();
+    stockpiles.sort();
+    jobs.sort();
+
+    ColonySnapshot {
+        blocks,
+        colonists,
+        items,
+        stockpiles,
+        jobs,
+    }
+}
+
+#[test]
+fn saved_colony_loads_into_a_fresh_app() {
+    let mut sim = TestSim::new(&fixture(
+        "
+        ..........
+        .C.P..L...
+        ..........
+        ......#.#.
+        ..........
+        ",
+    ));
+
+    sim.input(SimInput::CreateStockpile {
+        min: [1, 1, 4],
+        max: [3, 1, 4],
+        tags: vec![ItemTag::Log],
+        capacity: 1,
+    });
+    sim.input(SimInput::Mine {
+        min: [6, 1, 3],
+        max: [8, 1, 3],
+    });
+
+    // save with the second block still to be mined
+    assert!(
+        sim.run_until(3000, |sim| sim.block([6, 1, 3]) == BlockType::EMPTY),
+        "block was not mined by tick {}",
+        sim.tick()
+    );
+    sim.step(2);
+    assert_eq!(sim.block([8, 1, 3]), BlockType::STONE);
+
+    let path = std::env::temp_dir().join(format!("colony_round_trip_{}.sav", std::process::id()));
+
+    sim.send(SaveColonyEvent { path: path.clone() });
+    sim.step(1);
+
+    let saved = colony_snapshot(&mut sim);
+
+    let mut loaded = TestSim::new(&fixture(
+        "
+        ..........
+        ..........
+        ..........
+        ..........
+        ..........
+        ",
+    ));
+
+    loaded.send(LoadColonyEvent { path: path.clone() });
+
+    assert!(
+        loaded.run_until(100, |sim| sim
+            .world()
+            .get_resource::<PendingColonyLoad>()
+            .is_none()
+            && sim.tick() > 1),
+        "colony was not loaded by tick {}",
+        loaded.tick()
+    );
+
+    std::fs::remove_file(&path).unwrap();
+
+    let snapshot = colony_snapshot(&mut loaded);
+
+    assert!(snapshot.blocks == saved.blocks, "loaded terrain differs");
+    assert_eq!(snapshot.colonists, saved.colonists);
+    assert_eq!(snapshot.items, saved.items);
+    assert_eq!(snapshot.stockpiles, saved.stockpiles);
+    assert_eq!(snapshot.jobs, saved.jobs);
+
+    // the loaded colonist picks the job back up
+    assert!(
+        loaded.run_until(3000, |sim| sim.block([8, 1, 3]) == BlockType::EMPTY),
+        "loaded job was not finished by tick {}",
+        loaded.tick()
+    );
+}
@@ -8,6 +8,7 @@ use bevy::{
     render::{mesh::Mesh, texture::Image},
     utils::HashMap,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::colonists::{ItemTag, NavigationFlags};
 
@@ -62,13 +63,14 @@ pub struct BlueprintTile {
     pub position: [i32; 3],
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum BlueprintType {
     Workbench,
     Ladder,
     TorchStanding,
     TorchWall,
     Door,
+    Bed,
 }
 
 #[derive(Clone)]
@@ -87,6 +89,7 @@ pub struct Blueprint {
     pub name: String,
     pub center: [u32; 3],
     pub tiles: Vec<BlueprintTile>,
+    #[allow(dead_code)]
     pub texture: Option<Handle<Image>>,
     pub mesh: Handle<Mesh>,
     pub slots: BuildSlots,
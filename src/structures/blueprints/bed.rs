@@ -0,0 +1,84 @@
+use bevy::{
+    asset::AssetServer,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        system::{Commands, Res},
+    },
+};
+
+use crate::{
+    colonists::{ItemTag, NavigationFlags},
+    structures::{
+        Blueprint, BlueprintTile, BlueprintType, Blueprints, BuildSlot, BuildSlots,
+        BuiltStructureEvent, TileRequirement,
+    },
+};
+
+use bevy::ecs::system::ResMut;
+
+/// A built bed. The first colonist to sleep in it owns it, and it is
+/// reserved while a colonist is on their way to it or asleep.
+#[derive(Component, Default)]
+pub struct Bed {
+    pub owner: Option<Entity>,
+    pub reserved: Option<Entity>,
+}
+
+impl Bed {
+    pub fn is_available(&self, actor: Entity) -> bool {
+        self.owner.is_none_or(|o| o == actor) && self.reserved.is_none_or(|r| r == actor)
+    }
+}
+
+pub fn setup_blueprint_bed(mut blueprints: ResMut<Blueprints>, asset_server: Res<AssetServer>) {
+    blueprints.0.insert(
+        BlueprintType::Bed,
+        Blueprint {
+            name: "Bed".to_string(),
+            slots: BuildSlots {
+                slot_0: Some(BuildSlot {
+                    flags: vec![ItemTag::Log],
+                }),
+                slot_1: Some(BuildSlot {
+                    flags: vec![ItemTag::Log],
+                }),
+                slot_2: None,
+            },
+            center: [0, 0, 0],
+            tiles: vec![
+                // where the colonist lies down
+                BlueprintTile {
+                    position: [0, 0, 0],
+                    hotspot: None,
+                    requirements: TileRequirement::IS_WALKABLE | TileRequirement::IS_EMPTY,
+                    nav_flags: NavigationFlags::NONE,
+                    is_blocker: false,
+                    is_occupied: true,
+                },
+                BlueprintTile {
+                    position: [0, 0, 1],
+                    hotspot: None,
+                    requirements: TileRequirement::IS_WALKABLE | TileRequirement::IS_EMPTY,
+                    nav_flags: NavigationFlags::NONE,
+                    is_blocker: false,
+                    is_occupied: true,
+                },
+            ],
+            texture: None,
+            mesh: asset_server.load("bigbench.gltf#Mesh0/Primitive0"),
+        },
+    );
+}
+
+pub fn setup_structure_bed(
+    mut cmd: Commands,
+    mut ev_built_structure: EventReader<BuiltStructureEvent>,
+) {
+    for ev in ev_built_structure.read() {
+        if matches!(ev.blueprint_type, BlueprintType::Bed) {
+            cmd.entity(ev.entity).insert(Bed::default());
+        }
+    }
+}
@@ -1,8 +1,10 @@
+mod bed;
 mod door;
 mod ladder;
 mod torches;
 mod workbench;
 
+pub use bed::*;
 pub use door::*;
 pub use ladder::*;
 pub use torches::*;
@@ -1,10 +1,19 @@
-use bevy::{asset::AssetServer, ecs::system::Res};
+use bevy::{
+    asset::AssetServer,
+    ecs::{
+        entity::Entity,
+        event::EventReader,
+        query::With,
+        system::{Commands, Query, Res},
+    },
+};
 
 use crate::{
     colonists::{ItemTag, NavigationFlags},
+    items::CraftBills,
     structures::{
         Blueprint, BlueprintHotspot, BlueprintTile, BlueprintType, Blueprints, BuildSlot,
-        BuildSlots, DirectionSimple, TileRequirement,
+        BuildSlots, BuiltStructureEvent, DirectionSimple, TileRequirement,
     },
 };
 
@@ -93,3 +102,16 @@ pub fn setup_blueprint_workbench(
         },
     );
 }
+
+/// Built workbenches take craft bills. Bills restored from a save are kept.
+pub fn setup_structure_workbench(
+    mut cmd: Commands,
+    mut ev_built_structure: EventReader<BuiltStructureEvent>,
+    q_bills: Query<Entity, With<CraftBills>>,
+) {
+    for ev in ev_built_structure.read() {
+        if matches!(ev.blueprint_type, BlueprintType::Workbench) && !q_bills.contains(ev.entity) {
+            cmd.entity(ev.entity).insert(CraftBills::default());
+        }
+    }
+}
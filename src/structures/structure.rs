@@ -34,6 +34,7 @@ pub struct StructureGuide {
     pub is_hotspot: bool,
 }
 
+#[derive(Clone, Copy)]
 pub enum StructureMode {
     Placing,
     Placed,
@@ -154,6 +155,9 @@ pub struct SpawnStructureEvent {
     pub pos: [u32; 3],
     pub entity: Entity,
     pub blueprint_type: BlueprintType,
+    pub rotation: u8,
+    pub is_flipped: bool,
+    pub mode: StructureMode,
 }
 
 #[derive(Event)]
@@ -267,6 +271,8 @@ pub fn on_spawn_structure(
             })
             .collect::<Vec<_>>();
 
+        let is_placing = matches!(ev.mode, StructureMode::Placing);
+
         cmd.entity(ev.entity).insert((
             Name::new(blueprint.name.clone()),
             Inspectable {
@@ -275,14 +281,14 @@ pub fn on_spawn_structure(
             Structure {
                 position: ev.pos,
                 is_valid: false,
-                is_dirty: false,
+                is_dirty: !is_placing,
                 is_hotspots_valid: false,
                 blueprint_type: ev.blueprint_type,
                 guides,
-                rotation: 0,
-                is_flipped: true,
+                rotation: ev.rotation,
+                is_flipped: ev.is_flipped,
                 tiles: vec![],
-                mode: StructureMode::Placing,
+                mode: ev.mode,
             },
             PartSlots::from_build_slots(&blueprint.slots),
             Position::default(),
@@ -294,7 +300,10 @@ pub fn on_spawn_structure(
                     ev.pos[1] as f32,
                     ev.pos[2] as f32 + 0.5,
                 ),
-                visibility: Visibility::Hidden,
+                visibility: match is_placing {
+                    true => Visibility::Hidden,
+                    false => Visibility::Inherited,
+                },
                 ..default()
             },
         ));
@@ -547,6 +556,12 @@ pub fn check_structures(
             ev_remove_structure.send(RemoveStructureEvent { entity });
         }
 
+        // structures being positioned by the player are a preview only, and
+        // must not affect the simulation
+        if matches!(structure.mode, StructureMode::Placing) {
+            continue;
+        }
+
         // set tiles affecting terrain
         for tile in structure.tiles.iter() {
             let [x, y, z] = tile.position;
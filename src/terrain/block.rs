@@ -1,3 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+use super::{BlockDefinition, BlockFace, BlockRegistry, FluidDefinition, FLUID_LEVEL_SHALLOW};
+
 #[derive(Copy, Clone, Hash, Debug, PartialEq, Eq)]
 pub struct Block {
     pub block: BlockType,
@@ -6,6 +10,8 @@ pub struct Block {
     pub partition_id: Option<u32>,
     pub flag_mine: bool,
     pub flag_chop: bool,
+    /// how full a fluid block is, 0 for any other block
+    pub fluid_level: u8,
 }
 
 impl Default for Block {
@@ -17,6 +23,7 @@ impl Default for Block {
             partition_id: None,
             flag_mine: false,
             flag_chop: false,
+            fluid_level: 0,
         }
     }
 }
@@ -29,6 +36,7 @@ impl Block {
         partition_id: None,
         flag_mine: false,
         flag_chop: false,
+        fluid_level: 0,
     };
 
     pub fn is_oob(&self) -> bool {
@@ -40,17 +48,16 @@ impl Block {
     }
 
     pub fn is_walkable(&self) -> bool {
-        !matches!(
-            self.block,
-            BlockType::OOB | BlockType::EMPTY | BlockType::MAGMA | BlockType::LEAVES
-        )
+        self.block.definition().is_walkable
+    }
+
+    /// Cost of walking on top of the block.
+    pub fn walk_cost(&self) -> f32 {
+        self.block.definition().walk_cost
     }
 
     pub fn is_attachable(&self) -> bool {
-        !matches!(
-            self.block,
-            BlockType::OOB | BlockType::EMPTY | BlockType::MAGMA | BlockType::LEAVES
-        )
+        self.block.definition().is_attachable
     }
 
     pub fn is_empty(&self) -> bool {
@@ -58,71 +65,49 @@ impl Block {
     }
 
     pub fn is_opaque(&self) -> bool {
-        match self.block {
-            BlockType::OOB => true,
-            BlockType::EMPTY => false,
-            _ => true,
-        }
+        !self.block.definition().is_transparent
     }
 
     pub fn get_light_level(&self) -> u8 {
-        match self.block {
-            BlockType::LAMP => 12,
-            BlockType::MAGMA => 6,
-            _ => 0,
-        }
+        self.block.get_light_level()
     }
 
     pub fn is_light(&self) -> bool {
         self.get_light_level() > 0
     }
 
+    pub fn is_fluid(&self) -> bool {
+        self.block.fluid().is_some()
+    }
+
+    /// Shallow enough, and harmless enough, to walk through.
+    pub fn is_wadeable(&self) -> bool {
+        self.block
+            .fluid()
+            .is_some_and(|f| !f.is_destructive && self.fluid_level <= FLUID_LEVEL_SHALLOW)
+    }
+
+    /// Holds up the block above it, out of bounds counts so the world does
+    /// not fall out of its bottom.
+    pub fn is_supportive(&self) -> bool {
+        !self.is_empty() && !self.is_fluid()
+    }
+
     pub fn is_mineable(&self) -> bool {
-        matches!(
-            self.block,
-            BlockType::DIRT
-                | BlockType::GRASS
-                | BlockType::STONE
-                | BlockType::ASHLAR
-                | BlockType::ASHLAR_LARGE
-        )
-    }
-
-    pub fn texture_idx(&self) -> u32 {
-        match self.block {
-            BlockType::DIRT => 1,
-            BlockType::GRASS => 2,
-            BlockType::STONE => 3,
-            BlockType::ASHLAR_LARGE => 4,
-            BlockType::ASHLAR => 5,
-            BlockType::MAGMA => 6,
-            BlockType::LAMP => 8,
-            BlockType::LEAVES => 40,
-            BlockType::TREE_TRUNK => 32,
-            _ => 0,
-        }
+        self.block.definition().hardness.is_some()
     }
 
-    #[allow(dead_code)]
-    pub fn name(&self) -> String {
-        match self.block {
-            BlockType::OOB => String::from("out of bounds"),
-            BlockType::EMPTY => String::from("empty"),
-            BlockType::DIRT => String::from("dirt"),
-            BlockType::GRASS => String::from("grass"),
-            BlockType::STONE => String::from("stone"),
-            BlockType::LAMP => String::from("lamp"),
-            BlockType::MAGMA => String::from("magma"),
-            BlockType::ASHLAR_LARGE => String::from("ashlar (large)"),
-            BlockType::ASHLAR => String::from("ashlar"),
-            _ => String::from("unknown"),
-        }
+    pub fn texture_idx(&self, face: BlockFace) -> u32 {
+        self.block.definition().texture_idx(face)
     }
 }
 
-#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockType(pub u8);
 
+/// Ids of the blocks the code refers to directly. Their properties, and any
+/// other blocks, are defined in `BLOCK_REGISTRY_PATH`.
+#[allow(dead_code)]
 impl BlockType {
     pub const OOB: Self = Self(0);
     pub const EMPTY: Self = Self(1);
@@ -135,36 +120,30 @@ impl BlockType {
     pub const ASHLAR: Self = Self(8);
     pub const TREE_TRUNK: Self = Self(10);
     pub const LEAVES: Self = Self(11);
+    pub const WATER: Self = Self(15);
+    pub const SAND: Self = Self(16);
+    pub const SNOW: Self = Self(17);
 }
 
 impl BlockType {
+    pub fn definition(&self) -> &'static BlockDefinition {
+        BlockRegistry::get().definition(*self)
+    }
+
     pub fn get_light_level(&self) -> u8 {
-        match *self {
-            Self::LAMP => 12,
-            Self::MAGMA => 6,
-            _ => 0,
-        }
+        self.definition().light
+    }
+
+    pub fn fluid(&self) -> Option<&'static FluidDefinition> {
+        self.definition().fluid.as_ref()
     }
 
     pub fn is_light(&self) -> bool {
         self.get_light_level() > 0
     }
 
-    pub fn name(&self) -> String {
-        match *self {
-            Self::OOB => String::from("out of bounds"),
-            Self::EMPTY => String::from("empty"),
-            Self::DIRT => String::from("dirt"),
-            Self::GRASS => String::from("grass"),
-            Self::STONE => String::from("stone"),
-            Self::LAMP => String::from("lamp"),
-            Self::MAGMA => String::from("magma"),
-            Self::ASHLAR_LARGE => String::from("ashlar (large)"),
-            Self::ASHLAR => String::from("ashlar"),
-            Self::TREE_TRUNK => String::from("trunk"),
-            Self::LEAVES => String::from("leaves"),
-            _ => String::from("unknown"),
-        }
+    pub fn name(&self) -> &'static str {
+        &self.definition().name
     }
 }
 
@@ -0,0 +1,205 @@
+use std::{fs, path::Path, sync::OnceLock};
+
+use bevy::asset::io::file::FileAssetReader;
+use serde::Deserialize;
+
+use crate::items::{Commodity, ToolMaterial};
+
+use super::{BlockFace, BlockType};
+
+pub const BLOCK_REGISTRY_PATH: &str = "assets/blocks.ron";
+
+/// The blocks the binary was built with, for when it runs without its assets
+/// folder next to it.
+pub const BUILTIN_BLOCKS: &str = include_str!("../../assets/blocks.ron");
+
+static BLOCK_REGISTRY: OnceLock<BlockRegistry> = OnceLock::new();
+
+#[derive(Deserialize, Clone, Copy, Default)]
+pub struct BlockTextures {
+    pub top: u32,
+    pub side: u32,
+    pub bottom: u32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct BlockDrop {
+    pub commodity: Commodity,
+    pub chance: f32,
+    /// inclusive range of how many drop
+    #[serde(default = "default_drop_count")]
+    pub count: (u32, u32),
+    /// weakest tool material that gets the drop, any or no tool if `None`
+    #[serde(default)]
+    pub tool: Option<ToolMaterial>,
+}
+
+fn default_drop_count() -> (u32, u32) {
+    (1, 1)
+}
+
+fn default_walk_cost() -> f32 {
+    1.
+}
+
+impl BlockDrop {
+    pub fn is_tool_sufficient(&self, tool: Option<ToolMaterial>) -> bool {
+        match self.tool {
+            Some(required) => tool.is_some_and(|t| t >= required),
+            None => true,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct FluidDefinition {
+    /// fluid steps between each flow, higher flows slower
+    pub viscosity: u32,
+    /// destroys items it flows over
+    #[serde(default)]
+    pub is_destructive: bool,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct BlockDefinition {
+    pub id: u8,
+    pub name: String,
+    #[serde(default)]
+    pub textures: BlockTextures,
+    /// seconds to mine, or `None` if the block cannot be mined
+    #[serde(default)]
+    pub hardness: Option<f32>,
+    #[serde(default)]
+    pub light: u8,
+    #[serde(default)]
+    pub is_walkable: bool,
+    #[serde(default)]
+    pub is_attachable: bool,
+    #[serde(default)]
+    pub is_transparent: bool,
+    /// shown in the block toolbar
+    #[serde(default)]
+    pub is_placeable: bool,
+    #[serde(default)]
+    pub drops: Vec<BlockDrop>,
+    /// hauled to and used up by placing the block, placed for free if `None`
+    #[serde(default)]
+    pub material: Option<Commodity>,
+    #[serde(default)]
+    pub fluid: Option<FluidDefinition>,
+    /// cost of walking on top of the block, relative to plain ground
+    #[serde(default = "default_walk_cost")]
+    pub walk_cost: f32,
+}
+
+impl BlockDefinition {
+    pub fn texture_idx(&self, face: BlockFace) -> u32 {
+        match face {
+            BlockFace::PosY => self.textures.top,
+            BlockFace::NegY => self.textures.bottom,
+            _ => self.textures.side,
+        }
+    }
+}
+
+/// Properties of every block type, loaded once from `BLOCK_REGISTRY_PATH`,
+/// or `BUILTIN_BLOCKS` if there is no such file. Blocks are copied around
+/// the terrain code without access to the world, so the registry is global
+/// rather than a resource.
+pub struct BlockRegistry {
+    definitions: Vec<Option<BlockDefinition>>,
+    unknown: BlockDefinition,
+}
+
+impl BlockRegistry {
+    /// Panics if the registry cannot be loaded, `init` reports it instead.
+    pub fn get() -> &'static BlockRegistry {
+        Self::init().unwrap_or_else(|e| panic!("Could not load blocks: {}", e))
+    }
+
+    pub fn init() -> Result<&'static BlockRegistry, String> {
+        if let Some(registry) = BLOCK_REGISTRY.get() {
+            return Ok(registry);
+        }
+
+        let path = FileAssetReader::get_base_path().join(BLOCK_REGISTRY_PATH);
+
+        let registry = if path.exists() {
+            Self::load(&path).map_err(|e| format!("{}: {}", path.display(), e))?
+        } else {
+            Self::from_ron(BUILTIN_BLOCKS).map_err(|e| format!("built in blocks: {}", e))?
+        };
+
+        Ok(BLOCK_REGISTRY.get_or_init(|| registry))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let src = fs::read_to_string(path).map_err(|e| e.to_string())?;
+
+        Self::from_ron(&src)
+    }
+
+    pub fn from_ron(src: &str) -> Result<Self, String> {
+        let list: Vec<BlockDefinition> = ron::from_str(src).map_err(|e| e.to_string())?;
+        let mut definitions: Vec<Option<BlockDefinition>> = vec![None; 256];
+
+        for definition in list {
+            if definitions
+                .iter()
+                .flatten()
+                .any(|d| d.name == definition.name)
+            {
+                return Err(format!("duplicate block name \"{}\"", definition.name));
+            }
+
+            let slot = &mut definitions[definition.id as usize];
+
+            if slot.is_some() {
+                return Err(format!("duplicate block id {}", definition.id));
+            }
+
+            *slot = Some(definition);
+        }
+
+        for required in [BlockType::OOB, BlockType::EMPTY] {
+            if definitions[required.0 as usize].is_none() {
+                return Err(format!("missing required block id {}", required.0));
+            }
+        }
+
+        Ok(Self {
+            definitions,
+            unknown: BlockDefinition {
+                id: 0,
+                name: "unknown".to_string(),
+                textures: BlockTextures::default(),
+                hardness: None,
+                light: 0,
+                is_walkable: false,
+                is_attachable: false,
+                is_transparent: false,
+                is_placeable: false,
+                drops: vec![],
+                material: None,
+                fluid: None,
+                walk_cost: default_walk_cost(),
+            },
+        })
+    }
+
+    pub fn definition(&self, block_type: BlockType) -> &BlockDefinition {
+        self.definitions[block_type.0 as usize]
+            .as_ref()
+            .unwrap_or(&self.unknown)
+    }
+
+    pub fn find(&self, name: &str) -> Option<BlockType> {
+        self.iter()
+            .find(|d| d.name == name)
+            .map(|d| BlockType(d.id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BlockDefinition> {
+        self.definitions.iter().flatten()
+    }
+}
@@ -2,11 +2,14 @@ use bevy::{
     asset::Handle,
     ecs::{component::Component, entity::Entity},
     render::mesh::Mesh,
-    utils::hashbrown::{HashMap, HashSet},
 };
 use ndshape::{AbstractShape, RuntimeShape};
 
-use crate::{colonists::NavigationFlags, Block, BlockType};
+use crate::{
+    colonists::NavigationFlags,
+    common::{StableHashMap, StableHashSet},
+    Block, BlockType, FLUID_LEVEL_MAX,
+};
 
 #[derive(Component)]
 pub struct ChunkLayerMesh {
@@ -15,20 +18,32 @@ pub struct ChunkLayerMesh {
     pub mesh_handle: Handle<Mesh>,
 }
 
+/// Only resident chunks have their blocks in memory. The entities in a chunk
+/// stay in the world while it is paged out, so they are indexed sparsely and
+/// kept with the chunk.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChunkState {
+    Ungenerated,
+    Resident,
+    PagedOut,
+}
+
 #[derive(Clone)]
 pub struct Chunk {
     pub shape: RuntimeShape<u32, 3>,
+    pub state: ChunkState,
+    /// empty unless the chunk is resident
     pub blocks: Box<[Block]>,
-    pub items: Box<[HashSet<Entity>]>,
-    pub trees: Box<[HashSet<Entity>]>,
-    pub structures: Box<[HashMap<Entity, StructureTileDetail>]>,
-    pub lamps: Box<[HashMap<Entity, LampDetail>]>,
+    pub items: StableHashMap<u32, StableHashSet<Entity>>,
+    pub trees: StableHashMap<u32, StableHashSet<Entity>>,
+    pub structures: StableHashMap<u32, StableHashMap<Entity, StructureTileDetail>>,
+    pub lamps: StableHashMap<u32, StableHashMap<Entity, LampDetail>>,
     pub chunk_idx: u32,
     pub chunk_size: u32,
     pub world_x: u32,
     pub world_y: u32,
     pub world_z: u32,
-    pub dirty_layers: HashSet<u32>,
+    pub dirty_layers: StableHashSet<u32>,
     pub is_nav_dirty: bool,
 }
 
@@ -48,22 +63,42 @@ pub struct StructureTileDetail {
 impl Chunk {
     pub fn new(shape: RuntimeShape<u32, 3>) -> Self {
         Self {
-            blocks: vec![Block::default(); shape.size() as usize].into_boxed_slice(),
-            items: vec![HashSet::new(); shape.size() as usize].into_boxed_slice(),
-            trees: vec![HashSet::new(); shape.size() as usize].into_boxed_slice(),
-            structures: vec![HashMap::new(); shape.size() as usize].into_boxed_slice(),
-            lamps: vec![HashMap::new(); shape.size() as usize].into_boxed_slice(),
+            state: ChunkState::Ungenerated,
+            blocks: Box::new([]),
+            items: StableHashMap::default(),
+            trees: StableHashMap::default(),
+            structures: StableHashMap::default(),
+            lamps: StableHashMap::default(),
             shape,
             chunk_idx: 0,
             chunk_size: 0,
             world_x: 0,
             world_y: 0,
             world_z: 0,
-            dirty_layers: HashSet::new(),
+            dirty_layers: StableHashSet::default(),
             is_nav_dirty: true,
         }
     }
 
+    pub fn is_resident(&self) -> bool {
+        self.state == ChunkState::Resident
+    }
+
+    /// Allocates empty blocks for the chunk to be generated or paged into.
+    pub fn allocate(&mut self) {
+        if !self.is_resident() {
+            self.blocks = vec![Block::default(); self.shape.size() as usize].into_boxed_slice();
+            self.state = ChunkState::Resident;
+        }
+    }
+
+    /// Frees the blocks of a chunk that has been written to a page.
+    pub fn page_out(&mut self) {
+        self.blocks = Box::new([]);
+        self.dirty_layers.clear();
+        self.state = ChunkState::PagedOut;
+    }
+
     pub fn is_y_dirty(&self, local_y: &u32) -> bool {
         self.dirty_layers.contains(local_y)
     }
@@ -82,11 +117,35 @@ impl Chunk {
     }
 
     pub fn set_block_type(&mut self, block_idx: u32, value: BlockType) {
-        self.blocks[block_idx as usize].block = value;
+        let Some(block) = self.blocks.get_mut(block_idx as usize) else {
+            return;
+        };
+
+        block.block = value;
+        block.fluid_level = if value.fluid().is_some() {
+            FLUID_LEVEL_MAX
+        } else {
+            0
+        };
         self.set_block_dirty(block_idx);
         self.is_nav_dirty = true;
     }
 
+    /// Navigation only changes when the fluid rises above or falls below
+    /// wading depth.
+    pub fn set_fluid_level(&mut self, block_idx: u32, value: u8) {
+        let Some(block) = self.blocks.get_mut(block_idx as usize) else {
+            return;
+        };
+
+        let was_wadeable = block.is_wadeable();
+        block.fluid_level = value;
+
+        if block.is_wadeable() != was_wadeable {
+            self.is_nav_dirty = true;
+        }
+    }
+
     pub fn get_block(&self, block_idx: u32) -> Block {
         if let Some(block) = self.blocks.get(block_idx as usize) {
             return *block;
@@ -96,47 +155,51 @@ impl Chunk {
     }
 
     pub fn add_item(&mut self, block_idx: u32, item: Entity) {
-        if let Some(items) = self.items.get_mut(block_idx as usize) {
-            items.insert(item);
-        }
+        self.items.entry(block_idx).or_default().insert(item);
     }
 
     pub fn remove_item(&mut self, block_idx: u32, item: &Entity) -> bool {
-        if let Some(items) = self.items.get_mut(block_idx as usize) {
-            return items.remove(item);
+        let Some(items) = self.items.get_mut(&block_idx) else {
+            return false;
+        };
+
+        let is_removed = items.remove(item);
+
+        if items.is_empty() {
+            self.items.remove(&block_idx);
         }
 
-        false
+        is_removed
     }
 
-    pub fn get_trees(&self, block_idx: u32) -> HashSet<Entity> {
-        if let Some(trees) = self.trees.get(block_idx as usize) {
-            return trees.clone();
-        }
+    pub fn get_items(&self, block_idx: u32) -> StableHashSet<Entity> {
+        self.items.get(&block_idx).cloned().unwrap_or_default()
+    }
 
-        HashSet::new()
+    pub fn get_trees(&self, block_idx: u32) -> StableHashSet<Entity> {
+        self.trees.get(&block_idx).cloned().unwrap_or_default()
     }
 
     pub fn add_tree(&mut self, block_idx: u32, tree: Entity) {
-        if let Some(trees) = self.trees.get_mut(block_idx as usize) {
-            trees.insert(tree);
-        }
+        self.trees.entry(block_idx).or_default().insert(tree);
     }
 
     pub fn remove_tree(&mut self, block_idx: u32, tree: &Entity) -> bool {
-        if let Some(trees) = self.trees.get_mut(block_idx as usize) {
-            return trees.remove(tree);
-        }
+        let Some(trees) = self.trees.get_mut(&block_idx) else {
+            return false;
+        };
 
-        false
-    }
+        let is_removed = trees.remove(tree);
 
-    pub fn get_structures(&self, block_idx: u32) -> HashMap<Entity, StructureTileDetail> {
-        if let Some(structures) = self.structures.get(block_idx as usize) {
-            return structures.clone();
+        if trees.is_empty() {
+            self.trees.remove(&block_idx);
         }
 
-        HashMap::new()
+        is_removed
+    }
+
+    pub fn get_structures(&self, block_idx: u32) -> StableHashMap<Entity, StructureTileDetail> {
+        self.structures.get(&block_idx).cloned().unwrap_or_default()
     }
 
     pub fn add_structure(
@@ -145,9 +208,10 @@ impl Chunk {
         structure: Entity,
         detail: StructureTileDetail,
     ) {
-        if let Some(structures) = self.structures.get_mut(block_idx as usize) {
-            structures.insert(structure, detail);
-        }
+        self.structures
+            .entry(block_idx)
+            .or_default()
+            .insert(structure, detail);
     }
 
     pub fn remove_structure(
@@ -155,41 +219,48 @@ impl Chunk {
         block_idx: u32,
         structure: &Entity,
     ) -> Option<StructureTileDetail> {
-        if let Some(structures) = self.structures.get_mut(block_idx as usize) {
-            return structures.remove(structure);
+        let structures = self.structures.get_mut(&block_idx)?;
+        let detail = structures.remove(structure);
+
+        if structures.is_empty() {
+            self.structures.remove(&block_idx);
         }
 
-        None
+        detail
     }
 
-    pub fn get_lamps(&self, block_idx: u32) -> HashMap<Entity, LampDetail> {
-        if let Some(lamps) = self.lamps.get(block_idx as usize) {
-            return lamps.clone();
-        }
-
-        HashMap::new()
+    pub fn get_lamps(&self, block_idx: u32) -> StableHashMap<Entity, LampDetail> {
+        self.lamps.get(&block_idx).cloned().unwrap_or_default()
     }
 
     pub fn add_lamp(&mut self, block_idx: u32, lamp: Entity, detail: LampDetail) {
-        if let Some(lamps) = self.lamps.get_mut(block_idx as usize) {
-            lamps.insert(lamp, detail);
-        }
+        self.lamps
+            .entry(block_idx)
+            .or_default()
+            .insert(lamp, detail);
     }
 
     pub fn remove_lamp(&mut self, block_idx: u32, lamp: &Entity) -> Option<LampDetail> {
-        if let Some(lamps) = self.lamps.get_mut(block_idx as usize) {
-            return lamps.remove(lamp);
+        let lamps = self.lamps.get_mut(&block_idx)?;
+        let detail = lamps.remove(lamp);
+
+        if lamps.is_empty() {
+            self.lamps.remove(&block_idx);
         }
 
-        None
+        detail
     }
 
     pub fn set_partition_id(&mut self, block_idx: u32, value: u32) {
-        self.blocks[block_idx as usize].partition_id = Some(value);
+        if let Some(block) = self.blocks.get_mut(block_idx as usize) {
+            block.partition_id = Some(value);
+        }
     }
 
     pub fn unset_partition_id(&mut self, block_idx: u32) {
-        self.blocks[block_idx as usize].partition_id = None;
+        if let Some(block) = self.blocks.get_mut(block_idx as usize) {
+            block.partition_id = None;
+        }
     }
 
     pub fn get_partition_id(&self, block_idx: u32) -> Option<u32> {
@@ -207,9 +278,11 @@ impl Chunk {
     }
 
     pub fn set_flag_mine(&mut self, block_idx: u32, value: bool) -> bool {
-        let block = self.blocks[block_idx as usize];
+        let Some(block) = self.blocks.get_mut(block_idx as usize) else {
+            return false;
+        };
         let is_changed = block.flag_mine != value;
-        self.blocks[block_idx as usize].flag_mine = value;
+        block.flag_mine = value;
         if is_changed {
             self.set_block_dirty(block_idx);
         }
@@ -217,9 +290,11 @@ impl Chunk {
     }
 
     pub fn set_flag_chop(&mut self, block_idx: u32, value: bool) -> bool {
-        let block = self.blocks[block_idx as usize];
+        let Some(block) = self.blocks.get_mut(block_idx as usize) else {
+            return false;
+        };
         let is_changed = block.flag_chop != value;
-        self.blocks[block_idx as usize].flag_chop = value;
+        block.flag_chop = value;
         if is_changed {
             self.set_block_dirty(block_idx);
         }
@@ -227,13 +302,17 @@ impl Chunk {
     }
 
     pub fn set_sunlight(&mut self, block_idx: u32, value: u8) {
-        self.blocks[block_idx as usize].sunlight = value;
-        self.set_block_dirty(block_idx);
+        if let Some(block) = self.blocks.get_mut(block_idx as usize) {
+            block.sunlight = value;
+            self.set_block_dirty(block_idx);
+        }
     }
 
     pub fn set_torchlight(&mut self, block_idx: u32, value: u8) {
-        self.blocks[block_idx as usize].light = value;
-        self.set_block_dirty(block_idx);
+        if let Some(block) = self.blocks.get_mut(block_idx as usize) {
+            block.light = value;
+            self.set_block_dirty(block_idx);
+        }
     }
 }
 
@@ -0,0 +1,143 @@
+use bevy::ecs::{
+    event::EventWriter,
+    query::With,
+    system::{Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use ndshape::Shape;
+
+use crate::{colonists::Colonist, ChunkState, Position, SpawnTreeEvent, Terrain, TerrainGenerator};
+
+/// Columns loaded in a single tick, so walking into new terrain does not
+/// stall the simulation.
+pub const MAX_COLUMNS_LOADED_PER_TICK: usize = 4;
+
+/// Which chunks are kept in memory. Columns of chunks within `radius` of a
+/// colonist or the view focus are loaded, and those further than
+/// `radius + 1` are paged out, so a colonist walking back and forth over a
+/// chunk border does not keep loading the same chunks.
+///
+/// Loaded chunks change what colonists can reach, so the focus is only
+/// changed by a `SimInput`.
+#[derive(Resource)]
+pub struct ChunkStreaming {
+    pub radius: u32,
+    /// block x and z the player is looking at
+    pub focus: [u32; 2],
+}
+
+impl ChunkStreaming {
+    pub fn new(terrain: &Terrain) -> Self {
+        Self {
+            radius: 3,
+            focus: [terrain.world_size_x() / 2, terrain.world_size_z() / 2],
+        }
+    }
+
+    pub fn focus_column(&self, terrain: &Terrain) -> [u32; 2] {
+        [
+            self.focus[0].min(terrain.world_size_x() - 1) / terrain.chunk_size,
+            self.focus[1].min(terrain.world_size_z() - 1) / terrain.chunk_size,
+        ]
+    }
+
+    pub fn columns_around(
+        &self,
+        terrain: &Terrain,
+        chunk_x: u32,
+        chunk_z: u32,
+        margin: u32,
+    ) -> Vec<[u32; 2]> {
+        let radius = self.radius + margin;
+        let min_x = chunk_x.saturating_sub(radius);
+        let min_z = chunk_z.saturating_sub(radius);
+        let max_x = (chunk_x + radius).min(terrain.chunk_count_x - 1);
+        let max_z = (chunk_z + radius).min(terrain.chunk_count_z - 1);
+
+        (min_z..=max_z)
+            .flat_map(|z| (min_x..=max_x).map(move |x| [x, z]))
+            .collect()
+    }
+}
+
+impl FromWorld for ChunkStreaming {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.resource::<Terrain>())
+    }
+}
+
+pub fn stream_chunks(
+    mut terrain: ResMut<Terrain>,
+    streaming: Res<ChunkStreaming>,
+    mut generator: Option<ResMut<TerrainGenerator>>,
+    q_colonists: Query<&Position, With<Colonist>>,
+    mut ev_spawn_tree: EventWriter<SpawnTreeEvent>,
+) {
+    let mut centers = vec![streaming.focus_column(&terrain)];
+
+    for position in q_colonists.iter() {
+        centers.push([
+            position.x / terrain.chunk_size,
+            position.z / terrain.chunk_size,
+        ]);
+    }
+
+    let column_count = (terrain.chunk_count_x * terrain.chunk_count_z) as usize;
+    let mut is_wanted = vec![false; column_count];
+    let mut is_kept = vec![false; column_count];
+
+    for [center_x, center_z] in centers {
+        for [x, z] in streaming.columns_around(&terrain, center_x, center_z, 0) {
+            is_wanted[(z * terrain.chunk_count_x + x) as usize] = true;
+        }
+
+        for [x, z] in streaming.columns_around(&terrain, center_x, center_z, 1) {
+            is_kept[(z * terrain.chunk_count_x + x) as usize] = true;
+        }
+    }
+
+    let mut loaded = 0;
+
+    for column_idx in 0..column_count {
+        let chunk_x = column_idx as u32 % terrain.chunk_count_x;
+        let chunk_z = column_idx as u32 / terrain.chunk_count_x;
+        let bottom_idx = terrain.shape.linearize([chunk_x, 0, chunk_z]);
+
+        match terrain.get_chunk_state(bottom_idx) {
+            ChunkState::Resident if !is_kept[column_idx] => {
+                for chunk_y in 0..terrain.chunk_count_y {
+                    let chunk_idx = terrain.shape.linearize([chunk_x, chunk_y, chunk_z]);
+
+                    if let Err(e) = terrain.page_out_chunk(chunk_idx) {
+                        println!("Could not page out chunk {}: {}", chunk_idx, e);
+                    }
+                }
+            }
+            ChunkState::PagedOut
+                if is_wanted[column_idx] && loaded < MAX_COLUMNS_LOADED_PER_TICK =>
+            {
+                loaded += 1;
+
+                for chunk_y in 0..terrain.chunk_count_y {
+                    let chunk_idx = terrain.shape.linearize([chunk_x, chunk_y, chunk_z]);
+
+                    if let Err(e) = terrain.page_in_chunk(chunk_idx) {
+                        println!("Could not page in chunk {}: {}", chunk_idx, e);
+                    }
+                }
+            }
+            ChunkState::Ungenerated
+                if is_wanted[column_idx] && loaded < MAX_COLUMNS_LOADED_PER_TICK =>
+            {
+                // the test harness builds its terrain without a generator
+                let Some(generator) = generator.as_mut() else {
+                    continue;
+                };
+
+                loaded += 1;
+                generator.generate_column(&mut terrain, chunk_x, chunk_z, &mut ev_spawn_tree);
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,15 +1,12 @@
-use bevy::{
-    ecs::{
-        component::Component,
-        entity::Entity,
-        query::Changed,
-        removal_detection::RemovedComponents,
-        system::{Query, ResMut, Resource},
-    },
-    utils::HashMap,
+use bevy::ecs::{
+    component::Component,
+    entity::Entity,
+    query::Changed,
+    removal_detection::RemovedComponents,
+    system::{Query, ResMut, Resource},
 };
 
-use crate::{LampDetail, Position, Terrain};
+use crate::{common::StableHashMap, LampDetail, Position, Terrain};
 
 #[derive(Component)]
 pub struct Lamp {
@@ -18,7 +15,7 @@ pub struct Lamp {
 }
 
 #[derive(Resource, Default)]
-pub struct Lamps(pub HashMap<Entity, [u32; 2]>);
+pub struct Lamps(pub StableHashMap<Entity, [u32; 2]>);
 
 fn update_lamp(
     entity: &Entity,
@@ -1,5 +1,7 @@
 mod lamp;
+mod time_of_day;
 mod trees;
 
 pub use lamp::*;
+pub use time_of_day::*;
 pub use trees::*;
@@ -0,0 +1,61 @@
+use bevy::{
+    ecs::system::{Res, ResMut, Resource},
+    time::Time,
+};
+
+pub const HOURS_PER_DAY: u32 = 24;
+/// Seconds of simulation in a full day.
+pub const DAY_LENGTH: f32 = 480.;
+/// New colonies start in the morning.
+pub const DAY_START_HOUR: f32 = 8.;
+/// Sunlight fades in between dawn and sunrise, and out between sunset and
+/// dusk.
+const DAWN_HOUR: f32 = 5.;
+const SUNRISE_HOUR: f32 = 7.;
+const SUNSET_HOUR: f32 = 18.;
+const DUSK_HOUR: f32 = 20.;
+
+#[derive(Resource, Clone, Copy)]
+pub struct TimeOfDay {
+    /// seconds since midnight
+    pub elapsed: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            elapsed: DAY_START_HOUR / HOURS_PER_DAY as f32 * DAY_LENGTH,
+        }
+    }
+}
+
+impl TimeOfDay {
+    /// Fractional hour of the day, from 0 up to `HOURS_PER_DAY`.
+    pub fn hour_f32(&self) -> f32 {
+        self.elapsed / DAY_LENGTH * HOURS_PER_DAY as f32
+    }
+
+    pub fn hour(&self) -> u32 {
+        (self.hour_f32() as u32).min(HOURS_PER_DAY - 1)
+    }
+
+    /// How much of the sunlight reaches the world, from 0 at night to 1 at
+    /// midday.
+    pub fn daylight(&self) -> f32 {
+        let hour = self.hour_f32();
+
+        if !(DAWN_HOUR..DUSK_HOUR).contains(&hour) {
+            0.
+        } else if hour < SUNRISE_HOUR {
+            (hour - DAWN_HOUR) / (SUNRISE_HOUR - DAWN_HOUR)
+        } else if hour < SUNSET_HOUR {
+            1.
+        } else {
+            (DUSK_HOUR - hour) / (DUSK_HOUR - SUNSET_HOUR)
+        }
+    }
+}
+
+pub fn time_of_day_system(time: Res<Time>, mut time_of_day: ResMut<TimeOfDay>) {
+    time_of_day.elapsed = (time_of_day.elapsed + time.delta_seconds()) % DAY_LENGTH;
+}
@@ -0,0 +1,165 @@
+use bevy::{
+    ecs::{
+        event::EventWriter,
+        query::With,
+        system::{Local, Query, Res, ResMut},
+    },
+    time::Time,
+};
+
+use crate::{
+    colonists::{DestroyItemEvent, Item},
+    Block, BlockType, Terrain,
+};
+
+/// Most fluid a block can hold.
+pub const FLUID_LEVEL_MAX: u8 = 7;
+/// Deepest fluid that colonists can wade through.
+pub const FLUID_LEVEL_SHALLOW: u8 = 2;
+/// Seconds between fluid steps.
+pub const FLUID_STEP: f32 = 0.1;
+
+#[derive(Default)]
+pub struct FluidClock {
+    elapsed: f32,
+    step: u32,
+}
+
+/// Flows the queued fluid blocks, first down and then out to the sides
+/// until the levels even out. Fluid is never created or lost, except where
+/// two different fluids meet and harden into stone.
+pub fn fluid_system(
+    time: Res<Time>,
+    mut clock: Local<FluidClock>,
+    mut terrain: ResMut<Terrain>,
+    q_items: Query<(), With<Item>>,
+    mut ev_destroy_item: EventWriter<DestroyItemEvent>,
+) {
+    clock.elapsed += time.delta_seconds();
+
+    if clock.elapsed < FLUID_STEP {
+        return;
+    }
+
+    clock.elapsed -= FLUID_STEP;
+    clock.step += 1;
+
+    let mut queue = std::mem::take(&mut terrain.fluid_queue);
+
+    // lowest first, so fluid settles from the bottom up
+    queue.sort_by_key(|[x, y, z]| (*y, *x, *z));
+    queue.dedup();
+
+    for [x, y, z] in queue {
+        let [chunk_idx, block_idx] = terrain.get_block_indexes(x, y, z);
+
+        // fluid waits for chunks that are not loaded
+        if !terrain.is_chunk_resident(chunk_idx) {
+            terrain.fluid_queue.push([x, y, z]);
+            continue;
+        }
+
+        let block = terrain.get_block_by_idx(chunk_idx, block_idx);
+
+        let Some(fluid) = block.block.fluid() else {
+            continue;
+        };
+
+        if !clock.step.is_multiple_of(fluid.viscosity.max(1)) {
+            terrain.fluid_queue.push([x, y, z]);
+            continue;
+        }
+
+        if fluid.is_destructive {
+            // colonists are hurt by the fluid rather than destroyed
+            for item in terrain.get_items(chunk_idx, block_idx) {
+                if !q_items.contains(item) {
+                    continue;
+                }
+
+                ev_destroy_item.send(DestroyItemEvent { entity: item });
+            }
+        }
+
+        flow(&mut terrain, [x, y, z], block);
+    }
+}
+
+fn flow(terrain: &mut Terrain, [x, y, z]: [u32; 3], block: Block) {
+    let mut level = block.fluid_level;
+
+    if y > 0 {
+        level -= pour(terrain, block.block, [x, y - 1, z], level);
+    }
+
+    let [xi, yi, zi] = [x as i32, y as i32, z as i32];
+
+    for [nx, ny, nz] in [
+        [xi + 1, yi, zi],
+        [xi - 1, yi, zi],
+        [xi, yi, zi + 1],
+        [xi, yi, zi - 1],
+    ] {
+        // a block keeps at least as much as it gives away
+        if level <= 1 {
+            break;
+        }
+
+        let neighbor = terrain.get_block_i32(nx, ny, nz);
+
+        if neighbor.is_oob() || (neighbor.block == block.block && neighbor.fluid_level + 1 >= level)
+        {
+            continue;
+        }
+
+        level -= pour(terrain, block.block, [nx as u32, ny as u32, nz as u32], 1);
+    }
+
+    if level != block.fluid_level {
+        terrain.set_fluid_level(x, y, z, level);
+        terrain.wake_fluids(x, y, z);
+    }
+}
+
+/// Moves up to `amount` of the fluid into the block, returns how much moved.
+fn pour(terrain: &mut Terrain, fluid: BlockType, [x, y, z]: [u32; 3], amount: u8) -> u8 {
+    let target = terrain.get_block(x, y, z);
+
+    if target.is_empty() {
+        if is_blocked(terrain, [x, y, z]) {
+            return 0;
+        }
+
+        terrain.set_block_type(x, y, z, fluid);
+        terrain.set_fluid_level(x, y, z, amount);
+        return amount;
+    }
+
+    if target.block == fluid {
+        let moved = amount.min(FLUID_LEVEL_MAX - target.fluid_level);
+
+        if moved > 0 {
+            terrain.set_fluid_level(x, y, z, target.fluid_level + moved);
+            terrain.wake_fluids(x, y, z);
+        }
+
+        return moved;
+    }
+
+    if target.is_fluid() {
+        terrain.set_block_type(x, y, z, BlockType::STONE);
+        return 1;
+    }
+
+    0
+}
+
+/// Built walls and other blocking structures hold fluid back.
+fn is_blocked(terrain: &Terrain, [x, y, z]: [u32; 3]) -> bool {
+    let [chunk_idx, block_idx] = terrain.get_block_indexes(x, y, z);
+
+    terrain
+        .get_structures(chunk_idx, block_idx)
+        .values()
+        .any(|t| t.is_built && t.is_blocker)
+}
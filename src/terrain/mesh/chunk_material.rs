@@ -11,7 +11,9 @@ use bevy::{
     },
 };
 
-use crate::{Block, BlockFace, ATTRIBUTE_BLOCK_LIGHT, ATTRIBUTE_BLOCK_PACKED};
+use crate::{
+    rendering::DaylightMaterial, Block, BlockFace, ATTRIBUTE_BLOCK_LIGHT, ATTRIBUTE_BLOCK_PACKED,
+};
 
 #[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
 pub struct ChunkMaterial {
@@ -22,6 +24,9 @@ pub struct ChunkMaterial {
     pub color: Color,
     #[uniform[3]]
     pub texture_count: u32,
+    /// scales the sunlight with the time of day
+    #[uniform[5]]
+    pub daylight: f32,
 }
 
 impl Material for ChunkMaterial {
@@ -49,7 +54,7 @@ impl Material for ChunkMaterial {
             return Ok(());
         }
 
-        let Some(fragment) = descriptor.fragment.as_mut() else {
+        let Some(_fragment) = descriptor.fragment.as_mut() else {
             return Ok(());
         };
 
@@ -65,8 +70,14 @@ impl Material for ChunkMaterial {
     }
 }
 
+impl DaylightMaterial for ChunkMaterial {
+    fn set_daylight(&mut self, daylight: f32) {
+        self.daylight = daylight;
+    }
+}
+
 pub fn pack_block(block: Block, dir: BlockFace, ao: VertexCornerCount) -> u32 {
-    let t_id = block.texture_idx(); // eight bits, 0-256
+    let t_id = block.texture_idx(dir); // eight bits, 0-256
     let f_id = dir.bit(); // three bits, 0-7
     let ao_id = ao.bit(); // two bits, 0-3
     let mine_bit = if block.flag_mine { 1 } else { 0 }; // one bit;
@@ -7,8 +7,8 @@ use bevy::{
         render_asset::RenderAssetUsages,
         render_resource::VertexFormat,
     },
+    utils::HashMap,
 };
-use ndshape::AbstractShape;
 
 use crate::{
     items::image_loader_settings, pack_block, Block, BlockFace, ChunkLayerMesh, ChunkMaterial,
@@ -20,12 +20,18 @@ pub const ATTRIBUTE_BLOCK_PACKED: MeshVertexAttribute =
 pub const ATTRIBUTE_BLOCK_LIGHT: MeshVertexAttribute =
     MeshVertexAttribute::new("BlockLight", 98218357661, VertexFormat::Uint32);
 
+/// Layer meshes of every resident chunk, spawned as chunks are loaded.
+#[derive(Resource)]
+pub struct ChunkMeshes {
+    material: Handle<ChunkMaterial>,
+    container: Entity,
+    layers: HashMap<u32, Vec<Entity>>,
+}
+
 pub fn setup_chunk_meshes(
     mut cmd: Commands,
     asset_server: Res<AssetServer>,
-    mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ChunkMaterial>>,
-    terrain: Res<Terrain>,
 ) {
     let terrain_texture: Handle<Image> =
         asset_server.load_with_settings("textures/comfy.png", image_loader_settings);
@@ -34,18 +40,49 @@ pub fn setup_chunk_meshes(
         color: Color::YELLOW_GREEN,
         texture: terrain_texture,
         texture_count: 8,
+        daylight: 1.,
     });
 
     let chunk_container_entity = cmd
         .spawn((Name::new("Chunks"), SpatialBundle::default()))
         .id();
 
+    cmd.insert_resource(ChunkMeshes {
+        material: chunk_material,
+        container: chunk_container_entity,
+        layers: HashMap::new(),
+    });
+}
+
+/// Spawns layer meshes for chunks that were loaded, and despawns those of
+/// chunks that were paged out.
+pub fn sync_chunk_meshes(
+    mut cmd: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut chunk_meshes: ResMut<ChunkMeshes>,
+    terrain: Res<Terrain>,
+) {
+    let chunk_meshes = chunk_meshes.as_mut();
+
+    chunk_meshes.layers.retain(|chunk_idx, layers| {
+        if terrain.is_chunk_resident(*chunk_idx) {
+            return true;
+        }
+
+        for entity in layers.iter() {
+            cmd.entity(*entity).despawn_recursive();
+        }
+
+        false
+    });
+
     for chunk_idx in 0..terrain.chunk_count {
-        let chunk_pos = terrain.shape.delinearize(chunk_idx);
+        if !terrain.is_chunk_resident(chunk_idx) || chunk_meshes.layers.contains_key(&chunk_idx) {
+            continue;
+        }
 
-        let chunk_x = chunk_pos[0] * terrain.chunk_size;
-        let chunk_y = chunk_pos[1] * terrain.chunk_size;
-        let chunk_z = chunk_pos[2] * terrain.chunk_size;
+        let [chunk_x, chunk_y, chunk_z] = terrain.get_chunk_offset(chunk_idx);
+        let mut layers = vec![];
 
         for local_y in 0..terrain.chunk_size {
             let global_y = chunk_y + local_y;
@@ -77,7 +114,7 @@ pub fn setup_chunk_meshes(
                 },
                 MaterialMeshBundle {
                     mesh: mesh_handle.clone(),
-                    material: chunk_material.clone(),
+                    material: chunk_meshes.material.clone(),
                     transform: Transform::from_xyz(x_f32, y_f32, z_f32),
                     ..default()
                 },
@@ -87,8 +124,11 @@ pub fn setup_chunk_meshes(
                 },
             ));
 
-            chunk_cmds.set_parent(chunk_container_entity);
+            chunk_cmds.set_parent(chunk_meshes.container);
+            layers.push(chunk_cmds.id());
         }
+
+        chunk_meshes.layers.insert(chunk_idx, layers);
     }
 }
 
@@ -1,21 +1,33 @@
 mod block;
 mod block_face;
+mod block_registry;
 mod chunk;
+mod chunk_streaming;
 mod environment;
+mod fluid;
 mod light;
 mod mesh;
 mod position;
 mod slice;
+mod support;
 mod terrain;
 mod terrain_gen;
+#[cfg(test)]
+mod tests;
+mod world_gen_settings;
 
 pub use block::*;
 pub use block_face::*;
+pub use block_registry::*;
 pub use chunk::*;
+pub use chunk_streaming::*;
 pub use environment::*;
+pub use fluid::*;
 pub use light::*;
 pub use mesh::*;
 pub use position::*;
 pub use slice::*;
+pub use support::*;
 pub use terrain::*;
 pub use terrain_gen::*;
+pub use world_gen_settings::*;
@@ -26,6 +26,10 @@ impl Position {
     pub fn as_array(&self) -> [u32; 3] {
         [self.x, self.y, self.z]
     }
+
+    pub fn as_i32(&self) -> [i32; 3] {
+        [self.x as i32, self.y as i32, self.z as i32]
+    }
 }
 
 pub fn update_positions(
@@ -282,7 +282,7 @@ impl Material for SliceMaterial {
             return Ok(());
         }
 
-        let Some(fragment) = descriptor.fragment.as_mut() else {
+        let Some(_fragment) = descriptor.fragment.as_mut() else {
             return Ok(());
         };
 
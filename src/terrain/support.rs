@@ -0,0 +1,177 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    asset::{AssetServer, Assets},
+    core::Name,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        event::EventWriter,
+        query::{With, Without},
+        system::{Commands, Query, Res, ResMut},
+    },
+    hierarchy::{BuildChildren, DespawnRecursiveExt},
+    pbr::MaterialMeshBundle,
+    prelude::default,
+    render::{color::Color, prelude::SpatialBundle},
+    transform::components::Transform,
+};
+
+use crate::{
+    colonists::{BlockMove, DamageEvent, DestroyItemEvent, Health, Item},
+    common::StableHashSet,
+    rendering::BasicMaterial,
+    BlockType, Position, Terrain,
+};
+
+/// Furthest a block reaches sideways, through other blocks, to one that
+/// rests on something.
+pub const SUPPORT_SPAN: u32 = 4;
+/// A large cave-in is spread over several ticks.
+pub const SUPPORT_CHECKS_PER_TICK: usize = 256;
+pub const CAVE_IN_DAMAGE: f32 = 40.;
+
+/// A block that lost its support, on its way down to where it lands.
+#[derive(Component)]
+pub struct FallingBlock {
+    pub block_type: BlockType,
+}
+
+/// Collapses the queued blocks that no longer have support. Only blocks
+/// that can be mined fall, the rest are fixtures of the world.
+pub fn support_system(
+    mut cmd: Commands,
+    mut terrain: ResMut<Terrain>,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<BasicMaterial>>,
+) {
+    if terrain.support_queue.is_empty() {
+        return;
+    }
+
+    let mut queue = std::mem::take(&mut terrain.support_queue);
+
+    // lowest first, so blocks above see the collapse below them
+    queue.sort_by_key(|[x, y, z]| (*y, *x, *z));
+    queue.dedup();
+
+    let rest = queue.split_off(queue.len().min(SUPPORT_CHECKS_PER_TICK));
+    terrain.support_queue.extend(rest);
+
+    for [x, y, z] in queue {
+        let block = terrain.get_block(x, y, z);
+
+        if !block.is_mineable() || is_supported(&terrain, [x, y, z]) {
+            continue;
+        }
+
+        terrain.set_flag_mine(x, y, z, false);
+        terrain.set_block_type(x, y, z, BlockType::EMPTY);
+
+        let mut landing_y = y;
+
+        while landing_y > 0 && !terrain.get_block(x, landing_y - 1, z).is_supportive() {
+            landing_y -= 1;
+        }
+
+        cmd.spawn((
+            Name::new("Falling block"),
+            FallingBlock {
+                block_type: block.block,
+            },
+            SpatialBundle {
+                transform: Transform::from_xyz(x as f32 + 0.5, y as f32, z as f32 + 0.5),
+                ..default()
+            },
+            BlockMove {
+                speed: 12.,
+                target: [x as i32, landing_y as i32, z as i32],
+                look_at: false,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(MaterialMeshBundle {
+                mesh: asset_server.load("cube_offset.gltf#Mesh0/Primitive0"),
+                material: materials.add(BasicMaterial {
+                    color: Color::rgb(0.55, 0.5, 0.45),
+                    ..default()
+                }),
+                transform: Transform::from_xyz(-0.5, 0., -0.5),
+                ..default()
+            });
+        });
+    }
+}
+
+/// Whether a path of blocks no longer than `SUPPORT_SPAN` leads sideways
+/// from the block to one that rests on something.
+fn is_supported(terrain: &Terrain, [x, y, z]: [u32; 3]) -> bool {
+    let y = y as i32;
+    let mut visited = StableHashSet::default();
+    let mut open = VecDeque::from([([x as i32, z as i32], 0)]);
+
+    visited.insert([x as i32, z as i32]);
+
+    while let Some(([bx, bz], dist)) = open.pop_front() {
+        if terrain.get_block_i32(bx, y - 1, bz).is_supportive() {
+            return true;
+        }
+
+        if dist == SUPPORT_SPAN {
+            continue;
+        }
+
+        for [nx, nz] in [[bx + 1, bz], [bx - 1, bz], [bx, bz + 1], [bx, bz - 1]] {
+            if terrain.get_block_i32(nx, y, nz).is_supportive() && visited.insert([nx, nz]) {
+                open.push_back(([nx, nz], dist + 1));
+            }
+        }
+    }
+
+    false
+}
+
+/// Falling blocks crush the items and hurt anyone beneath them where they
+/// land.
+pub fn land_falling_blocks(
+    mut cmd: Commands,
+    mut terrain: ResMut<Terrain>,
+    q_falling: Query<(Entity, &FallingBlock, &Transform), Without<BlockMove>>,
+    q_health: Query<(Entity, &Position), With<Health>>,
+    q_items: Query<(), With<Item>>,
+    mut ev_destroy_item: EventWriter<DestroyItemEvent>,
+    mut ev_damage: EventWriter<DamageEvent>,
+) {
+    for (entity, falling, transform) in q_falling.iter() {
+        let x = transform.translation.x as u32;
+        let mut y = transform.translation.y as u32;
+        let z = transform.translation.z as u32;
+
+        // another block may have landed here first
+        while y < terrain.world_size_y() - 1 && terrain.get_block(x, y, z).is_supportive() {
+            y += 1;
+        }
+
+        let [chunk_idx, block_idx] = terrain.get_block_indexes(x, y, z);
+
+        for item in terrain.get_items(chunk_idx, block_idx) {
+            if !q_items.contains(item) {
+                continue;
+            }
+
+            ev_destroy_item.send(DestroyItemEvent { entity: item });
+        }
+
+        for (victim, position) in q_health.iter() {
+            if position.as_array() == [x, y, z] {
+                ev_damage.send(DamageEvent {
+                    entity: victim,
+                    amount: CAVE_IN_DAMAGE,
+                });
+            }
+        }
+
+        terrain.set_block_type(x, y, z, falling.block_type);
+        cmd.entity(entity).despawn_recursive();
+    }
+}
@@ -1,11 +1,11 @@
-use bevy::{
-    ecs::{entity::Entity, system::Resource},
-    utils::hashbrown::{HashMap, HashSet},
-};
+use bevy::ecs::{entity::Entity, system::Resource};
 use ndshape::{RuntimeShape, Shape};
 
 use crate::{
-    common::sig_num, Block, BlockFace, BlockType, Chunk, LampDetail, LightNode, StructureTileDetail,
+    common::{sig_num, StableHashMap, StableHashSet},
+    persistence::{decode_chunk_runs, ChunkPager},
+    Block, BlockFace, BlockType, Chunk, ChunkState, LampDetail, LightNode, StructureTileDetail,
+    SUPPORT_SPAN,
 };
 
 #[derive(Resource)]
@@ -18,10 +18,15 @@ pub struct Terrain {
     pub shape: RuntimeShape<u32, 3>,
     pub chunk_shape: RuntimeShape<u32, 3>,
     pub chunks: Vec<Chunk>,
+    pub pager: ChunkPager,
     pub lights_queue_add: Vec<LightNode>,
     pub lights_queue_remove: Vec<LightNode>,
     pub sunlight_queue_add: Vec<LightNode>,
     pub sunlight_queue_remove: Vec<LightNode>,
+    /// blocks of fluid that may flow on the next fluid step
+    pub fluid_queue: Vec<[u32; 3]>,
+    /// blocks that may have lost their support
+    pub support_queue: Vec<[u32; 3]>,
 }
 
 pub struct RayResult {
@@ -51,14 +56,18 @@ impl Terrain {
             chunk_count: shape.size(),
             chunk_shape: chunk_shape.clone(),
             chunks: vec![Chunk::new(chunk_shape); shape.size() as usize],
+            pager: ChunkPager::default(),
             shape,
             lights_queue_add: vec![],
             lights_queue_remove: vec![],
             sunlight_queue_add: vec![],
             sunlight_queue_remove: vec![],
+            fluid_queue: vec![],
+            support_queue: vec![],
         }
     }
 
+    /// Makes the chunk resident, with every block empty.
     pub fn init_chunk(&mut self, chunk_idx: u32) {
         let chunk_pos = self.shape.delinearize(chunk_idx);
         let chunk = self.chunks.get_mut(chunk_idx as usize).unwrap();
@@ -68,6 +77,89 @@ impl Terrain {
         chunk.world_y = self.chunk_size * chunk_pos[1];
         chunk.world_z = self.chunk_size * chunk_pos[2];
         chunk.chunk_size = self.chunk_size;
+        chunk.allocate();
+    }
+
+    pub fn get_chunk_state(&self, chunk_idx: u32) -> ChunkState {
+        self.get_chunk(chunk_idx)
+            .map(|chunk| chunk.state)
+            .unwrap_or(ChunkState::Ungenerated)
+    }
+
+    pub fn is_chunk_resident(&self, chunk_idx: u32) -> bool {
+        self.get_chunk_state(chunk_idx) == ChunkState::Resident
+    }
+
+    /// Writes the chunk to disk and frees its blocks. Blocks in a chunk that
+    /// is not resident read as out of bounds, and writes to them are ignored.
+    pub fn page_out_chunk(&mut self, chunk_idx: u32) -> Result<(), String> {
+        let Some(chunk) = self.get_chunk(chunk_idx) else {
+            return Err(format!("missing chunk {}", chunk_idx));
+        };
+
+        if !chunk.is_resident() {
+            return Ok(());
+        }
+
+        self.pager.write(chunk)?;
+        self.get_chunk_mut(chunk_idx).unwrap().page_out();
+
+        // partitions in the chunk are removed when it is repartitioned
+        self.set_chunk_nav_dirty(chunk_idx, true);
+        self.set_neighbor_chunks_dirty(chunk_idx);
+
+        Ok(())
+    }
+
+    pub fn page_in_chunk(&mut self, chunk_idx: u32) -> Result<(), String> {
+        if self.get_chunk_state(chunk_idx) != ChunkState::PagedOut {
+            return Ok(());
+        }
+
+        let runs = self.pager.read(chunk_idx)?;
+
+        self.init_chunk(chunk_idx);
+        decode_chunk_runs(self.get_chunk_mut(chunk_idx).unwrap(), &runs)?;
+        self.set_chunk_dirty(chunk_idx);
+        self.set_neighbor_chunks_dirty(chunk_idx);
+
+        Ok(())
+    }
+
+    /// Every layer needs meshing, and the chunk needs partitioning.
+    pub fn set_chunk_dirty(&mut self, chunk_idx: u32) {
+        let [_, chunk_y, _] = self.get_chunk_offset(chunk_idx);
+
+        for local_y in 0..self.chunk_size {
+            self.set_layer_dirty(chunk_idx, chunk_y + local_y, true);
+        }
+    }
+
+    /// Resident chunks beside this one are remeshed and repartitioned, to
+    /// match its blocks being loaded or unloaded.
+    fn set_neighbor_chunks_dirty(&mut self, chunk_idx: u32) {
+        let [x, y, z] = self.shape.delinearize(chunk_idx);
+
+        for [nx, nz] in [
+            [x as i32 - 1, z as i32],
+            [x as i32 + 1, z as i32],
+            [x as i32, z as i32 - 1],
+            [x as i32, z as i32 + 1],
+        ] {
+            if nx < 0
+                || nz < 0
+                || nx >= self.chunk_count_x as i32
+                || nz >= self.chunk_count_z as i32
+            {
+                continue;
+            }
+
+            let neighbor_idx = self.shape.linearize([nx as u32, y, nz as u32]);
+
+            if self.is_chunk_resident(neighbor_idx) {
+                self.set_chunk_dirty(neighbor_idx);
+            }
+        }
     }
 
     pub fn world_size_x(&self) -> u32 {
@@ -91,8 +183,27 @@ impl Terrain {
             || z >= self.world_size_z() as i32
     }
 
+    /// A copy of the given chunks, for work done off the main thread. Every
+    /// other chunk reads as out of bounds.
+    pub fn snapshot(&self, chunk_idxs: impl IntoIterator<Item = u32>) -> Self {
+        let mut snapshot = Self::new(
+            self.chunk_count_x,
+            self.chunk_count_y,
+            self.chunk_count_z,
+            self.chunk_size,
+        );
+
+        for chunk_idx in chunk_idxs {
+            if let Some(chunk) = self.get_chunk(chunk_idx) {
+                snapshot.chunks[chunk_idx as usize] = chunk.clone();
+            }
+        }
+
+        snapshot
+    }
+
     pub fn get_chunk(&self, chunk_idx: u32) -> Option<&Chunk> {
-        return self.chunks.get(chunk_idx as usize);
+        self.chunks.get(chunk_idx as usize)
     }
 
     pub fn is_layer_dirty(&self, chunk_idx: u32, global_y: u32) -> bool {
@@ -118,7 +229,7 @@ impl Terrain {
         }
     }
 
-    pub fn set_layer_position_mesh_dirty(&mut self, x: u32, y: u32, z: u32, value: bool) {
+    pub fn set_layer_position_mesh_dirty(&mut self, x: u32, y: u32, z: u32, _value: bool) {
         let [chunk_idx, block_idx] = self.get_block_indexes(x, y, z);
         if let Some(chunk) = self.chunks.get_mut(chunk_idx as usize) {
             chunk.set_block_dirty(block_idx);
@@ -139,7 +250,7 @@ impl Terrain {
     }
 
     pub fn get_chunk_mut(&mut self, chunk_idx: u32) -> Option<&mut Chunk> {
-        return self.chunks.get_mut(chunk_idx as usize);
+        self.chunks.get_mut(chunk_idx as usize)
     }
 
     pub fn get_chunk_offset(&self, chunk_idx: u32) -> [u32; 3] {
@@ -182,6 +293,7 @@ impl Terrain {
 
     pub fn set_block_type(&mut self, x: u32, y: u32, z: u32, value: BlockType) {
         let [chunk_idx, block_idx] = self.get_block_indexes(x, y, z);
+        let was_supportive = self.get_block_by_idx(chunk_idx, block_idx).is_supportive();
 
         if let Some(chunk) = self.get_chunk_mut(chunk_idx) {
             chunk.set_block_type(block_idx, value);
@@ -195,11 +307,7 @@ impl Terrain {
         }
 
         let local_x = x % self.chunk_size;
-        let local_y = y % self.chunk_size;
         let local_z = z % self.chunk_size;
-        let chunk_x = x / self.chunk_size;
-        let chunk_y = y / self.chunk_size;
-        let chunk_z = z / self.chunk_size;
 
         // what chunks does this block touch?
         if local_x == 0 && x > 0 {
@@ -280,6 +388,65 @@ impl Terrain {
             self.set_layer_position_mesh_dirty(x, y - 1, z + 1, true);
             self.set_layer_position_mesh_dirty(x, y + 1, z + 1, true);
         }
+
+        self.wake_fluids(x, y, z);
+
+        if was_supportive && !self.get_block(x, y, z).is_supportive() {
+            self.wake_supports(x, y, z);
+        }
+    }
+
+    /// Queues the blocks that could have been held up by this one, the block
+    /// above and those beside it within `SUPPORT_SPAN`.
+    pub fn wake_supports(&mut self, x: u32, y: u32, z: u32) {
+        let span = SUPPORT_SPAN as i32;
+        let [x, y, z] = [x as i32, y as i32, z as i32];
+
+        if !self.is_oob(x, y + 1, z) {
+            self.support_queue.push([x as u32, y as u32 + 1, z as u32]);
+        }
+
+        for nx in (x - span)..=(x + span) {
+            for nz in (z - span)..=(z + span) {
+                if !self.is_oob(nx, y, nz) && (nx, nz) != (x, z) {
+                    self.support_queue.push([nx as u32, y as u32, nz as u32]);
+                }
+            }
+        }
+    }
+
+    /// Fluid levels are kept apart from the block type, a level of zero
+    /// empties the block.
+    pub fn set_fluid_level(&mut self, x: u32, y: u32, z: u32, value: u8) {
+        if value == 0 {
+            self.set_block_type(x, y, z, BlockType::EMPTY);
+            return;
+        }
+
+        let [chunk_idx, block_idx] = self.get_block_indexes(x, y, z);
+
+        if let Some(chunk) = self.get_chunk_mut(chunk_idx) {
+            chunk.set_fluid_level(block_idx, value);
+        }
+    }
+
+    /// Queues any fluid in or beside the block to flow.
+    pub fn wake_fluids(&mut self, x: u32, y: u32, z: u32) {
+        let [x, y, z] = [x as i32, y as i32, z as i32];
+
+        for [nx, ny, nz] in [
+            [x, y, z],
+            [x + 1, y, z],
+            [x - 1, y, z],
+            [x, y + 1, z],
+            [x, y - 1, z],
+            [x, y, z + 1],
+            [x, y, z - 1],
+        ] {
+            if self.get_block_i32(nx, ny, nz).is_fluid() {
+                self.fluid_queue.push([nx as u32, ny as u32, nz as u32]);
+            }
+        }
     }
 
     pub fn init_block(&mut self, x: u32, y: u32, z: u32, value: BlockType) {
@@ -321,12 +488,20 @@ impl Terrain {
         false
     }
 
-    pub fn get_trees(&self, chunk_idx: u32, block_idx: u32) -> HashSet<Entity> {
+    pub fn get_items(&self, chunk_idx: u32, block_idx: u32) -> StableHashSet<Entity> {
+        if let Some(chunk) = self.get_chunk(chunk_idx) {
+            return chunk.get_items(block_idx);
+        }
+
+        StableHashSet::default()
+    }
+
+    pub fn get_trees(&self, chunk_idx: u32, block_idx: u32) -> StableHashSet<Entity> {
         if let Some(chunk) = self.get_chunk(chunk_idx) {
             return chunk.get_trees(block_idx);
         }
 
-        HashSet::new()
+        StableHashSet::default()
     }
 
     pub fn add_tree(&mut self, chunk_idx: u32, block_idx: u32, tree: Entity) {
@@ -347,12 +522,12 @@ impl Terrain {
         &self,
         chunk_idx: u32,
         block_idx: u32,
-    ) -> HashMap<Entity, StructureTileDetail> {
+    ) -> StableHashMap<Entity, StructureTileDetail> {
         if let Some(chunk) = self.get_chunk(chunk_idx) {
             return chunk.get_structures(block_idx);
         }
 
-        HashMap::new()
+        StableHashMap::default()
     }
 
     pub fn add_structure(
@@ -380,12 +555,12 @@ impl Terrain {
         None
     }
 
-    pub fn get_lamps(&self, chunk_idx: u32, block_idx: u32) -> HashMap<Entity, LampDetail> {
+    pub fn get_lamps(&self, chunk_idx: u32, block_idx: u32) -> StableHashMap<Entity, LampDetail> {
         if let Some(chunk) = self.get_chunk(chunk_idx) {
             return chunk.get_lamps(block_idx);
         }
 
-        HashMap::new()
+        StableHashMap::default()
     }
 
     pub fn add_lamp(&mut self, chunk_idx: u32, block_idx: u32, lamp: Entity, detail: LampDetail) {
@@ -2,89 +2,264 @@ use std::cmp::min;
 
 use crate::{
     common::{FractalNoise, Rand},
-    BlockType, SpawnTreeEvent, Terrain, TreeSettings,
+    BlockRegistry, BlockType, ChunkStreaming, SpawnTreeEvent, Terrain, TreeSettings,
+    WorldGenSettings,
 };
-use bevy::ecs::{event::EventWriter, system::ResMut};
-
-pub fn setup_terrain(mut terrain: ResMut<Terrain>, mut ev_spawn_tree: EventWriter<SpawnTreeEvent>) {
-    let seed = 7;
-    let mut height = FractalNoise::new(seed, 0.01, 8);
-    let mut caverns = FractalNoise::new(seed + 1, 0.01, 6);
-    let mut caves = FractalNoise::new(seed + 2, 0.02, 2);
-    let mut trees = Rand::seed(seed as u64);
-
-    let sky_height = 8;
-    let top = terrain.world_size_y() - sky_height;
-    let mountain_height = min(top - 4, 38);
-    let magma_level = 4;
-    let dirt_depth = 3;
-    let cavern_depth = 0.3;
-
-    for chunk_idx in 0..terrain.chunk_count {
-        terrain.init_chunk(chunk_idx);
+use bevy::ecs::{
+    event::EventWriter,
+    system::{Commands, Res, ResMut, Resource},
+};
+use ndshape::Shape;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Biome {
+    Tundra,
+    Desert,
+    Grassland,
+    Forest,
+}
+
+impl Biome {
+    pub fn from_climate(temperature: f32, moisture: f32) -> Self {
+        if temperature < 0.38 {
+            Biome::Tundra
+        } else if temperature > 0.62 && moisture < 0.5 {
+            Biome::Desert
+        } else if moisture > 0.55 {
+            Biome::Forest
+        } else {
+            Biome::Grassland
+        }
+    }
+
+    fn surface_block(&self) -> BlockType {
+        match self {
+            Biome::Tundra => BlockType::SNOW,
+            Biome::Desert => BlockType::SAND,
+            Biome::Grassland | Biome::Forest => BlockType::GRASS,
+        }
+    }
+
+    fn subsurface_block(&self) -> BlockType {
+        match self {
+            Biome::Desert => BlockType::SAND,
+            _ => BlockType::DIRT,
+        }
+    }
+
+    fn tree_chance_multiplier(&self) -> f32 {
+        match self {
+            Biome::Tundra => 0.3,
+            Biome::Desert => 0.,
+            Biome::Grassland => 0.5,
+            Biome::Forest => 3.,
+        }
+    }
+}
+
+struct OreNoise {
+    block_type: BlockType,
+    min_y: u32,
+    max_y: u32,
+    threshold: f32,
+    noise: FractalNoise,
+}
+
+/// Generates the world one column of chunks at a time, as they are first
+/// loaded. A column is the same no matter when or in what order it is
+/// generated.
+#[derive(Resource)]
+pub struct TerrainGenerator {
+    seed: u64,
+    height: FractalNoise,
+    caverns: FractalNoise,
+    caves: FractalNoise,
+    temperature: FractalNoise,
+    moisture: FractalNoise,
+    pools: FractalNoise,
+    ores: Vec<OreNoise>,
+    settings: WorldGenSettings,
+}
+
+impl TerrainGenerator {
+    pub fn new(settings: &WorldGenSettings) -> Self {
+        let seed = Rand::seed(settings.seed).range_n(0, i32::MAX - 16);
+        let registry = BlockRegistry::get();
+        let mut ores = vec![];
+
+        for (idx, ore) in settings.ores.iter().enumerate() {
+            let Some(block_type) = registry.find(&ore.block) else {
+                println!("Cannot generate ore, unknown block \"{}\"", ore.block);
+                continue;
+            };
+
+            ores.push(OreNoise {
+                block_type,
+                min_y: ore.min_y,
+                max_y: ore.max_y,
+                threshold: ore.threshold,
+                noise: FractalNoise::new(seed + 6 + idx as i32, ore.frequency, 2),
+            });
+        }
+
+        Self {
+            seed: settings.seed,
+            height: FractalNoise::new(seed, settings.height_frequency, settings.height_octaves),
+            caverns: FractalNoise::new(seed + 1, settings.cavern_frequency, 6),
+            caves: FractalNoise::new(seed + 2, settings.cave_frequency, 2),
+            temperature: FractalNoise::new(seed + 3, settings.biome_frequency, 3),
+            moisture: FractalNoise::new(seed + 4, settings.biome_frequency, 3),
+            pools: FractalNoise::new(seed + 5, settings.pool_frequency, 2),
+            ores,
+            settings: settings.clone(),
+        }
+    }
+
+    /// Height the cavern layer is carved around, the deep caverns are below.
+    pub fn cavern_level(&self, terrain: &Terrain) -> u32 {
+        (self.settings.cavern_depth * terrain.world_size_y() as f32) as u32
     }
 
-    println!("generating world..");
+    /// Generates every chunk from the bottom to the top of the world at
+    /// chunk `chunk_x`, `chunk_z`.
+    pub fn generate_column(
+        &mut self,
+        terrain: &mut Terrain,
+        chunk_x: u32,
+        chunk_z: u32,
+        ev_spawn_tree: &mut EventWriter<SpawnTreeEvent>,
+    ) {
+        let settings = &self.settings;
+        let top = terrain.world_size_y() - settings.sky_height;
+        let mountain_height = min(top - 4, settings.mountain_height);
+        let magma_level = settings.magma_level;
+        let dirt_depth = settings.dirt_depth;
+        let cavern_depth = settings.cavern_depth * terrain.world_size_y() as f32;
+        let chunk_size = terrain.chunk_size;
 
-    for x in 0..terrain.world_size_x() {
-        for y in 0..terrain.world_size_y() {
-            for z in 0..terrain.world_size_z() {
+        // trees are the only randomness, seeded by column so they do not
+        // depend on the order columns are loaded in
+        let column_idx = (chunk_z * terrain.chunk_count_x + chunk_x) as u64;
+        let mut rand = Rand::seed(self.seed ^ column_idx.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+
+        for chunk_y in 0..terrain.chunk_count_y {
+            let chunk_idx = terrain.shape.linearize([chunk_x, chunk_y, chunk_z]);
+            terrain.init_chunk(chunk_idx);
+        }
+
+        for local_x in 0..chunk_size {
+            for local_z in 0..chunk_size {
+                let x = chunk_x * chunk_size + local_x;
+                let z = chunk_z * chunk_size + local_z;
                 let x_f32 = x as f32;
-                let y_f32 = y as f32;
                 let z_f32 = z as f32;
-                let h = height.get_2d(x_f32, z_f32);
-
-                let surface = top - (((h.clamp(0.1, 0.5)) * (mountain_height) as f32) as u32); // 0 to 28
-
-                // above ground
-                if y > surface {
-                    terrain.init_block(x, y, z, BlockType::EMPTY);
-                    if y == surface + 1 {
-                        terrain.add_sunlight(x, y, z, 15);
-                        if trees.bool(0.01) {
-                            ev_spawn_tree.send(SpawnTreeEvent {
-                                position: [x, y, z],
-                                settings: TreeSettings {
-                                    height: trees.range_n(6, 14) as u32,
-                                    canopy_radius: 1,
-                                },
-                            });
+                let h = self.height.get_2d(x_f32, z_f32);
+                let surface = top - (((h.clamp(0.1, 0.5)) * (mountain_height) as f32) as u32);
+                let biome = Biome::from_climate(
+                    self.temperature.get_2d(x_f32, z_f32),
+                    self.moisture.get_2d(x_f32, z_f32),
+                );
+                let pool = self.pools.get_2d(x_f32, z_f32);
+
+                // keep canopies inside the column, the neighbors may not be loaded
+                let is_tree_allowed = local_x > 0
+                    && local_z > 0
+                    && local_x < chunk_size - 1
+                    && local_z < chunk_size - 1;
+
+                for y in 0..terrain.world_size_y() {
+                    let y_f32 = y as f32;
+
+                    // above ground
+                    if y > surface {
+                        terrain.init_block(x, y, z, BlockType::EMPTY);
+                        if y == surface + 1 {
+                            terrain.add_sunlight(x, y, z, 15);
+                            let tree_chance = settings.tree_chance * biome.tree_chance_multiplier();
+                            if rand.bool(tree_chance) && is_tree_allowed {
+                                ev_spawn_tree.send(SpawnTreeEvent {
+                                    position: [x, y, z],
+                                    settings: TreeSettings {
+                                        height: rand.range_n(6, 14) as u32,
+                                        canopy_radius: 1,
+                                    },
+                                });
+                            }
+                        } else {
+                            terrain.set_sunlight(x, y, z, 15);
                         }
-                    } else {
-                        terrain.set_sunlight(x, y, z, 15);
+                        continue;
                     }
-                    continue;
-                }
 
-                if y <= magma_level {
-                    terrain.init_block(x, y, z, BlockType::MAGMA);
-                    continue;
-                }
+                    if y <= magma_level {
+                        terrain.init_block(x, y, z, BlockType::MAGMA);
+                        continue;
+                    }
 
-                // below ground
-                let c = caverns.get_3d(x_f32, y_f32, z_f32);
+                    // below ground
+                    let c = self.caverns.get_3d(x_f32, y_f32, z_f32);
+                    let depth = ((cavern_depth - (y + 1) as f32) / cavern_depth).abs();
 
-                let c_depth = cavern_depth * terrain.world_size_y() as f32;
-                let depth = ((c_depth - (y + 1) as f32) / c_depth).abs();
+                    if c > depth {
+                        let cave = self.caves.get_3d(x_f32, y_f32, z_f32);
+                        if cave < settings.cave_threshold {
+                            let block_type = if pool < 0.35 && y <= settings.magma_pool_level {
+                                BlockType::MAGMA
+                            } else if pool > 0.6 && y <= settings.water_level {
+                                BlockType::WATER
+                            } else {
+                                BlockType::EMPTY
+                            };
 
-                if c > depth {
-                    let cave = caves.get_3d(x_f32, y_f32, z_f32);
-                    if cave < 0.5 {
-                        terrain.init_block(x, y, z, BlockType::EMPTY);
-                        continue;
+                            terrain.init_block(x, y, z, block_type);
+                            continue;
+                        }
                     }
-                }
 
-                if y == surface {
-                    terrain.init_block(x, y, z, BlockType::GRASS);
-                } else if y > surface - dirt_depth {
-                    terrain.init_block(x, y, z, BlockType::DIRT);
-                } else {
-                    terrain.init_block(x, y, z, BlockType::STONE);
+                    if y == surface {
+                        terrain.init_block(x, y, z, biome.surface_block());
+                    } else if y > surface - dirt_depth {
+                        terrain.init_block(x, y, z, biome.subsurface_block());
+                    } else {
+                        let ore = self.ores.iter_mut().find_map(|ore| {
+                            if y < ore.min_y || y > ore.max_y {
+                                return None;
+                            }
+
+                            if ore.noise.get_3d(x_f32, y_f32, z_f32) > ore.threshold {
+                                Some(ore.block_type)
+                            } else {
+                                None
+                            }
+                        });
+
+                        terrain.init_block(x, y, z, ore.unwrap_or(BlockType::STONE));
+                    }
                 }
             }
         }
     }
+}
+
+/// Generates the chunks around the view, the rest of the world is generated
+/// by `stream_chunks` as the colony spreads out.
+pub fn setup_terrain(
+    mut cmd: Commands,
+    settings: Res<WorldGenSettings>,
+    streaming: Res<ChunkStreaming>,
+    mut terrain: ResMut<Terrain>,
+    mut ev_spawn_tree: EventWriter<SpawnTreeEvent>,
+) {
+    let mut generator = TerrainGenerator::new(&settings);
+    let [focus_x, focus_z] = streaming.focus_column(&terrain);
+
+    println!("generating {:?} world..", settings.preset);
+
+    for [chunk_x, chunk_z] in streaming.columns_around(&terrain, focus_x, focus_z, 0) {
+        generator.generate_column(&mut terrain, chunk_x, chunk_z, &mut ev_spawn_tree);
+    }
 
     println!("..done generating world");
+
+    cmd.insert_resource(generator);
 }
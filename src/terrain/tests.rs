@@ -0,0 +1,233 @@
+use bevy::ecs::{event::Events, system::RunSystemOnce, world::World};
+use ndshape::Shape;
+
+use crate::items::ToolMaterial;
+
+use super::{
+    setup_terrain, stream_chunks, BlockRegistry, BlockType, ChunkState, ChunkStreaming,
+    SpawnTreeEvent, Terrain, TimeOfDay, WorldGenSettings, WorldPreset, BUILTIN_BLOCKS, DAY_LENGTH,
+    HOURS_PER_DAY,
+};
+
+#[test]
+fn registry_defines_every_block_type() {
+    let registry = BlockRegistry::get();
+
+    for block_type in [
+        BlockType::OOB,
+        BlockType::EMPTY,
+        BlockType::DIRT,
+        BlockType::STONE,
+        BlockType::GRASS,
+        BlockType::LAMP,
+        BlockType::MAGMA,
+        BlockType::ASHLAR_LARGE,
+        BlockType::ASHLAR,
+        BlockType::TREE_TRUNK,
+        BlockType::LEAVES,
+        BlockType::WATER,
+        BlockType::SAND,
+        BlockType::SNOW,
+    ] {
+        assert!(
+            registry.iter().any(|d| d.id == block_type.0),
+            "block id {} is not defined",
+            block_type.0
+        );
+    }
+}
+
+#[test]
+fn builtin_blocks_load() {
+    assert!(BlockRegistry::from_ron(BUILTIN_BLOCKS).is_ok());
+}
+
+#[test]
+fn registry_rejects_duplicate_ids() {
+    let src = r#"[
+        (id: 0, name: "out of bounds"),
+        (id: 1, name: "empty"),
+        (id: 1, name: "air"),
+    ]"#;
+
+    assert!(BlockRegistry::from_ron(src).is_err());
+}
+
+#[test]
+fn registry_requires_empty() {
+    let src = r#"[(id: 0, name: "out of bounds")]"#;
+
+    assert!(BlockRegistry::from_ron(src).is_err());
+}
+
+#[test]
+fn registry_drops_default_to_one_for_any_tool() {
+    let src = r#"[
+        (id: 0, name: "out of bounds"),
+        (id: 1, name: "empty"),
+        (id: 2, name: "dirt", drops: [(commodity: Dirt, chance: 0.5)]),
+        (id: 3, name: "gold ore", drops: [
+            (commodity: OreGold, chance: 1.0, count: (2, 3), tool: Some(Copper)),
+        ]),
+    ]"#;
+
+    let registry = BlockRegistry::from_ron(src).unwrap();
+
+    let dirt = &registry.definition(BlockType::DIRT).drops[0];
+    assert_eq!(dirt.count, (1, 1));
+    assert!(dirt.is_tool_sufficient(None));
+
+    let gold = &registry.definition(BlockType::STONE).drops[0];
+    assert_eq!(gold.count, (2, 3));
+    assert!(!gold.is_tool_sufficient(None));
+    assert!(!gold.is_tool_sufficient(Some(ToolMaterial::Stone)));
+    assert!(gold.is_tool_sufficient(Some(ToolMaterial::Copper)));
+    assert!(gold.is_tool_sufficient(Some(ToolMaterial::Iron)));
+}
+
+#[test]
+fn daylight_follows_time_of_day() {
+    let at = |hour: f32| TimeOfDay {
+        elapsed: hour / HOURS_PER_DAY as f32 * DAY_LENGTH,
+    };
+
+    assert_eq!(at(0.).daylight(), 0.);
+    assert_eq!(at(12.).daylight(), 1.);
+    assert_eq!(at(23.).daylight(), 0.);
+
+    let dawn = at(6.).daylight();
+    assert!(dawn > 0. && dawn < 1.);
+    assert_eq!(at(13.5).hour(), 13);
+}
+
+fn generate_world(
+    settings: WorldGenSettings,
+    streaming: impl FnOnce(&mut ChunkStreaming),
+) -> World {
+    let [chunks_x, chunks_y, chunks_z] = settings.chunk_counts;
+    let terrain = Terrain::new(chunks_x, chunks_y, chunks_z, settings.chunk_size);
+    let mut world = World::new();
+    let mut chunk_streaming = ChunkStreaming::new(&terrain);
+
+    streaming(&mut chunk_streaming);
+
+    world.insert_resource(chunk_streaming);
+    world.insert_resource(terrain);
+    world.insert_resource(settings);
+    world.init_resource::<Events<SpawnTreeEvent>>();
+    world.run_system_once(setup_terrain);
+
+    world
+}
+
+fn generate(settings: WorldGenSettings) -> Terrain {
+    generate_world(settings, |_| {})
+        .remove_resource::<Terrain>()
+        .unwrap()
+}
+
+fn small_world(seed: u64, preset: WorldPreset) -> WorldGenSettings {
+    WorldGenSettings {
+        chunk_counts: [3, 3, 3],
+        ..WorldGenSettings::new(seed, preset)
+    }
+}
+
+fn blocks(terrain: &Terrain) -> Vec<BlockType> {
+    let [size_x, size_y, size_z] = [
+        terrain.world_size_x(),
+        terrain.world_size_y(),
+        terrain.world_size_z(),
+    ];
+
+    (0..size_x)
+        .flat_map(|x| (0..size_y).flat_map(move |y| (0..size_z).map(move |z| [x, y, z])))
+        .map(|[x, y, z]| terrain.get_block(x, y, z).block)
+        .collect()
+}
+
+#[test]
+fn world_gen_is_seeded() {
+    let a = generate(small_world(3, WorldPreset::Standard));
+    let b = generate(small_world(3, WorldPreset::Standard));
+    let c = generate(small_world(4, WorldPreset::Standard));
+
+    assert!(blocks(&a) == blocks(&b));
+    assert!(blocks(&a) != blocks(&c));
+}
+
+#[test]
+fn world_gen_presets_differ() {
+    let standard = generate(small_world(3, WorldPreset::Standard));
+    let flat = generate(small_world(3, WorldPreset::Flat));
+
+    assert!(blocks(&standard) != blocks(&flat));
+}
+
+#[test]
+fn world_gen_places_ores() {
+    let terrain = generate(small_world(3, WorldPreset::Standard));
+    let coal = BlockRegistry::get().find("coal ore").unwrap();
+
+    assert!(blocks(&terrain).contains(&coal));
+}
+
+#[test]
+fn paged_out_chunks_are_restored() {
+    let mut terrain = generate(small_world(3, WorldPreset::Standard));
+    let before = blocks(&terrain);
+
+    terrain.page_out_chunk(0).unwrap();
+
+    assert_eq!(terrain.get_chunk_state(0), ChunkState::PagedOut);
+    assert!(terrain.get_block(0, 0, 0).is_oob());
+
+    terrain.page_in_chunk(0).unwrap();
+
+    assert!(blocks(&terrain) == before);
+}
+
+#[test]
+fn chunks_stream_around_focus() {
+    let settings = WorldGenSettings {
+        chunk_counts: [8, 3, 1],
+        ..WorldGenSettings::new(3, WorldPreset::Flat)
+    };
+    let mut world = generate_world(settings, |streaming| {
+        streaming.radius = 1;
+        streaming.focus = [0, 0];
+    });
+    let column_state = |world: &World, chunk_x: u32| {
+        let terrain = world.resource::<Terrain>();
+        terrain.get_chunk_state(terrain.shape.linearize([chunk_x, 0, 0]))
+    };
+
+    assert_eq!(column_state(&world, 1), ChunkState::Resident);
+    assert_eq!(column_state(&world, 2), ChunkState::Ungenerated);
+
+    let first_column = column_blocks(world.resource::<Terrain>(), 0);
+
+    world.resource_mut::<ChunkStreaming>().focus = [127, 0];
+    world.run_system_once(stream_chunks);
+
+    assert_eq!(column_state(&world, 0), ChunkState::PagedOut);
+    assert_eq!(column_state(&world, 1), ChunkState::PagedOut);
+    assert_eq!(column_state(&world, 7), ChunkState::Resident);
+
+    world.resource_mut::<ChunkStreaming>().focus = [0, 0];
+    world.run_system_once(stream_chunks);
+
+    assert_eq!(column_state(&world, 0), ChunkState::Resident);
+    assert!(column_blocks(world.resource::<Terrain>(), 0) == first_column);
+}
+
+fn column_blocks(terrain: &Terrain, chunk_x: u32) -> Vec<BlockType> {
+    blocks(terrain)
+        .into_iter()
+        .skip(
+            (chunk_x * terrain.chunk_size * terrain.world_size_y() * terrain.world_size_z())
+                as usize,
+        )
+        .take((terrain.chunk_size * terrain.world_size_y() * terrain.world_size_z()) as usize)
+        .collect()
+}
@@ -0,0 +1,148 @@
+use bevy::ecs::system::Resource;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WorldPreset {
+    Standard,
+    Flat,
+    Mountains,
+    Caverns,
+}
+
+impl WorldPreset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Self::Standard),
+            "flat" => Some(Self::Flat),
+            "mountains" => Some(Self::Mountains),
+            "caverns" => Some(Self::Caverns),
+            _ => None,
+        }
+    }
+}
+
+/// A vein of `block` replacing stone between `min_y` and `max_y`, wherever
+/// its noise is above `threshold`. `block` is a name in the block registry.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OreSettings {
+    pub block: String,
+    pub min_y: u32,
+    pub max_y: u32,
+    pub frequency: f32,
+    pub threshold: f32,
+}
+
+/// Everything `TerrainGenerator` needs to generate a world. Saved with the
+/// input log, so a replay generates the same world.
+#[derive(Resource, Serialize, Deserialize, Clone)]
+pub struct WorldGenSettings {
+    pub seed: u64,
+    pub preset: WorldPreset,
+    /// chunks along x, y and z
+    pub chunk_counts: [u32; 3],
+    pub chunk_size: u32,
+    /// empty blocks above the highest possible surface
+    pub sky_height: u32,
+    pub mountain_height: u32,
+    pub height_frequency: f32,
+    pub height_octaves: i32,
+    pub magma_level: u32,
+    pub dirt_depth: u32,
+    /// height of the cavern layer, as a fraction of the world height
+    pub cavern_depth: f32,
+    pub cavern_frequency: f32,
+    pub cave_frequency: f32,
+    /// higher values carve more of the cavern layer out
+    pub cave_threshold: f32,
+    pub biome_frequency: f32,
+    pub tree_chance: f32,
+    pub ores: Vec<OreSettings>,
+    pub pool_frequency: f32,
+    /// caves below this are flooded with water, in wet regions
+    pub water_level: u32,
+    /// caves below this are flooded with magma, in hot regions
+    pub magma_pool_level: u32,
+}
+
+impl WorldGenSettings {
+    pub fn new(seed: u64, preset: WorldPreset) -> Self {
+        let standard = Self {
+            seed,
+            preset,
+            chunk_counts: [32, 3, 32],
+            chunk_size: 16,
+            sky_height: 8,
+            mountain_height: 38,
+            height_frequency: 0.01,
+            height_octaves: 8,
+            magma_level: 4,
+            dirt_depth: 3,
+            cavern_depth: 0.3,
+            cavern_frequency: 0.01,
+            cave_frequency: 0.02,
+            cave_threshold: 0.5,
+            biome_frequency: 0.004,
+            tree_chance: 0.01,
+            ores: vec![
+                OreSettings {
+                    block: "coal ore".to_string(),
+                    min_y: 8,
+                    max_y: 36,
+                    frequency: 0.08,
+                    threshold: 0.78,
+                },
+                OreSettings {
+                    block: "copper ore".to_string(),
+                    min_y: 6,
+                    max_y: 28,
+                    frequency: 0.07,
+                    threshold: 0.8,
+                },
+                OreSettings {
+                    block: "gold ore".to_string(),
+                    min_y: 5,
+                    max_y: 16,
+                    frequency: 0.1,
+                    threshold: 0.84,
+                },
+                OreSettings {
+                    block: "iron ore".to_string(),
+                    min_y: 4,
+                    max_y: 22,
+                    frequency: 0.07,
+                    threshold: 0.82,
+                },
+            ],
+            pool_frequency: 0.02,
+            water_level: 14,
+            magma_pool_level: 9,
+        };
+
+        match preset {
+            WorldPreset::Standard => standard,
+            WorldPreset::Flat => Self {
+                mountain_height: 4,
+                height_frequency: 0.004,
+                height_octaves: 2,
+                cave_threshold: 0.3,
+                ..standard
+            },
+            WorldPreset::Mountains => Self {
+                mountain_height: 64,
+                height_frequency: 0.012,
+                chunk_counts: [32, 5, 32],
+                tree_chance: 0.006,
+                ..standard
+            },
+            WorldPreset::Caverns => Self {
+                mountain_height: 12,
+                cavern_depth: 0.45,
+                cave_threshold: 0.7,
+                chunk_counts: [32, 4, 32],
+                water_level: 20,
+                magma_pool_level: 12,
+                ..standard
+            },
+        }
+    }
+}
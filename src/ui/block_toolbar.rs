@@ -1,6 +1,11 @@
 use bevy::{prelude::*, ui::FocusPolicy, utils::HashMap};
 
-use crate::{items::image_loader_settings, structures::BlueprintType, BlockType};
+use crate::{
+    colonists::{CreatureType, JOB_PRIORITY_DEFAULT, JOB_PRIORITY_MAX, JOB_PRIORITY_MIN},
+    items::image_loader_settings,
+    structures::BlueprintType,
+    BlockRegistry, BlockType,
+};
 
 use super::Tool;
 
@@ -63,10 +68,12 @@ pub fn on_toolbar_submenu_btn(
         }
     }
 
-    if current_submenu.is_some() && current_submenu != toolbar.submenu {
-        if let Some(entity) = toolbar.submenus.get(&current_submenu.unwrap()) {
-            cmd.entity(*entity).insert(Visibility::Hidden);
-        };
+    if let Some(submenu) = current_submenu {
+        if current_submenu != toolbar.submenu {
+            if let Some(entity) = toolbar.submenus.get(&submenu) {
+                cmd.entity(*entity).insert(Visibility::Hidden);
+            };
+        }
     }
 
     if let Some(submenu_type) = toolbar.submenu {
@@ -152,6 +159,15 @@ fn tool_btn(
         });
 }
 
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 fn toggle_submenu_btn(
     parent: &mut ChildBuilder,
     name: impl Into<String>,
@@ -196,7 +212,6 @@ fn toggle_submenu_btn(
                     font_size: 18.0,
                     font,
                     color: Color::rgb(0.9, 0.9, 0.9),
-                    ..default()
                 },
             ));
         });
@@ -305,6 +320,29 @@ pub fn setup_block_toolbar_ui(
         tool_group(p1, "ORDERS", fnt2.clone(), |p2| {
             tool_btn(p2, "Mine", Tool::Mine, Some(icon_pickaxe), fnt1.clone());
             tool_btn(p2, "Chop", Tool::Chop, Some(icon_axe), fnt1.clone());
+            tool_btn(p2, "Stockpile", Tool::Stockpile(vec![]), None, fnt1.clone());
+            tool_btn(p2, "Move", Tool::OrderMove, None, fnt1.clone());
+            tool_btn(
+                p2,
+                "Urgent",
+                Tool::Prioritize(JOB_PRIORITY_MAX),
+                None,
+                fnt1.clone(),
+            );
+            tool_btn(
+                p2,
+                "Normal",
+                Tool::Prioritize(JOB_PRIORITY_DEFAULT),
+                None,
+                fnt1.clone(),
+            );
+            tool_btn(
+                p2,
+                "Low",
+                Tool::Prioritize(JOB_PRIORITY_MIN),
+                None,
+                fnt1.clone(),
+            );
         });
     })
     .with_children(|p1| {
@@ -344,6 +382,27 @@ pub fn setup_block_toolbar_ui(
     .with_children(|p1| {
         tool_group(p1, "SPAWN", fnt2.clone(), |p2| {
             tool_btn(p2, "Colonist", Tool::SpawnColonist, None, fnt1.clone());
+            tool_btn(
+                p2,
+                "Cat",
+                Tool::SpawnCreature(CreatureType::Cat),
+                None,
+                fnt1.clone(),
+            );
+            tool_btn(
+                p2,
+                "Rabbit",
+                Tool::SpawnCreature(CreatureType::Rabbit),
+                None,
+                fnt1.clone(),
+            );
+            tool_btn(
+                p2,
+                "Crawler",
+                Tool::SpawnCreature(CreatureType::Crawler),
+                None,
+                fnt1.clone(),
+            );
             tool_btn(p2, "Axe", Tool::SpawnAxe, None, fnt1.clone());
             tool_btn(p2, "Pickaxe", Tool::SpawnPickaxe, None, fnt1.clone());
             toggle_submenu_btn(p2, "Block", None, fnt1.clone(), SubmenuType::Blocks);
@@ -371,48 +430,15 @@ pub fn setup_block_toolbar_ui(
         .with_children(|p1| {
             tool_group(p1, "BLOCKS", fnt2.clone(), |p2| {
                 tool_btn(p2, "Clear", Tool::ClearBlocks, None, fnt1.clone());
-                tool_btn(
-                    p2,
-                    "Stone",
-                    Tool::PlaceBlocks(BlockType::STONE),
-                    None,
-                    fnt1.clone(),
-                );
-                tool_btn(
-                    p2,
-                    "Grass",
-                    Tool::PlaceBlocks(BlockType::GRASS),
-                    None,
-                    fnt1.clone(),
-                );
-                tool_btn(
-                    p2,
-                    "Dirt",
-                    Tool::PlaceBlocks(BlockType::DIRT),
-                    None,
-                    fnt1.clone(),
-                );
-                tool_btn(
-                    p2,
-                    "Ashlar",
-                    Tool::PlaceBlocks(BlockType::ASHLAR),
-                    None,
-                    fnt1.clone(),
-                );
-                tool_btn(
-                    p2,
-                    "Smooth",
-                    Tool::PlaceBlocks(BlockType::ASHLAR_LARGE),
-                    None,
-                    fnt1.clone(),
-                );
-                tool_btn(
-                    p2,
-                    "Magma",
-                    Tool::PlaceBlocks(BlockType::MAGMA),
-                    None,
-                    fnt1.clone(),
-                );
+                for block in BlockRegistry::get().iter().filter(|b| b.is_placeable) {
+                    tool_btn(
+                        p2,
+                        capitalize(&block.name),
+                        Tool::PlaceBlocks(BlockType(block.id)),
+                        None,
+                        fnt1.clone(),
+                    );
+                }
             });
         })
         .id();
@@ -474,6 +500,13 @@ pub fn setup_block_toolbar_ui(
                     None,
                     fnt1.clone(),
                 );
+                tool_btn(
+                    p2,
+                    "Bed",
+                    Tool::SpawnStructure(BlueprintType::Bed),
+                    None,
+                    fnt1.clone(),
+                );
             });
         })
         .id();
@@ -1,5 +1,13 @@
-use bevy::ecs::system::Resource;
+use bevy::{
+    ecs::{
+        change_detection::DetectChanges,
+        system::{Res, ResMut, Resource},
+    },
+    time::{Time, Virtual},
+};
 
+/// `speed` scales how many fixed ticks run per second, while pausing is a
+/// simulation input so that paused ticks are replayed too.
 #[derive(Resource)]
 pub struct GameSpeed {
     pub speed: f32,
@@ -24,3 +32,9 @@ impl Default for GameSpeed {
         }
     }
 }
+
+pub fn apply_game_speed(game_speed: Res<GameSpeed>, mut time: ResMut<Time<Virtual>>) {
+    if game_speed.is_changed() {
+        time.set_relative_speed(game_speed.speed);
+    }
+}
@@ -3,22 +3,21 @@ use bevy::{
     ecs::{
         component::Component,
         entity::Entity,
-        event::{Event, EventReader},
-        query::{Changed, With, Without},
+        event::{Event, EventReader, EventWriter},
+        query::{Changed, Has, With, Without},
         system::{Commands, Query, Res, ResMut, Resource},
     },
-    hierarchy::BuildChildren,
+    hierarchy::{BuildChildren, Children},
     prelude::default,
     render::{color::Color, mesh::Mesh, view::Visibility},
     text::{Text, TextStyle},
     ui::{
         node_bundles::{ButtonBundle, NodeBundle, TextBundle},
-        AlignContent, AlignItems, BackgroundColor, Display, FlexDirection, FocusPolicy,
+        AlignContent, AlignItems, BackgroundColor, Display, FlexDirection, FlexWrap, FocusPolicy,
         Interaction, JustifyContent, PositionType, Style, UiRect, Val,
     },
 };
 use bevy_mod_picking::{
-    backends::raycast::bevy_mod_raycast::markers::SimplifiedMesh,
     events::{Down, Pointer},
     picking_core::Pickable,
     prelude::{ListenerInput, On},
@@ -26,10 +25,15 @@ use bevy_mod_picking::{
 };
 
 use crate::{
-    items::{Commodities, Commodity, CommodityData},
+    colonists::{
+        Colonist, Downed, Drafted, Health, Skill, Skills, WorkPreference, WorkPreferences,
+        WorkType, WORK_RANK_MAX, WORK_RANK_MIN,
+    },
+    items::{Commodities, Commodity, CraftBills, RecipeType},
     rendering::SlotIndex,
-    structures::PartSlots,
-    ui::{BTN_HOVERED, BTN_NONE, BTN_PRESSED, BTN_TOGGLED},
+    simulation::{SimInput, SimInputEvent},
+    structures::{PartSlots, Structure},
+    ui::{BTN_HOVERED, BTN_NONE, BTN_PRESSED},
 };
 
 #[derive(Resource)]
@@ -37,13 +41,32 @@ pub struct InspectorUi {
     pub selected: Option<Entity>,
     pub window: Entity,
     pub display_text: Entity,
+    pub details: Entity,
     pub slots: Entity,
     pub slot_0: Entity,
     pub slot_1: Entity,
     pub slot_2: Entity,
+    pub work: Entity,
+    pub bills: Entity,
+}
+
+/// Cycles the selected colonist's preference for the work type.
+#[derive(Component)]
+pub struct BtnWorkPreference(pub WorkType);
+
+/// Drafts or releases the selected colonist.
+#[derive(Component)]
+pub struct BtnDraft;
+
+/// Queues one craft of a recipe at the selected station, or clears its bills.
+#[derive(Component)]
+pub enum BtnCraftBill {
+    Add(RecipeType),
+    Clear,
 }
 
 #[derive(Event)]
+#[allow(dead_code)]
 pub struct InspectableClickedEvent(Entity, f32);
 
 impl From<ListenerInput<Pointer<Down>>> for InspectableClickedEvent {
@@ -58,6 +81,12 @@ pub fn update_inspector(
     q_inspectables: Query<&Inspectable>,
     q_slots: Query<&PartSlots>,
     q_commodities: Query<&Commodity>,
+    q_work: Query<&WorkPreferences>,
+    q_skills: Query<(&Skills, &Health, Has<Downed>)>,
+    q_bills: Query<&CraftBills>,
+    q_work_btns: Query<(&BtnWorkPreference, &Children)>,
+    q_draft_btns: Query<&Children, With<BtnDraft>>,
+    q_drafted: Query<Has<Drafted>>,
     commodities: Res<Commodities>,
     mut q_text: Query<&mut Text>,
 ) {
@@ -80,6 +109,41 @@ pub fn update_inspector(
         .value
         .clone_from(&inspectable.display_name);
 
+    if let Ok(mut details) = q_text.get_mut(inspector.details) {
+        details.sections[0].value =
+            if let Ok((skills, health, is_downed)) = q_skills.get(inspectable_e) {
+                let mut health = format!("Health {:.0}/{:.0}", health.value, health.max);
+
+                if is_downed {
+                    health.push_str(" (downed)");
+                }
+
+                std::iter::once(health)
+                    .chain(
+                        Skill::ALL
+                            .iter()
+                            .map(|s| format!("{} {}", s.label(), skills.level(*s))),
+                    )
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else if let Ok(bills) = q_bills.get(inspectable_e) {
+                bills
+                    .bills
+                    .iter()
+                    .map(|b| format!("{} x{}", b.recipe.label(), b.remaining))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else {
+                String::new()
+            };
+    }
+
+    if q_bills.contains(inspectable_e) {
+        cmd.entity(inspector.bills).insert(Visibility::Inherited);
+    } else {
+        cmd.entity(inspector.bills).insert(Visibility::Hidden);
+    }
+
     if let Ok(slots) = q_slots.get(inspectable_e) {
         if let Some(commodity) = get_commodity_data(slots, SlotIndex::Slot0, &q_commodities) {
             let commodity_data = commodities.0.get(&commodity).unwrap();
@@ -114,6 +178,159 @@ pub fn update_inspector(
     } else {
         cmd.entity(inspector.slots).insert(Visibility::Hidden);
     }
+
+    if let Ok(work) = q_work.get(inspectable_e) {
+        for (BtnWorkPreference(work_type), children) in q_work_btns.iter() {
+            let preference = work.get(*work_type);
+            let Some(mut txt) = children.first().and_then(|c| q_text.get_mut(*c).ok()) else {
+                continue;
+            };
+
+            txt.sections[0].value = if preference.is_enabled {
+                format!("{} {}", work_type.label(), preference.rank)
+            } else {
+                format!("{} -", work_type.label())
+            };
+        }
+
+        let is_drafted = q_drafted.get(inspectable_e).unwrap_or(false);
+
+        for children in q_draft_btns.iter() {
+            if let Some(mut txt) = children.first().and_then(|c| q_text.get_mut(*c).ok()) {
+                txt.sections[0].value = if is_drafted { "Drafted" } else { "Draft" }.into();
+            }
+        }
+
+        cmd.entity(inspector.work).insert(Visibility::Inherited);
+    } else {
+        cmd.entity(inspector.work).insert(Visibility::Hidden);
+    }
+}
+
+pub fn on_work_preference_btn(
+    inspector: Res<InspectorUi>,
+    q_colonists: Query<(&Colonist, &WorkPreferences)>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+    mut btn_query: Query<
+        (&Interaction, &BtnWorkPreference, &mut BackgroundColor),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, BtnWorkPreference(work_type), mut bkg) in &mut btn_query {
+        match *interaction {
+            Interaction::Pressed => {
+                bkg.0 = BTN_PRESSED;
+
+                let Some(selected) = inspector.selected else {
+                    continue;
+                };
+
+                let Ok((colonist, work)) = q_colonists.get(selected) else {
+                    continue;
+                };
+
+                // ranks 1 to 5, then disabled, then back to 1
+                let current = work.get(*work_type);
+                let preference = if !current.is_enabled {
+                    WorkPreference::new(WORK_RANK_MIN)
+                } else if current.rank >= WORK_RANK_MAX {
+                    WorkPreference {
+                        is_enabled: false,
+                        rank: current.rank,
+                    }
+                } else {
+                    WorkPreference::new(current.rank + 1)
+                };
+
+                ev_sim_input.send(SimInputEvent(SimInput::SetWorkPreference {
+                    colonist: colonist.id,
+                    work_type: *work_type,
+                    preference,
+                }));
+            }
+            Interaction::Hovered => {
+                bkg.0 = BTN_HOVERED;
+            }
+            Interaction::None => {
+                bkg.0 = BTN_NONE;
+            }
+        }
+    }
+}
+
+pub fn on_draft_btn(
+    inspector: Res<InspectorUi>,
+    q_colonists: Query<(&Colonist, Has<Drafted>)>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+    mut btn_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (With<BtnDraft>, Changed<Interaction>),
+    >,
+) {
+    for (interaction, mut bkg) in &mut btn_query {
+        match *interaction {
+            Interaction::Pressed => {
+                bkg.0 = BTN_PRESSED;
+
+                let Some(selected) = inspector.selected else {
+                    continue;
+                };
+
+                let Ok((colonist, is_drafted)) = q_colonists.get(selected) else {
+                    continue;
+                };
+
+                ev_sim_input.send(SimInputEvent(SimInput::SetDrafted {
+                    colonist: colonist.id,
+                    is_drafted: !is_drafted,
+                }));
+            }
+            Interaction::Hovered => {
+                bkg.0 = BTN_HOVERED;
+            }
+            Interaction::None => {
+                bkg.0 = BTN_NONE;
+            }
+        }
+    }
+}
+
+pub fn on_craft_bill_btn(
+    inspector: Res<InspectorUi>,
+    q_stations: Query<&Structure, With<CraftBills>>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+    mut btn_query: Query<(&Interaction, &BtnCraftBill, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    for (interaction, btn, mut bkg) in &mut btn_query {
+        match *interaction {
+            Interaction::Pressed => {
+                bkg.0 = BTN_PRESSED;
+
+                let Some(station) = inspector.selected.and_then(|e| q_stations.get(e).ok()) else {
+                    continue;
+                };
+
+                let input = match btn {
+                    BtnCraftBill::Add(recipe) => SimInput::AddCraftBill {
+                        station: station.position,
+                        recipe: *recipe,
+                        count: 1,
+                    },
+                    BtnCraftBill::Clear => SimInput::ClearCraftBills {
+                        station: station.position,
+                    },
+                };
+
+                ev_sim_input.send(SimInputEvent(input));
+            }
+            Interaction::Hovered => {
+                bkg.0 = BTN_HOVERED;
+            }
+            Interaction::None => {
+                bkg.0 = BTN_NONE;
+            }
+        }
+    }
 }
 
 fn get_commodity_data(
@@ -159,10 +376,13 @@ pub fn setup_inspector_ui(mut cmd: Commands, asset_server: Res<AssetServer>) {
     let fnt2 = asset_server.load("fonts/Averia_Serif/AveriaSerifLibre-Regular.ttf");
 
     let mut display_text = None;
+    let mut details = None;
     let mut slots = None;
     let mut slot_1 = None;
     let mut slot_2 = None;
     let mut slot_3 = None;
+    let mut work = None;
+    let mut bills = None;
 
     let inspector = cmd
         .spawn(NodeBundle {
@@ -201,6 +421,21 @@ pub fn setup_inspector_ui(mut cmd: Commands, asset_server: Res<AssetServer>) {
                 .id(),
             );
 
+            details = Some(
+                p1.spawn(TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 16.0,
+                            font: fnt1.clone(),
+                            color: Color::rgb(0.9, 0.9, 0.9),
+                        },
+                    ),
+                    ..default()
+                })
+                .id(),
+            );
+
             p1.spawn((
                 ButtonBundle {
                     style: Style {
@@ -295,6 +530,123 @@ pub fn setup_inspector_ui(mut cmd: Commands, asset_server: Res<AssetServer>) {
                 })
                 .id(),
             );
+
+            work = Some(
+                p1.spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        flex_wrap: FlexWrap::Wrap,
+                        row_gap: Val::Px(4.),
+                        column_gap: Val::Px(4.),
+                        display: Display::Flex,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|p2| {
+                    p2.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                                ..default()
+                            },
+                            background_color: BTN_NONE.into(),
+                            ..default()
+                        },
+                        BtnDraft,
+                    ))
+                    .with_children(|p3| {
+                        p3.spawn(TextBundle {
+                            text: Text::from_section(
+                                "Draft",
+                                TextStyle {
+                                    font_size: 16.0,
+                                    font: fnt1.clone(),
+                                    color: Color::rgb(0.9, 0.9, 0.9),
+                                },
+                            ),
+                            ..default()
+                        });
+                    });
+
+                    for work_type in WorkType::ALL {
+                        p2.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                                    ..default()
+                                },
+                                background_color: BTN_NONE.into(),
+                                ..default()
+                            },
+                            BtnWorkPreference(work_type),
+                        ))
+                        .with_children(|p3| {
+                            p3.spawn(TextBundle {
+                                text: Text::from_section(
+                                    work_type.label(),
+                                    TextStyle {
+                                        font_size: 16.0,
+                                        font: fnt1.clone(),
+                                        color: Color::rgb(0.9, 0.9, 0.9),
+                                    },
+                                ),
+                                ..default()
+                            });
+                        });
+                    }
+                })
+                .id(),
+            );
+
+            bills = Some(
+                p1.spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        flex_wrap: FlexWrap::Wrap,
+                        row_gap: Val::Px(4.),
+                        column_gap: Val::Px(4.),
+                        display: Display::Flex,
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                })
+                .with_children(|p2| {
+                    let buttons = RecipeType::ALL
+                        .iter()
+                        .map(|r| (BtnCraftBill::Add(*r), format!("+ {}", r.label())))
+                        .chain([(BtnCraftBill::Clear, "Clear".to_string())]);
+
+                    for (btn, label) in buttons {
+                        p2.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+                                    ..default()
+                                },
+                                background_color: BTN_NONE.into(),
+                                ..default()
+                            },
+                            btn,
+                        ))
+                        .with_children(|p3| {
+                            p3.spawn(TextBundle {
+                                text: Text::from_section(
+                                    label,
+                                    TextStyle {
+                                        font_size: 16.0,
+                                        font: fnt1.clone(),
+                                        color: Color::rgb(0.9, 0.9, 0.9),
+                                    },
+                                ),
+                                ..default()
+                            });
+                        });
+                    }
+                })
+                .id(),
+            );
         })
         .id();
 
@@ -302,10 +654,13 @@ pub fn setup_inspector_ui(mut cmd: Commands, asset_server: Res<AssetServer>) {
         selected: None,
         window: inspector,
         display_text: display_text.unwrap(),
+        details: details.unwrap(),
         slots: slots.unwrap(),
         slot_0: slot_1.unwrap(),
         slot_1: slot_2.unwrap(),
         slot_2: slot_3.unwrap(),
+        work: work.unwrap(),
+        bills: bills.unwrap(),
     });
 }
 
@@ -2,6 +2,7 @@ mod block_toolbar;
 mod game_speed;
 mod inspector;
 mod pointer_capture;
+mod save_toolbar;
 mod time_toolbar;
 mod tool;
 mod tools;
@@ -10,6 +11,7 @@ pub use block_toolbar::*;
 pub use game_speed::*;
 pub use inspector::*;
 pub use pointer_capture::*;
+pub use save_toolbar::*;
 pub use time_toolbar::*;
 pub use tool::*;
 pub use tools::*;
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use bevy::{
+    ecs::{event::EventWriter, system::Res},
+    input::{keyboard::KeyCode, ButtonInput},
+};
+use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
+
+use crate::persistence::{
+    LoadColonyEvent, SaveColonyEvent, SaveReplayEvent, DEFAULT_REPLAY_PATH, DEFAULT_SAVE_PATH,
+};
+
+pub fn save_toolbar(
+    mut contexts: EguiContexts,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut ev_save_colony: EventWriter<SaveColonyEvent>,
+    mut ev_load_colony: EventWriter<LoadColonyEvent>,
+    mut ev_save_replay: EventWriter<SaveReplayEvent>,
+) {
+    let mut save = key_input.just_released(KeyCode::F5);
+    let mut load = key_input.just_released(KeyCode::F9);
+    let mut save_replay = key_input.just_released(KeyCode::F6);
+
+    egui::Window::new("Colony").show(contexts.ctx_mut(), |ui| {
+        if ui.button("Save (F5)").clicked() {
+            save = true;
+        }
+
+        if ui.button("Load (F9)").clicked() {
+            load = true;
+        }
+
+        if ui.button("Save replay (F6)").clicked() {
+            save_replay = true;
+        }
+    });
+
+    if save {
+        ev_save_colony.send(SaveColonyEvent {
+            path: PathBuf::from(DEFAULT_SAVE_PATH),
+        });
+    }
+
+    if load {
+        ev_load_colony.send(LoadColonyEvent {
+            path: PathBuf::from(DEFAULT_SAVE_PATH),
+        });
+    }
+
+    if save_replay {
+        ev_save_replay.send(SaveReplayEvent {
+            path: PathBuf::from(DEFAULT_REPLAY_PATH),
+        });
+    }
+}
@@ -1,32 +1,58 @@
-use bevy::ecs::system::ResMut;
+use bevy::ecs::{
+    event::EventWriter,
+    system::{Res, ResMut},
+};
 use bevy_inspector_egui::{bevy_egui::EguiContexts, egui};
 
+use crate::{
+    simulation::{SimInput, SimInputEvent},
+    TimeOfDay,
+};
+
 use super::GameSpeed;
 
-pub fn job_toolbar(mut contexts: EguiContexts, mut game_speed: ResMut<GameSpeed>) {
+pub fn job_toolbar(
+    mut contexts: EguiContexts,
+    mut game_speed: ResMut<GameSpeed>,
+    time_of_day: Res<TimeOfDay>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+) {
+    let mut speed = None;
+
     egui::Window::new("Speed").show(contexts.ctx_mut(), |ui| {
+        let hour = time_of_day.hour_f32();
+        ui.label(format!(
+            "{:02}:{:02}",
+            hour as u32,
+            (hour.fract() * 60.) as u32
+        ));
+
         if ui.button("Pause").clicked() {
-            game_speed.is_paused = !game_speed.is_paused;
+            ev_sim_input.send(SimInputEvent(SimInput::SetPaused(!game_speed.is_paused)));
         }
 
         if ui.button("0.5x").clicked() {
-            game_speed.is_paused = false;
-            game_speed.speed = 0.5;
+            speed = Some(0.5);
         }
 
         if ui.button("1x").clicked() {
-            game_speed.is_paused = false;
-            game_speed.speed = 1.;
+            speed = Some(1.);
         }
 
         if ui.button("2x").clicked() {
-            game_speed.is_paused = false;
-            game_speed.speed = 2.;
+            speed = Some(2.);
         }
 
         if ui.button("4x").clicked() {
-            game_speed.is_paused = false;
-            game_speed.speed = 4.;
+            speed = Some(4.);
         }
     });
+
+    if let Some(speed) = speed {
+        game_speed.speed = speed;
+
+        if game_speed.is_paused {
+            ev_sim_input.send(SimInputEvent(SimInput::SetPaused(false)));
+        }
+    }
 }
@@ -10,14 +10,12 @@ use bevy::{
 };
 
 use crate::{
-    colonists::{
-        NavigationGraph, PartitionDebug, SpawnColonistEvent, SpawnJobChopEvent, SpawnJobMineEvent,
-        SpawnJobPlaceBlockEvent,
-    },
+    colonists::{Colonist, CreatureType, Drafted, ItemTag, NavigationGraph, PartitionDebug},
     common::min_max,
     controls::Raycast,
     debug::debug_settings::DebugSettings,
-    items::{SpawnAxeEvent, SpawnPickaxeEvent},
+    items::STOCKPILE_TILE_CAPACITY,
+    simulation::{SimInput, SimInputEvent},
     structures::BlueprintType,
     BlockType, Cursor, Terrain,
 };
@@ -30,13 +28,21 @@ pub enum Tool {
     TogglePathDebug,
     ClearBlocks,
     SpawnColonist,
+    SpawnCreature(CreatureType),
     SpawnPickaxe,
     SpawnStructure(BlueprintType),
     SpawnAxe,
+    #[allow(dead_code)]
     PlaceStone,
     BlockInfo,
     Mine,
     Chop,
+    /// stockpile for items with any of the tags, or every item when empty
+    Stockpile(Vec<ItemTag>),
+    /// sets the priority of jobs in the dragged area
+    Prioritize(u8),
+    /// sends every drafted colonist to the clicked block
+    OrderMove,
 }
 
 #[derive(Default)]
@@ -48,10 +54,10 @@ pub struct ToolState {
 pub fn tool_place_blocks(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
-    mut terrain: ResMut<Terrain>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut state: Local<ToolState>,
     mut cursor_query: Query<&mut Transform, With<Cursor>>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::PlaceBlocks(block_type) = toolbar.tool else {
         return;
@@ -85,13 +91,11 @@ pub fn tool_place_blocks(
 
         cursor.scale = Vec3::ZERO;
 
-        for x in min_x..=max_x {
-            for y in min_y..=max_y {
-                for z in min_z..=max_z {
-                    terrain.set_block_type(x, y, z, block_type);
-                }
-            }
-        }
+        ev_sim_input.send(SimInputEvent(SimInput::SetBlocks {
+            min: [min_x, min_y, min_z],
+            max: [max_x, max_y, max_z],
+            block_type,
+        }));
     }
 
     if state.is_dragging {
@@ -112,10 +116,10 @@ pub fn tool_place_blocks(
 pub fn tool_clear_block(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
-    mut terrain: ResMut<Terrain>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut state: Local<ToolState>,
     mut cursor_query: Query<&mut Transform, With<Cursor>>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::ClearBlocks = toolbar.tool else {
         return;
@@ -163,13 +167,11 @@ pub fn tool_clear_block(
 
         cursor.scale = Vec3::ZERO;
 
-        for x in min_x..=max_x {
-            for y in min_y..=max_y {
-                for z in min_z..=max_z {
-                    terrain.set_block_type(x, y, z, BlockType::EMPTY);
-                }
-            }
-        }
+        ev_sim_input.send(SimInputEvent(SimInput::SetBlocks {
+            min: [min_x, min_y, min_z],
+            max: [max_x, max_y, max_z],
+            block_type: BlockType::EMPTY,
+        }));
     }
 }
 
@@ -177,7 +179,7 @@ pub fn tool_spawn_colonist(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut ev_spawn_colonist: EventWriter<SpawnColonistEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::SpawnColonist = toolbar.tool else {
         return;
@@ -188,9 +190,62 @@ pub fn tool_spawn_colonist(
             return;
         }
 
-        ev_spawn_colonist.send(SpawnColonistEvent {
+        ev_sim_input.send(SimInputEvent(SimInput::SpawnColonist(raycast.adj_pos)));
+    }
+}
+
+pub fn tool_spawn_creature(
+    toolbar: Res<Toolbar>,
+    raycast: Res<Raycast>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    q_colonists: Query<&Colonist>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+) {
+    let Tool::SpawnCreature(creature_type) = toolbar.tool else {
+        return;
+    };
+
+    if mouse_input.just_released(MouseButton::Left) {
+        if !raycast.is_adj_hit {
+            return;
+        }
+
+        // pets go to the first colonist
+        let owner = match creature_type {
+            CreatureType::Cat => q_colonists.iter().map(|c| c.id).min(),
+            CreatureType::Rabbit | CreatureType::Crawler => None,
+        };
+
+        ev_sim_input.send(SimInputEvent(SimInput::SpawnCreature {
             pos: raycast.adj_pos,
-        });
+            creature_type,
+            owner,
+        }));
+    }
+}
+
+pub fn tool_order_move(
+    toolbar: Res<Toolbar>,
+    raycast: Res<Raycast>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    q_drafted: Query<&Colonist, With<Drafted>>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+) {
+    let Tool::OrderMove = toolbar.tool else {
+        return;
+    };
+
+    if mouse_input.just_released(MouseButton::Left) {
+        if !raycast.is_adj_hit {
+            return;
+        }
+
+        for colonist in q_drafted.iter() {
+            ev_sim_input.send(SimInputEvent(SimInput::OrderMove {
+                colonist: colonist.id,
+                pos: raycast.adj_pos,
+            }));
+        }
     }
 }
 
@@ -216,7 +271,12 @@ pub fn tool_block_info(
 
         let block = terrain.get_block_by_idx(chunk_idx, block_idx);
 
-        println!("sunlight={}, torchlight={}", block.sunlight, block.light);
+        println!(
+            "{}, sunlight={}, torchlight={}",
+            block.block.name(),
+            block.sunlight,
+            block.light
+        );
 
         let Some(partition_id) = terrain.get_partition_id(chunk_idx, block_idx) else {
             println!("no partition");
@@ -243,11 +303,10 @@ pub fn tool_block_info(
 pub fn tool_mine(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
-    terrain: Res<Terrain>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut state: Local<ToolState>,
     mut cursor_query: Query<&mut Transform, With<Cursor>>,
-    mut ev_spawn_job_mine: EventWriter<SpawnJobMineEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::Mine = toolbar.tool else {
         return;
@@ -295,26 +354,20 @@ pub fn tool_mine(
 
         cursor.scale = Vec3::ZERO;
 
-        for x in min_x..=max_x {
-            for y in min_y..=max_y {
-                for z in min_z..=max_z {
-                    if !terrain.get_block(x, y, z).is_empty() {
-                        ev_spawn_job_mine.send(SpawnJobMineEvent { pos: [x, y, z] });
-                    }
-                }
-            }
-        }
+        ev_sim_input.send(SimInputEvent(SimInput::Mine {
+            min: [min_x, min_y, min_z],
+            max: [max_x, max_y, max_z],
+        }));
     }
 }
 
 pub fn tool_chop(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
-    terrain: Res<Terrain>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut state: Local<ToolState>,
     mut cursor_query: Query<&mut Transform, With<Cursor>>,
-    mut ev_spawn_job_chop: EventWriter<SpawnJobChopEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::Chop = toolbar.tool else {
         return;
@@ -362,15 +415,10 @@ pub fn tool_chop(
 
         cursor.scale = Vec3::ZERO;
 
-        for x in min_x..=max_x {
-            for y in min_y..=max_y {
-                for z in min_z..=max_z {
-                    if !terrain.get_block(x, y, z).is_empty() {
-                        ev_spawn_job_chop.send(SpawnJobChopEvent { pos: [x, y, z] });
-                    }
-                }
-            }
-        }
+        ev_sim_input.send(SimInputEvent(SimInput::Chop {
+            min: [min_x, min_y, min_z],
+            max: [max_x, max_y, max_z],
+        }));
     }
 }
 
@@ -392,7 +440,7 @@ pub fn tool_spawn_pickaxe(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut ev_spawn_pickaxe: EventWriter<SpawnPickaxeEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::SpawnPickaxe = toolbar.tool else {
         return;
@@ -403,9 +451,7 @@ pub fn tool_spawn_pickaxe(
     }
 
     if mouse_input.just_released(MouseButton::Left) {
-        ev_spawn_pickaxe.send(SpawnPickaxeEvent {
-            pos: raycast.adj_pos,
-        });
+        ev_sim_input.send(SimInputEvent(SimInput::SpawnPickaxe(raycast.adj_pos)));
     }
 }
 
@@ -413,7 +459,7 @@ pub fn tool_spawn_axe(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut ev_spawn_axe: EventWriter<SpawnAxeEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::SpawnAxe = toolbar.tool else {
         return;
@@ -424,9 +470,7 @@ pub fn tool_spawn_axe(
     }
 
     if mouse_input.just_released(MouseButton::Left) {
-        ev_spawn_axe.send(SpawnAxeEvent {
-            pos: raycast.adj_pos,
-        });
+        ev_sim_input.send(SimInputEvent(SimInput::SpawnAxe(raycast.adj_pos)));
     }
 }
 
@@ -434,7 +478,7 @@ pub fn tool_place_stone(
     toolbar: Res<Toolbar>,
     raycast: Res<Raycast>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    mut ev_spawn_job_place_block: EventWriter<SpawnJobPlaceBlockEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::PlaceStone = toolbar.tool else {
         return;
@@ -445,9 +489,134 @@ pub fn tool_place_stone(
     }
 
     if mouse_input.just_released(MouseButton::Left) {
-        ev_spawn_job_place_block.send(SpawnJobPlaceBlockEvent {
+        ev_sim_input.send(SimInputEvent(SimInput::PlaceBlock {
             pos: raycast.adj_pos,
             block_type: BlockType::ASHLAR,
-        });
+        }));
+    }
+}
+
+pub fn tool_stockpile(
+    toolbar: Res<Toolbar>,
+    raycast: Res<Raycast>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: Local<ToolState>,
+    mut cursor_query: Query<&mut Transform, With<Cursor>>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+) {
+    let Tool::Stockpile(tags) = &toolbar.tool else {
+        return;
+    };
+
+    let mut cursor = cursor_query.get_single_mut().unwrap();
+
+    if mouse_input.just_released(MouseButton::Right) {
+        state.is_dragging = false;
+        cursor.scale = Vec3::ZERO;
+        return;
+    }
+
+    if mouse_input.just_released(MouseButton::Left) {
+        if !raycast.is_adj_hit {
+            state.is_dragging = false;
+            return;
+        }
+
+        if !state.is_dragging {
+            state.is_dragging = true;
+            state.start = raycast.adj_pos;
+            return;
+        }
+
+        state.is_dragging = false;
+
+        let [min_x, max_x] = min_max(state.start[0], raycast.adj_pos[0]);
+        let [min_y, max_y] = min_max(state.start[1], raycast.adj_pos[1]);
+        let [min_z, max_z] = min_max(state.start[2], raycast.adj_pos[2]);
+
+        cursor.scale = Vec3::ZERO;
+
+        ev_sim_input.send(SimInputEvent(SimInput::CreateStockpile {
+            min: [min_x, min_y, min_z],
+            max: [max_x, max_y, max_z],
+            tags: tags.clone(),
+            capacity: STOCKPILE_TILE_CAPACITY,
+        }));
+    }
+
+    if state.is_dragging {
+        let [min_x, max_x] = min_max(state.start[0], raycast.adj_pos[0]);
+        let [min_y, max_y] = min_max(state.start[1], raycast.adj_pos[1]);
+        let [min_z, max_z] = min_max(state.start[2], raycast.adj_pos[2]);
+
+        let scale = Vec3::new(
+            ((max_x - min_x) + 1) as f32,
+            ((max_y - min_y) + 1) as f32,
+            ((max_z - min_z) + 1) as f32,
+        );
+        cursor.scale = scale;
+        cursor.translation = Vec3::new(min_x as f32, min_y as f32, min_z as f32);
+    }
+}
+
+pub fn tool_prioritize(
+    toolbar: Res<Toolbar>,
+    raycast: Res<Raycast>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut state: Local<ToolState>,
+    mut cursor_query: Query<&mut Transform, With<Cursor>>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
+) {
+    let Tool::Prioritize(priority) = toolbar.tool else {
+        return;
+    };
+
+    let mut cursor = cursor_query.get_single_mut().unwrap();
+
+    if mouse_input.just_released(MouseButton::Right) {
+        state.is_dragging = false;
+        cursor.scale = Vec3::ZERO;
+        return;
+    }
+
+    if state.is_dragging {
+        let [min_x, max_x] = min_max(state.start[0], raycast.hit_pos[0]);
+        let [min_y, max_y] = min_max(state.start[1], raycast.hit_pos[1]);
+        let [min_z, max_z] = min_max(state.start[2], raycast.hit_pos[2]);
+
+        let scale = Vec3::new(
+            ((max_x - min_x) + 1) as f32,
+            ((max_y - min_y) + 1) as f32,
+            ((max_z - min_z) + 1) as f32,
+        );
+        cursor.scale = scale;
+        cursor.translation = Vec3::new(min_x as f32, min_y as f32, min_z as f32);
+    }
+
+    if mouse_input.just_released(MouseButton::Left) {
+        if !raycast.is_hit {
+            state.is_dragging = false;
+            return;
+        }
+
+        if !state.is_dragging {
+            state.is_dragging = true;
+            state.start = raycast.hit_pos;
+            return;
+        }
+
+        state.is_dragging = false;
+
+        let [min_x, max_x] = min_max(state.start[0], raycast.hit_pos[0]);
+        let [min_y, max_y] = min_max(state.start[1], raycast.hit_pos[1]);
+        let [min_z, max_z] = min_max(state.start[2], raycast.hit_pos[2]);
+
+        cursor.scale = Vec3::ZERO;
+
+        ev_sim_input.send(SimInputEvent(SimInput::SetJobPriority {
+            min: [min_x, min_y, min_z],
+            max: [max_x, max_y, max_z],
+            priority,
+        }));
     }
 }
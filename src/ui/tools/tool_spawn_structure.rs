@@ -9,8 +9,8 @@ use bevy::{
 };
 
 use crate::{
-    colonists::SpawnJobBuildEvent,
     controls::Raycast,
+    simulation::{SimInput, SimInputEvent},
     structures::{RemoveStructureEvent, SpawnStructureEvent, Structure, StructureMode},
     ui::{Tool, Toolbar},
 };
@@ -43,7 +43,7 @@ pub fn tool_spawn_structure(
     mut ev_spawn_structure: EventWriter<SpawnStructureEvent>,
     mut state: Local<StructurePlacementState>,
     mut ev_remove_structure: EventWriter<RemoveStructureEvent>,
-    mut ev_spawn_build_job: EventWriter<SpawnJobBuildEvent>,
+    mut ev_sim_input: EventWriter<SimInputEvent>,
 ) {
     let Tool::SpawnStructure(blueprint_type) = toolbar.tool else {
         if let Some(entity) = state.structure {
@@ -60,6 +60,9 @@ pub fn tool_spawn_structure(
             pos: raycast.adj_pos,
             entity: id,
             blueprint_type,
+            rotation: state.rotation,
+            is_flipped: state.is_flipped,
+            mode: StructureMode::Placing,
         });
         return;
     };
@@ -76,9 +79,14 @@ pub fn tool_spawn_structure(
 
     if mouse_input.just_released(MouseButton::Right) {
         if structure.is_valid {
-            structure.mode = StructureMode::Placed;
-            structure.is_dirty = true;
-            ev_spawn_build_job.send(SpawnJobBuildEvent { structure: entity });
+            // the preview is replaced by a structure spawned by the simulation
+            ev_sim_input.send(SimInputEvent(SimInput::PlaceStructure {
+                blueprint_type,
+                pos: structure.position,
+                rotation: structure.rotation,
+                is_flipped: structure.is_flipped,
+            }));
+            ev_remove_structure.send(RemoveStructureEvent { entity });
             state.structure = None;
             state.last_build_pos = raycast.adj_pos
         } else {